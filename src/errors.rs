@@ -0,0 +1,19 @@
+use pinocchio::error::ProgramError;
+
+/// Program errors returned as `ProgramError::Custom` where a generic error
+/// would not tell the client what to fix.
+#[repr(u32)]
+pub enum ReflexError {
+    /// The stake is smaller than the market's `min_stake`.
+    StakeBelowMinimum = 0,
+    /// The farmer's stake on this side would exceed `max_stake_per_farmer`.
+    FarmerStakeCapExceeded = 1,
+    /// The side's total stake would exceed `max_total_stake`.
+    MarketStakeCapExceeded = 2,
+}
+
+impl From<ReflexError> for ProgramError {
+    fn from(error: ReflexError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
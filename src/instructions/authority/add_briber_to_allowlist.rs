@@ -0,0 +1,98 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+
+use crate::{
+    states::{BriberAllowlist, Config},
+    utils::{Account, constants},
+};
+
+pub struct AddBriberToAllowlist<'a> {
+    accounts: AddBriberToAllowlistAccounts<'a>,
+}
+
+struct AddBriberToAllowlistAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+    briber: &'a AccountView,
+    briber_allowlist: &'a AccountView,
+    briber_allowlist_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AddBriberToAllowlistAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config, briber, briber_allowlist, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        let (briber_allowlist_address, briber_allowlist_bump) = Address::find_program_address(
+            &[constants::BRIBER_ALLOWLIST_SEED, briber.address().as_ref()],
+            &crate::ID,
+        );
+        if &briber_allowlist_address != briber_allowlist.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            authority,
+            config,
+            briber,
+            briber_allowlist,
+            briber_allowlist_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AddBriberToAllowlist<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: AddBriberToAllowlistAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> AddBriberToAllowlist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and authority
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // create briber allowlist account and set data
+        let bump_binding = &[self.accounts.briber_allowlist_bump];
+        let seeds = &[
+            Seed::from(constants::BRIBER_ALLOWLIST_SEED),
+            Seed::from(self.accounts.briber.address().as_ref()),
+            Seed::from(bump_binding),
+        ];
+        Account::init_pda::<BriberAllowlist>(
+            self.accounts.briber_allowlist,
+            self.accounts.authority,
+            seeds,
+        )?;
+
+        let mut briber_allowlist_data = self.accounts.briber_allowlist.try_borrow_mut()?;
+        let briber_allowlist = BriberAllowlist::load_mut(&mut briber_allowlist_data)?;
+
+        briber_allowlist.set_inner(
+            self.accounts.briber.address(),
+            self.accounts.briber_allowlist_bump,
+        );
+
+        Ok(())
+    }
+}
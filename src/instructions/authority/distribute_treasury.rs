@@ -0,0 +1,141 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    states::{Config, RevenueSplit},
+    utils::{Account, MintInterface, TokenAccountInterface, constants, math},
+};
+
+pub struct DistributeTreasury<'a> {
+    accounts: DistributeTreasuryAccounts<'a>,
+}
+
+struct DistributeTreasuryAccounts<'a> {
+    config: &'a AccountView,
+    revenue_split: &'a AccountView,
+    mint: &'a AccountView,
+    treasury: &'a AccountView,
+    destinations: &'a [AccountView], // one token account per recipient, in order
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for DistributeTreasuryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            config,
+            revenue_split,
+            mint,
+            treasury,
+            _token_program,
+            destinations @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::program_account_check(revenue_split)?;
+
+        let (treasury_address, _) = Address::find_program_address(
+            &[constants::TREASURY_SEED, mint.address().as_ref()],
+            &crate::ID,
+        );
+        if &treasury_address != treasury.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            config,
+            revenue_split,
+            mint,
+            treasury,
+            destinations,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for DistributeTreasury<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: DistributeTreasuryAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> DistributeTreasury<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and revenue split
+        let config_bump = {
+            let config_data = self.accounts.config.try_borrow()?;
+            let config = Config::load(&config_data)?;
+
+            let config_address =
+                Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+            if &config_address != self.accounts.config.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            config.bump
+        };
+
+        let revenue_split_data = self.accounts.revenue_split.try_borrow()?;
+        let revenue_split = RevenueSplit::load(&revenue_split_data)?;
+
+        let revenue_split_address = Address::derive_address(
+            &[constants::REVENUE_SPLIT_SEED],
+            Some(revenue_split.bump),
+            &crate::ID,
+        );
+        if &revenue_split_address != self.accounts.revenue_split.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if self.accounts.destinations.len() != revenue_split.recipient_count() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let balance = { TokenAccount::from_account_view(self.accounts.treasury)?.amount() };
+        if balance == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let bump_binding = &[config_bump];
+        let seeds = &[Seed::from(constants::CONFIG_SEED), Seed::from(bump_binding)];
+
+        // every recipient but the first gets its rounded down share
+        let mut first_share = balance;
+        for (i, destination) in self.accounts.destinations.iter().enumerate().skip(1) {
+            TokenAccountInterface::owner_check(destination, &revenue_split.recipient(i))?;
+            TokenAccountInterface::mint_check(destination, self.accounts.mint.address())?;
+
+            let share = math::fee_calculation(balance, revenue_split.weight_bps(i))?;
+            if share > 0 {
+                MintInterface::transfer_signed(
+                    self.accounts.treasury,
+                    destination,
+                    self.accounts.config,
+                    share,
+                    seeds,
+                )?;
+            }
+            first_share = first_share
+                .checked_sub(share)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        // the first recipient also gets the rounding dust
+        let destination = &self.accounts.destinations[0];
+        TokenAccountInterface::owner_check(destination, &revenue_split.recipient(0))?;
+        TokenAccountInterface::mint_check(destination, self.accounts.mint.address())?;
+
+        MintInterface::transfer_signed(
+            self.accounts.treasury,
+            destination,
+            self.accounts.config,
+            first_share,
+            seeds,
+        )
+    }
+}
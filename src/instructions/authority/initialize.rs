@@ -1,7 +1,7 @@
 use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
 
 use crate::{
-    states::Config,
+    states::{Config, MarketCreationMode},
     utils::{Account, MintInterface, TokenAccountInterface, constants},
 };
 
@@ -26,6 +26,9 @@ pub struct InitializeAccounts<'a> {
 pub struct InitializeData {
     fee_bps: u16,
     briber_fee_bps: u16,
+    min_briber_fee_bps: u16,
+    max_briber_fee_bps: u16,
+    market_creation_mode: MarketCreationMode,
 }
 
 // --- IMPLEMENTATIONS ---
@@ -34,20 +37,32 @@ impl<'a> TryFrom<&'a [u8]> for InitializeData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<Self>() {
+        // 2 bytes each for the four fees, 1 for the market creation mode
+        if data.len() != 9 {
             return Err(ProgramError::InvalidInstructionData);
         };
 
         let fee_bps = u16::from_le_bytes(data[..2].try_into().unwrap());
         let briber_fee_bps = u16::from_le_bytes(data[2..4].try_into().unwrap());
-
-        if fee_bps > 5_000 || briber_fee_bps > 5_000 {
+        let min_briber_fee_bps = u16::from_le_bytes(data[4..6].try_into().unwrap());
+        let max_briber_fee_bps = u16::from_le_bytes(data[6..8].try_into().unwrap());
+        let market_creation_mode = MarketCreationMode::try_from(data[8])?;
+
+        // the default briber fee must sit within the bounds
+        if fee_bps > 5_000
+            || max_briber_fee_bps > 5_000
+            || min_briber_fee_bps > briber_fee_bps
+            || briber_fee_bps > max_briber_fee_bps
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
 
         Ok(Self {
             fee_bps,
             briber_fee_bps,
+            min_briber_fee_bps,
+            max_briber_fee_bps,
+            market_creation_mode,
         })
     }
 }
@@ -138,6 +153,9 @@ impl<'a> Initialize<'a> {
             self.accounts.authority.address(),
             self.data.fee_bps,
             self.data.briber_fee_bps,
+            self.data.min_briber_fee_bps,
+            self.data.max_briber_fee_bps,
+            self.data.market_creation_mode,
             self.accounts.config_bump,
         );
 
@@ -0,0 +1,115 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::{
+        AccountHeader, BRIBER_ALLOWLIST_DISCRIMINATOR, BRIBER_CONTRIBUTION_DISCRIMINATOR,
+        BriberAllowlist, BriberContribution, CONFIG_DISCRIMINATOR, Config,
+        FARMER_POSITION_DISCRIMINATOR, FarmerPosition, MARKET_DISCRIMINATOR,
+        MARKET_METADATA_DISCRIMINATOR, Market, MarketMetadata, OUTCOME_MINTS_DISCRIMINATOR,
+        OutcomeMints, REVENUE_SPLIT_DISCRIMINATOR, RevenueSplit,
+        SETTLEMENT_COMMITTEE_DISCRIMINATOR, SETTLEMENT_VOTES_DISCRIMINATOR, SettlementCommittee,
+        SettlementVotes, Versioned,
+    },
+    utils::Account,
+};
+
+pub struct MigrateAccount<'a> {
+    accounts: MigrateAccountAccounts<'a>,
+    data: &'a [u8],
+}
+
+struct MigrateAccountAccounts<'a> {
+    payer: &'a AccountView,
+    account: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MigrateAccountAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [payer, account, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(payer)?;
+        Account::program_account_check(account)?;
+
+        Ok(Self { payer, account })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for MigrateAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        // data is kind specific and validated by each layout's migration
+        Ok(Self {
+            accounts: MigrateAccountAccounts::try_from(accounts)?,
+            data,
+        })
+    }
+}
+
+impl<'a> MigrateAccount<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&self) -> ProgramResult {
+        // headerless accounts are identified by their legacy size
+        let (discriminator, version) = {
+            let data = self.accounts.account.try_borrow()?;
+
+            match data.len() {
+                Config::LEGACY_LEN => (CONFIG_DISCRIMINATOR, 0),
+                Market::LEGACY_LEN => (MARKET_DISCRIMINATOR, 0),
+                FarmerPosition::LEGACY_LEN => (FARMER_POSITION_DISCRIMINATOR, 0),
+                _ => {
+                    let header = AccountHeader::load(&data)?;
+                    (header.discriminator(), header.version())
+                }
+            }
+        };
+
+        match discriminator {
+            CONFIG_DISCRIMINATOR => self.migrate::<Config>(version),
+            MARKET_DISCRIMINATOR => self.migrate::<Market>(version),
+            FARMER_POSITION_DISCRIMINATOR => self.migrate::<FarmerPosition>(version),
+            MARKET_METADATA_DISCRIMINATOR => self.migrate::<MarketMetadata>(version),
+            BRIBER_ALLOWLIST_DISCRIMINATOR => self.migrate::<BriberAllowlist>(version),
+            BRIBER_CONTRIBUTION_DISCRIMINATOR => self.migrate::<BriberContribution>(version),
+            REVENUE_SPLIT_DISCRIMINATOR => self.migrate::<RevenueSplit>(version),
+            SETTLEMENT_COMMITTEE_DISCRIMINATOR => self.migrate::<SettlementCommittee>(version),
+            SETTLEMENT_VOTES_DISCRIMINATOR => self.migrate::<SettlementVotes>(version),
+            OUTCOME_MINTS_DISCRIMINATOR => self.migrate::<OutcomeMints>(version),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn migrate<T: Versioned>(&self, version: u8) -> ProgramResult {
+        // already up to date
+        if version == T::VERSION {
+            return Ok(());
+        }
+
+        let old_len = T::len_for_version(version).ok_or(ProgramError::InvalidAccountData)?;
+        if version > T::VERSION || self.accounts.account.data_len() != old_len {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // grow the account, topping up rent from the payer
+        Account::realloc(self.accounts.account, self.accounts.payer, T::LEN)?;
+
+        let mut data = self.accounts.account.try_borrow_mut()?;
+
+        // make room for the header in front of the legacy layout
+        if version == 0 {
+            data.copy_within(..T::LEGACY_LEN, AccountHeader::LEN);
+        }
+
+        AccountHeader::load_mut(&mut data)?.set_inner(T::DISCRIMINATOR, T::VERSION);
+        T::check_header(&data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        let account = unsafe { &mut *(data.as_mut_ptr() as *mut T) };
+        account.migrate(self.accounts.account.address(), version, self.data)
+    }
+}
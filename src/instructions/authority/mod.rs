@@ -1,6 +1,24 @@
+mod add_briber_to_allowlist;
+mod distribute_treasury;
 mod initialize;
+mod migrate_account;
+mod register_outcome_mints;
+mod remove_briber_from_allowlist;
+mod set_dispute_window;
+mod set_revenue_split;
+mod set_settlement_committee;
+mod set_settlement_oracle;
 mod settle_market;
+mod settle_market_with_attestation;
 mod update_config;
+mod vote_settlement;
 mod withdraw_treasury;
+mod withdraw_treasury_mint;
 
-pub use {initialize::*, settle_market::*, update_config::*, withdraw_treasury::*};
+pub use {
+    add_briber_to_allowlist::*, distribute_treasury::*, initialize::*, migrate_account::*,
+    register_outcome_mints::*, remove_briber_from_allowlist::*, set_dispute_window::*,
+    set_revenue_split::*, set_settlement_committee::*, set_settlement_oracle::*, settle_market::*,
+    settle_market_with_attestation::*, update_config::*, vote_settlement::*, withdraw_treasury::*,
+    withdraw_treasury_mint::*,
+};
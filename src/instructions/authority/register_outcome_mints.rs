@@ -0,0 +1,129 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+
+use crate::{
+    states::{Config, MarketSeed, OutcomeMints},
+    utils::{Account, constants},
+};
+
+pub struct RegisterOutcomeMints<'a> {
+    accounts: RegisterOutcomeMintsAccounts<'a>,
+    data: RegisterOutcomeMintsData<'a>,
+}
+
+struct RegisterOutcomeMintsAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+    outcome_mints: &'a AccountView,
+    outcome_yes_mint: &'a AccountView,
+    outcome_no_mint: &'a AccountView,
+}
+
+struct RegisterOutcomeMintsData<'a> {
+    id: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for RegisterOutcomeMintsData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < constants::MIN_ID_LENGTH || data.len() > constants::MAX_ID_LENGTH {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { id: data })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RegisterOutcomeMintsAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            authority,
+            config,
+            outcome_mints,
+            outcome_yes_mint,
+            outcome_no_mint,
+            _system_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        if outcome_yes_mint.address() == outcome_no_mint.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            authority,
+            config,
+            outcome_mints,
+            outcome_yes_mint,
+            outcome_no_mint,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for RegisterOutcomeMints<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: RegisterOutcomeMintsAccounts::try_from(accounts)?,
+            data: RegisterOutcomeMintsData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> RegisterOutcomeMints<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &39;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and authority
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // keyed by the same hashed id as the market PDA
+        let market_seed = MarketSeed::from_id(self.data.id);
+        let (outcome_mints_address, outcome_mints_bump) = Address::find_program_address(
+            &[constants::OUTCOME_MINTS_SEED, market_seed.as_ref()],
+            &crate::ID,
+        );
+        if &outcome_mints_address != self.accounts.outcome_mints.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // create outcome mints account and set data
+        let bump_binding = &[outcome_mints_bump];
+        let seeds = &[
+            Seed::from(constants::OUTCOME_MINTS_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+        Account::init_pda::<OutcomeMints>(
+            self.accounts.outcome_mints,
+            self.accounts.authority,
+            seeds,
+        )?;
+
+        let mut outcome_mints_data = self.accounts.outcome_mints.try_borrow_mut()?;
+        OutcomeMints::load_mut(&mut outcome_mints_data)?.set_inner(
+            self.accounts.outcome_yes_mint.address(),
+            self.accounts.outcome_no_mint.address(),
+            outcome_mints_bump,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::{BriberAllowlist, Config},
+    utils::{Account, constants},
+};
+
+pub struct RemoveBriberFromAllowlist<'a> {
+    accounts: RemoveBriberFromAllowlistAccounts<'a>,
+}
+
+struct RemoveBriberFromAllowlistAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+    briber_allowlist: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemoveBriberFromAllowlistAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config, briber_allowlist] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+        Account::program_account_check(briber_allowlist)?;
+
+        Ok(Self {
+            authority,
+            config,
+            briber_allowlist,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RemoveBriberFromAllowlist<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: RemoveBriberFromAllowlistAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> RemoveBriberFromAllowlist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and authority
+        {
+            let config_data = self.accounts.config.try_borrow()?;
+            let config = Config::load(&config_data)?;
+
+            let config_address =
+                Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+            if &config_address != self.accounts.config.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if &config.authority() != self.accounts.authority.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // check briber allowlist
+        {
+            let briber_allowlist_data = self.accounts.briber_allowlist.try_borrow()?;
+            let briber_allowlist = BriberAllowlist::load(&briber_allowlist_data)?;
+
+            let briber_allowlist_address = Address::derive_address(
+                &[
+                    constants::BRIBER_ALLOWLIST_SEED,
+                    briber_allowlist.briber().as_ref(),
+                ],
+                Some(briber_allowlist.bump),
+                &crate::ID,
+            );
+            if &briber_allowlist_address != self.accounts.briber_allowlist.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // close briber allowlist, returning rent to the authority
+        Account::close(self.accounts.briber_allowlist, self.accounts.authority)
+    }
+}
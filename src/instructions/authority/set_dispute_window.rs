@@ -0,0 +1,85 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Config,
+    utils::{Account, constants},
+};
+
+pub struct SetDisputeWindow<'a> {
+    accounts: SetDisputeWindowAccounts<'a>,
+    data: SetDisputeWindowData,
+}
+
+struct SetDisputeWindowAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+}
+
+struct SetDisputeWindowData {
+    dispute_window: i64, // seconds, 0 opens claims at settlement
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetDisputeWindowData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let dispute_window = i64::from_le_bytes(data.try_into().unwrap());
+        if !(0..=constants::MAX_DISPUTE_WINDOW).contains(&dispute_window) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { dispute_window })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetDisputeWindowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        Ok(Self { authority, config })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetDisputeWindow<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetDisputeWindowAccounts::try_from(accounts)?,
+            data: SetDisputeWindowData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetDisputeWindow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &27;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut config_data = self.accounts.config.try_borrow_mut()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // only markets settled from now on take the new window
+        config.set_dispute_window(self.data.dispute_window);
+
+        Ok(())
+    }
+}
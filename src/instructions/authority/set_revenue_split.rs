@@ -0,0 +1,123 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+
+use crate::{
+    states::{Config, RevenueSplit},
+    utils::{Account, constants},
+};
+
+pub struct SetRevenueSplit<'a> {
+    accounts: SetRevenueSplitAccounts<'a>,
+    data: SetRevenueSplitData<'a>,
+}
+
+struct SetRevenueSplitAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+    revenue_split: &'a AccountView,
+    revenue_split_bump: u8,
+}
+
+struct SetRevenueSplitData<'a> {
+    recipients: &'a [u8], // packed (address, u16 weight_bps) entries
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetRevenueSplitData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty()
+            || !data.len().is_multiple_of(constants::REVENUE_RECIPIENT_LEN)
+            || data.len() > constants::REVENUE_RECIPIENT_LEN * constants::MAX_REVENUE_RECIPIENTS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // weights must cover the whole treasury balance
+        let mut total_weight_bps = 0u32;
+        for entry in data.chunks_exact(constants::REVENUE_RECIPIENT_LEN) {
+            let weight_bps = u16::from_le_bytes(entry[32..].try_into().unwrap());
+            if weight_bps == 0 {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            total_weight_bps += weight_bps as u32;
+        }
+        if total_weight_bps != 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { recipients: data })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetRevenueSplitAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config, revenue_split, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        let (revenue_split_address, revenue_split_bump) =
+            Address::find_program_address(&[constants::REVENUE_SPLIT_SEED], &crate::ID);
+        if &revenue_split_address != revenue_split.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            authority,
+            config,
+            revenue_split,
+            revenue_split_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetRevenueSplit<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetRevenueSplitAccounts::try_from(accounts)?,
+            data: SetRevenueSplitData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetRevenueSplit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and authority
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // create the revenue split on first use, then replace its recipients
+        let bump_binding = &[self.accounts.revenue_split_bump];
+        let seeds = &[
+            Seed::from(constants::REVENUE_SPLIT_SEED),
+            Seed::from(bump_binding),
+        ];
+        Account::init_if_needed::<RevenueSplit>(
+            self.accounts.revenue_split,
+            self.accounts.authority,
+            seeds,
+        )?;
+
+        let mut revenue_split_data = self.accounts.revenue_split.try_borrow_mut()?;
+        RevenueSplit::load_mut(&mut revenue_split_data)?
+            .set_inner(self.data.recipients, self.accounts.revenue_split_bump);
+
+        Ok(())
+    }
+}
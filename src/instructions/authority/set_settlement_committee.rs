@@ -0,0 +1,134 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+
+use crate::{
+    states::{Config, SettlementCommittee},
+    utils::{Account, constants},
+};
+
+pub struct SetSettlementCommittee<'a> {
+    accounts: SetSettlementCommitteeAccounts<'a>,
+    data: SetSettlementCommitteeData<'a>,
+}
+
+struct SetSettlementCommitteeAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+    settlement_committee: &'a AccountView,
+    settlement_committee_bump: u8,
+}
+
+struct SetSettlementCommitteeData<'a> {
+    threshold: u8,
+    members: &'a [u8], // packed addresses
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetSettlementCommitteeData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let [threshold, members @ ..] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        if members.is_empty()
+            || !members.len().is_multiple_of(32)
+            || members.len() > 32 * constants::MAX_COMMITTEE_MEMBERS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // the quorum must be reachable by the committee
+        let member_count = members.len() / 32;
+        if *threshold == 0 || *threshold as usize > member_count {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // every member votes once, so duplicates would lower the quorum
+        for (i, member) in members.chunks_exact(32).enumerate() {
+            if member == [0; 32] || members[..i * 32].chunks_exact(32).any(|m| m == member) {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        Ok(Self {
+            threshold: *threshold,
+            members,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetSettlementCommitteeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config, settlement_committee, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        let (settlement_committee_address, settlement_committee_bump) =
+            Address::find_program_address(&[constants::SETTLEMENT_COMMITTEE_SEED], &crate::ID);
+        if &settlement_committee_address != settlement_committee.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            authority,
+            config,
+            settlement_committee,
+            settlement_committee_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetSettlementCommittee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetSettlementCommitteeAccounts::try_from(accounts)?,
+            data: SetSettlementCommitteeData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetSettlementCommittee<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and authority
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // create the committee on first use, then replace its members
+        let bump_binding = &[self.accounts.settlement_committee_bump];
+        let seeds = &[
+            Seed::from(constants::SETTLEMENT_COMMITTEE_SEED),
+            Seed::from(bump_binding),
+        ];
+        Account::init_if_needed::<SettlementCommittee>(
+            self.accounts.settlement_committee,
+            self.accounts.authority,
+            seeds,
+        )?;
+
+        let mut settlement_committee_data = self.accounts.settlement_committee.try_borrow_mut()?;
+        SettlementCommittee::load_mut(&mut settlement_committee_data)?.set_inner(
+            self.data.threshold,
+            self.data.members,
+            self.accounts.settlement_committee_bump,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Config,
+    utils::{Account, constants},
+};
+
+pub struct SetSettlementOracle<'a> {
+    accounts: SetSettlementOracleAccounts<'a>,
+    data: SetSettlementOracleData,
+}
+
+struct SetSettlementOracleAccounts<'a> {
+    authority: &'a AccountView,
+    config: &'a AccountView,
+}
+
+struct SetSettlementOracleData {
+    settlement_oracle: Address, // zero address disables attestation settlement
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetSettlementOracleData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let settlement_oracle = Address::new_from_array(data.try_into().unwrap());
+
+        Ok(Self { settlement_oracle })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetSettlementOracleAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        Ok(Self { authority, config })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetSettlementOracle<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetSettlementOracleAccounts::try_from(accounts)?,
+            data: SetSettlementOracleData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetSettlementOracle<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut config_data = self.accounts.config.try_borrow_mut()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config.authority() != self.accounts.authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        config.set_settlement_oracle(&self.data.settlement_oracle);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
-use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
 
 use crate::{
     states::{Config, Market},
@@ -7,7 +11,7 @@ use crate::{
 
 pub struct SettleMarket<'a> {
     accounts: SettleMarketAccounts<'a>,
-    data: SettleMarketData<'a>,
+    data: SettleMarketData,
 }
 
 struct SettleMarketAccounts<'a> {
@@ -16,18 +20,15 @@ struct SettleMarketAccounts<'a> {
     market: &'a AccountView,
 }
 
-#[repr(C, packed)]
-struct SettleMarketData<'a> {
+struct SettleMarketData {
     resolution: u8, // 1 = Yes, 2 = No
-    id: &'a [u8],
 }
 
-impl<'a> TryFrom<&'a [u8]> for SettleMarketData<'a> {
+impl<'a> TryFrom<&'a [u8]> for SettleMarketData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        // 1 byte for resolution, rest for ID
-        if data.len() < constants::MIN_ID_LENGTH + 1 {
+        if data.len() != size_of::<Self>() {
             return Err(ProgramError::InvalidInstructionData);
         };
 
@@ -36,9 +37,7 @@ impl<'a> TryFrom<&'a [u8]> for SettleMarketData<'a> {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let id = &data[1..];
-
-        Ok(Self { id, resolution })
+        Ok(Self { resolution })
     }
 }
 
@@ -92,18 +91,21 @@ impl<'a> SettleMarket<'a> {
         let market = Market::load_mut(&mut market_data)?;
 
         let market_address = Address::derive_address(
-            &[constants::MARKET_SEED, self.data.id],
+            &[constants::MARKET_SEED, market.seed().as_ref()],
             Some(market.bump),
             &crate::ID,
         );
         if &market_address != self.accounts.market.address() {
             return Err(ProgramError::InvalidAccountData);
         }
-        if market.is_settled() {
+        // the authority also resolves disputed markets, and corrects a
+        // resolution until its dispute window is over
+        let now = Clock::get()?.unix_timestamp;
+        if market.is_final(now) {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        market.set_resolution(self.data.resolution);
+        market.set_resolution(self.data.resolution, now, config.dispute_window());
 
         Ok(())
     }
@@ -0,0 +1,141 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::{Config, Market},
+    utils::{Ed25519, constants},
+};
+
+pub struct SettleMarketWithAttestation<'a> {
+    accounts: SettleMarketWithAttestationAccounts<'a>,
+    data: SettleMarketWithAttestationData,
+}
+
+struct SettleMarketWithAttestationAccounts<'a> {
+    config: &'a AccountView,
+    market: &'a AccountView,
+    instructions_sysvar: &'a AccountView,
+}
+
+struct SettleMarketWithAttestationData {
+    resolution: u8, // 1 = Yes, 2 = No
+    timestamp: i64, // when the oracle observed the resolution
+}
+
+impl<'a> TryFrom<&'a [u8]> for SettleMarketWithAttestationData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1 byte for resolution, 8 for timestamp
+        if data.len() != 9 {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let resolution = data[0];
+        if resolution != 1 && resolution != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let timestamp = i64::from_le_bytes(data[1..9].try_into().unwrap());
+
+        Ok(Self {
+            resolution,
+            timestamp,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SettleMarketWithAttestationAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [config, market, instructions_sysvar] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config,
+            market,
+            instructions_sysvar,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SettleMarketWithAttestation<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SettleMarketWithAttestationAccounts::try_from(accounts)?,
+            data: SettleMarketWithAttestationData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SettleMarketWithAttestation<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config and oracle
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let settlement_oracle = config
+            .settlement_oracle()
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        // check market
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // disputed markets are left to the authority
+        if !market.is_open() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // the resolution must be observed during the market lifetime
+        let now = Clock::get()?.unix_timestamp;
+        if self.data.timestamp < market.creation_timestamp() || self.data.timestamp > now {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // the oracle signs (domain, program id, resolution, timestamp, market id), so
+        // its signatures for other programs or purposes cannot be replayed here
+        let id = market.id();
+        let prefix_len = constants::ATTESTATION_DOMAIN.len() + 32;
+        let mut message = [0u8; constants::MAX_ATTESTATION_LENGTH];
+        message[..constants::ATTESTATION_DOMAIN.len()]
+            .copy_from_slice(constants::ATTESTATION_DOMAIN);
+        message[constants::ATTESTATION_DOMAIN.len()..prefix_len]
+            .copy_from_slice(crate::ID.as_ref());
+        message[prefix_len] = self.data.resolution;
+        message[prefix_len + 1..prefix_len + 9].copy_from_slice(&self.data.timestamp.to_le_bytes());
+        message[prefix_len + 9..prefix_len + 9 + id.len()].copy_from_slice(id);
+
+        Ed25519::signature_check(
+            self.accounts.instructions_sysvar,
+            &settlement_oracle,
+            &message[..prefix_len + 9 + id.len()],
+        )?;
+
+        market.set_resolution(self.data.resolution, now, config.dispute_window());
+
+        Ok(())
+    }
+}
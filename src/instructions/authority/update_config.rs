@@ -1,7 +1,7 @@
 use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
 
 use crate::{
-    states::Config,
+    states::{Config, MarketCreationMode},
     utils::{Account, constants},
 };
 
@@ -19,21 +19,33 @@ struct UpdateConfigData {
     new_authority: Address,
     new_fee_bps: u16,
     new_briber_fee_bps: u16,
+    new_min_briber_fee_bps: u16,
+    new_max_briber_fee_bps: u16,
+    new_market_creation_mode: MarketCreationMode,
 }
 
 impl<'a> TryFrom<&'a [u8]> for UpdateConfigData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<Self>() {
+        // 32 bytes for authority, 2 bytes each for the four fees, 1 for the market creation mode
+        if data.len() != 41 {
             return Err(ProgramError::InvalidInstructionData);
         };
 
         let new_authority = Address::new_from_array(data[0..32].try_into().unwrap());
         let new_fee_bps = u16::from_le_bytes(data[32..34].try_into().unwrap());
         let new_briber_fee_bps = u16::from_le_bytes(data[34..36].try_into().unwrap());
-
-        if new_fee_bps > 5_000 || new_briber_fee_bps > 5_000 {
+        let new_min_briber_fee_bps = u16::from_le_bytes(data[36..38].try_into().unwrap());
+        let new_max_briber_fee_bps = u16::from_le_bytes(data[38..40].try_into().unwrap());
+        let new_market_creation_mode = MarketCreationMode::try_from(data[40])?;
+
+        // the default briber fee must sit within the bounds
+        if new_fee_bps > 5_000
+            || new_max_briber_fee_bps > 5_000
+            || new_min_briber_fee_bps > new_briber_fee_bps
+            || new_briber_fee_bps > new_max_briber_fee_bps
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -41,6 +53,9 @@ impl<'a> TryFrom<&'a [u8]> for UpdateConfigData {
             new_authority,
             new_fee_bps,
             new_briber_fee_bps,
+            new_min_briber_fee_bps,
+            new_max_briber_fee_bps,
+            new_market_creation_mode,
         })
     }
 }
@@ -92,6 +107,9 @@ impl<'a> UpdateConfig<'a> {
             &self.data.new_authority,
             self.data.new_fee_bps,
             self.data.new_briber_fee_bps,
+            self.data.new_min_briber_fee_bps,
+            self.data.new_max_briber_fee_bps,
+            self.data.new_market_creation_mode,
         );
 
         Ok(())
@@ -0,0 +1,206 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::{Config, Market, SettlementCommittee, SettlementVotes},
+    utils::{Account, constants},
+};
+
+pub struct VoteSettlement<'a> {
+    accounts: VoteSettlementAccounts<'a>,
+    data: VoteSettlementData,
+}
+
+struct VoteSettlementAccounts<'a> {
+    oracle: &'a AccountView, // committee member, pays for the votes account
+    config: &'a AccountView,
+    settlement_committee: &'a AccountView,
+    market: &'a AccountView,
+    settlement_votes: &'a AccountView,
+    settlement_votes_bump: u8,
+}
+
+struct VoteSettlementData {
+    resolution: u8, // 1 = Yes, 2 = No
+}
+
+impl<'a> TryFrom<&'a [u8]> for VoteSettlementData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let [resolution] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        if *resolution != 1 && *resolution != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            resolution: *resolution,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for VoteSettlementAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            oracle,
+            config,
+            settlement_committee,
+            market,
+            settlement_votes,
+            _system_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(oracle)?;
+
+        let (settlement_votes_address, settlement_votes_bump) = Address::find_program_address(
+            &[constants::SETTLEMENT_VOTES_SEED, market.address().as_ref()],
+            &crate::ID,
+        );
+        if &settlement_votes_address != settlement_votes.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            oracle,
+            config,
+            settlement_committee,
+            market,
+            settlement_votes,
+            settlement_votes_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for VoteSettlement<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: VoteSettlementAccounts::try_from(accounts)?,
+            data: VoteSettlementData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> VoteSettlement<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+
+    pub fn process(&self) -> ProgramResult {
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // check committee and oracle
+        let settlement_committee_data = self.accounts.settlement_committee.try_borrow()?;
+        let settlement_committee = SettlementCommittee::load(&settlement_committee_data)?;
+
+        let settlement_committee_address = Address::derive_address(
+            &[constants::SETTLEMENT_COMMITTEE_SEED],
+            Some(settlement_committee.bump),
+            &crate::ID,
+        );
+        if &settlement_committee_address != self.accounts.settlement_committee.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !settlement_committee.is_member(self.accounts.oracle.address()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // check market, votes close once it is settled or disputed
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // votes are only needed while the market is open, so votes left on a
+        // market settled some other way can be closed by any member
+        if !market.is_open() {
+            Account::program_account_check(self.accounts.settlement_votes)?;
+            return Account::close(self.accounts.settlement_votes, self.accounts.oracle);
+        }
+
+        // first vote on this market
+        if Account::program_account_check(self.accounts.settlement_votes).is_err() {
+            let bump_binding = &[self.accounts.settlement_votes_bump];
+            let seeds = &[
+                Seed::from(constants::SETTLEMENT_VOTES_SEED),
+                Seed::from(self.accounts.market.address().as_ref()),
+                Seed::from(bump_binding),
+            ];
+            Account::init_pda::<SettlementVotes>(
+                self.accounts.settlement_votes,
+                self.accounts.oracle,
+                seeds,
+            )?;
+
+            let mut settlement_votes_data = self.accounts.settlement_votes.try_borrow_mut()?;
+            SettlementVotes::load_mut(&mut settlement_votes_data)?.set_inner(
+                self.accounts.market.address(),
+                self.accounts.settlement_votes_bump,
+            );
+        }
+
+        // only votes of current members count, so a committee change drops removed oracles
+        let is_decided = {
+            let mut settlement_votes_data = self.accounts.settlement_votes.try_borrow_mut()?;
+            let settlement_votes = SettlementVotes::load_mut(&mut settlement_votes_data)?;
+            settlement_votes.retain_votes(|voter| settlement_committee.is_member(voter));
+            settlement_votes.add_vote(self.accounts.oracle.address(), self.data.resolution)?;
+
+            let mut matching_votes = 0;
+            let mut is_conflicting = false;
+            for (_, resolution) in settlement_votes.votes() {
+                if resolution == self.data.resolution {
+                    matching_votes += 1;
+                } else {
+                    is_conflicting = true;
+                }
+            }
+
+            if is_conflicting {
+                market.set_disputed();
+                true
+            } else if matching_votes >= settlement_committee.threshold() {
+                market.set_resolution(
+                    self.data.resolution,
+                    Clock::get()?.unix_timestamp,
+                    config.dispute_window(),
+                );
+                true
+            } else {
+                false
+            }
+        };
+
+        // the votes are done once the market is settled or disputed, and their
+        // rent goes to the oracle casting the deciding vote
+        if is_decided {
+            Account::close(self.accounts.settlement_votes, self.accounts.oracle)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    states::Config,
+    utils::{Account, MintInterface, TokenAccountInterface, constants},
+};
+
+pub struct WithdrawTreasuryMint<'a> {
+    accounts: WithdrawTreasuryMintAccounts<'a>,
+    data: WithdrawTreasuryMintData,
+}
+
+struct WithdrawTreasuryMintAccounts<'a> {
+    config: &'a AccountView,
+    treasury: &'a AccountView,
+    destination: &'a AccountView,
+    config_bump: u8,
+}
+
+struct WithdrawTreasuryMintData {
+    amount: Option<u64>, // whole balance when omitted
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawTreasuryMintData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let amount = match data.len() {
+            0 => None,
+            8 => Some(u64::from_le_bytes(data.try_into().unwrap())),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { amount })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for WithdrawTreasuryMintAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            authority,
+            config,
+            mint,
+            treasury,
+            destination,
+            _token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(authority)?;
+
+        let config_data = config.try_borrow()?;
+        let config_account = Config::load(&config_data)?;
+
+        let config_address = Address::derive_address(
+            &[constants::CONFIG_SEED],
+            Some(config_account.bump),
+            &crate::ID,
+        );
+
+        if &config_address != config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &config_account.authority() != authority.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (treasury_address, _) = Address::find_program_address(
+            &[constants::TREASURY_SEED, mint.address().as_ref()],
+            &crate::ID,
+        );
+        if &treasury_address != treasury.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        TokenAccountInterface::mint_check(destination, mint.address())?;
+
+        Ok(Self {
+            config,
+            treasury,
+            destination,
+            config_bump: config_account.bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for WithdrawTreasuryMint<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: WithdrawTreasuryMintAccounts::try_from(accounts)?,
+            data: WithdrawTreasuryMintData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> WithdrawTreasuryMint<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&self) -> ProgramResult {
+        let balance = { TokenAccount::from_account_view(self.accounts.treasury)?.amount() };
+
+        let amount = self.data.amount.unwrap_or(balance);
+        if amount == 0 || amount > balance {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let bump_binding = &[self.accounts.config_bump];
+        let seeds = &[Seed::from(constants::CONFIG_SEED), Seed::from(bump_binding)];
+
+        MintInterface::transfer_signed(
+            self.accounts.treasury,
+            self.accounts.destination,
+            self.accounts.config,
+            amount,
+            seeds,
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct AcceptMarketOwnership<'a> {
+    accounts: AcceptMarketOwnershipAccounts<'a>,
+}
+
+struct AcceptMarketOwnershipAccounts<'a> {
+    new_briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AcceptMarketOwnershipAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [new_briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(new_briber)?;
+
+        Ok(Self { new_briber, market })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AcceptMarketOwnership<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: AcceptMarketOwnershipAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> AcceptMarketOwnership<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and pending briber
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.pending_briber() != self.accounts.new_briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        market.accept_ownership();
+
+        Ok(())
+    }
+}
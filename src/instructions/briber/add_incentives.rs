@@ -1,36 +1,37 @@
-use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
 
 use crate::{
-    states::{Config, Market},
+    states::{BriberContribution, Config, Market},
     utils::{Account, MintInterface, constants, math},
 };
 
 pub struct AddIncentives<'a> {
     accounts: AddIncentivesAccounts<'a>,
-    data: AddIncentivesData<'a>,
+    data: AddIncentivesData,
 }
 
 pub struct AddIncentivesAccounts<'a> {
-    briber: &'a AccountView,
+    contributor: &'a AccountView, // market briber or co-funder
     config: &'a AccountView,
     treasury: &'a AccountView,
     market: &'a AccountView,
     incentive_mint: &'a AccountView,
-    briber_ata: &'a AccountView,
+    contributor_ata: &'a AccountView,
     market_incentive_vault: &'a AccountView,
+    co_funding: &'a [AccountView], // gatekeeper, briber_contribution, system_program
 }
 
-pub struct AddIncentivesData<'a> {
+pub struct AddIncentivesData {
     amount: u64,
-    id: &'a [u8],
+    max_fee_bps: u16, // protocol fee slippage guard
 }
 
-impl<'a> TryFrom<&'a [u8]> for AddIncentivesData<'a> {
+impl<'a> TryFrom<&'a [u8]> for AddIncentivesData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        // 8 bytes for amount, rest for ID
-        if data.len() < constants::MIN_ID_LENGTH + 8 {
+        // 8 bytes for amount, 2 for max protocol fee
+        if data.len() != 10 {
             return Err(ProgramError::InvalidInstructionData);
         };
 
@@ -39,9 +40,11 @@ impl<'a> TryFrom<&'a [u8]> for AddIncentivesData<'a> {
             return Err(ProgramError::InvalidInstructionData);
         }
 
+        let max_fee_bps = u16::from_le_bytes(data[8..10].try_into().unwrap());
+
         Ok(Self {
             amount,
-            id: &data[8..],
+            max_fee_bps,
         })
     }
 }
@@ -51,20 +54,21 @@ impl<'a> TryFrom<&'a [AccountView]> for AddIncentivesAccounts<'a> {
 
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
         let [
-            briber,
+            contributor,
             config,
             treasury,
             market,
             incentive_mint,
-            briber_ata,
+            contributor_ata,
             market_incentive_vault,
             _token_program,
+            co_funding @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        Account::signer_check(briber)?;
+        Account::signer_check(contributor)?;
 
         let (treasury_address, _) = Address::find_program_address(
             &[constants::TREASURY_SEED, incentive_mint.address().as_ref()],
@@ -74,26 +78,15 @@ impl<'a> TryFrom<&'a [AccountView]> for AddIncentivesAccounts<'a> {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        let (market_incentive_vault_address, _) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                incentive_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_incentive_vault_address != market_incentive_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
         Ok(Self {
-            briber,
+            contributor,
             config,
             treasury,
             market,
             incentive_mint,
-            briber_ata,
+            contributor_ata,
             market_incentive_vault,
+            co_funding,
         })
     }
 }
@@ -113,12 +106,12 @@ impl<'a> AddIncentives<'a> {
     pub const DISCRIMINATOR: &'a u8 = &2;
 
     pub fn process(&self) -> ProgramResult {
-        // check market, briber and incentive mint are valid
+        // check market and incentive mint are valid
         let mut market_data = self.accounts.market.try_borrow_mut()?;
         let market = Market::load_mut(&mut market_data)?;
 
         let market_address = Address::derive_address(
-            &[constants::MARKET_SEED, self.data.id],
+            &[constants::MARKET_SEED, market.seed().as_ref()],
             Some(market.bump),
             &crate::ID,
         );
@@ -128,15 +121,22 @@ impl<'a> AddIncentives<'a> {
         if !market.is_open() {
             return Err(ProgramError::InvalidAccountData);
         }
-        if &market.briber() != self.accounts.briber.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
         if &market.incentive_mint() != self.accounts.incentive_mint.address() {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // update market data
-        market.add_incentives(self.data.amount)?;
+        let market_incentive_vault_address = Address::derive_address(
+            &[
+                constants::MARKET_SEED,
+                self.accounts.market.address().as_ref(),
+                self.accounts.incentive_mint.address().as_ref(),
+            ],
+            Some(market.incentive_vault_bump),
+            &crate::ID,
+        );
+        if &market_incentive_vault_address != self.accounts.market_incentive_vault.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
 
         let config_data = self.accounts.config.try_borrow()?;
         let config = Config::load(&config_data)?;
@@ -147,20 +147,98 @@ impl<'a> AddIncentives<'a> {
             return Err(ProgramError::InvalidAccountData);
         }
 
+        // charge the fee snapshotted at creation, not the current one
+        let fee_bps = match market.protocol_fee_bps() {
+            Some(fee_bps) => fee_bps,
+            None => {
+                market.set_protocol_fee_bps(config.fee_bps());
+                config.fee_bps()
+            }
+        };
+        if fee_bps > self.data.max_fee_bps {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // record who funded the incentives and update market data
+        if &market.briber() == self.accounts.contributor.address() {
+            market.add_briber_contribution(self.data.amount)?;
+        } else {
+            self.record_contribution(config, market)?;
+        }
+        market.add_incentives(self.data.amount)?;
+
         // transfer fees to treasury
         MintInterface::transfer(
-            self.accounts.briber_ata,
+            self.accounts.contributor_ata,
             self.accounts.treasury,
-            self.accounts.briber,
-            math::fee_calculation(self.data.amount, config.fee_bps())?,
+            self.accounts.contributor,
+            math::fee_calculation(self.data.amount, fee_bps)?,
         )?;
 
         // transfer incentives to market
         MintInterface::transfer(
-            self.accounts.briber_ata,
+            self.accounts.contributor_ata,
             self.accounts.market_incentive_vault,
-            self.accounts.briber,
+            self.accounts.contributor,
             self.data.amount,
         )
     }
+
+    fn record_contribution(&self, config: &Config, market: &mut Market) -> ProgramResult {
+        let [gatekeeper, briber_contribution, _system_program] = self.accounts.co_funding else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // co-funders must pass the same gate as market creators
+        Account::briber_allowed_check(config, gatekeeper, self.accounts.contributor)?;
+
+        let (briber_contribution_address, briber_contribution_bump) = Address::find_program_address(
+            &[
+                constants::BRIBER_CONTRIBUTION_SEED,
+                self.accounts.market.address().as_ref(),
+                self.accounts.contributor.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if &briber_contribution_address != briber_contribution.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // first contribution to this market, large enough that dust cannot
+        // take up the co-funder slots
+        if Account::program_account_check(briber_contribution).is_err() {
+            let min_amount = math::fee_calculation(
+                market.total_incentive_amount(),
+                constants::MIN_CO_FUNDING_BPS,
+            )?;
+            if self.data.amount < min_amount {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            let bump_binding = &[briber_contribution_bump];
+            let seeds = &[
+                Seed::from(constants::BRIBER_CONTRIBUTION_SEED),
+                Seed::from(self.accounts.market.address().as_ref()),
+                Seed::from(self.accounts.contributor.address().as_ref()),
+                Seed::from(bump_binding),
+            ];
+            Account::init_pda::<BriberContribution>(
+                briber_contribution,
+                self.accounts.contributor,
+                seeds,
+            )?;
+
+            let mut briber_contribution_data = briber_contribution.try_borrow_mut()?;
+            BriberContribution::load_mut(&mut briber_contribution_data)?.set_inner(
+                self.accounts.market.address(),
+                self.accounts.contributor.address(),
+                briber_contribution_bump,
+            );
+
+            market.add_contributor()?;
+        }
+
+        let mut briber_contribution_data = briber_contribution.try_borrow_mut()?;
+        BriberContribution::load_mut(&mut briber_contribution_data)?.add_amount(self.data.amount)
+    }
 }
@@ -2,13 +2,12 @@ use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramEr
 use pinocchio_token::state::TokenAccount;
 
 use crate::{
-    states::Market,
-    utils::{Account, MintInterface, TokenAccountInterface, constants},
+    states::{BriberContribution, Market},
+    utils::{Account, MintInterface, TokenAccountInterface, constants, math},
 };
 
 pub struct CancelMarket<'a> {
     accounts: CancelMarketAccounts<'a>,
-    data: CancelMarketData<'a>,
 }
 
 struct CancelMarketAccounts<'a> {
@@ -18,22 +17,9 @@ struct CancelMarketAccounts<'a> {
     market_incentive_vault: &'a AccountView,
     market_yes_vault: &'a AccountView,
     market_no_vault: &'a AccountView,
-}
-
-struct CancelMarketData<'a> {
-    id: &'a [u8],
-}
-
-impl<'a> TryFrom<&'a [u8]> for CancelMarketData<'a> {
-    type Error = ProgramError;
-
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() < constants::MIN_ID_LENGTH {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        Ok(Self { id: data })
-    }
+    recipient_yes_ata: &'a AccountView, // fee recipient's, only checked when it receives penalties
+    recipient_no_ata: &'a AccountView,  // fee recipient's, only checked when it receives penalties
+    contributions: &'a [AccountView], // (briber_contribution, contributor_ata, contributor) per co-funder
 }
 
 impl<'a> TryFrom<&'a [AccountView]> for CancelMarketAccounts<'a> {
@@ -47,7 +33,10 @@ impl<'a> TryFrom<&'a [AccountView]> for CancelMarketAccounts<'a> {
             market_incentive_vault,
             market_yes_vault,
             market_no_vault,
+            recipient_yes_ata,
+            recipient_no_ata,
             _token_program,
+            contributions @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -62,17 +51,19 @@ impl<'a> TryFrom<&'a [AccountView]> for CancelMarketAccounts<'a> {
             market_incentive_vault,
             market_yes_vault,
             market_no_vault,
+            recipient_yes_ata,
+            recipient_no_ata,
+            contributions,
         })
     }
 }
 
-impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for CancelMarket<'a> {
+impl<'a> TryFrom<&'a [AccountView]> for CancelMarket<'a> {
     type Error = ProgramError;
 
-    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
         Ok(Self {
             accounts: CancelMarketAccounts::try_from(accounts)?,
-            data: CancelMarketData::try_from(data)?,
         })
     }
 }
@@ -82,85 +73,191 @@ impl<'a> CancelMarket<'a> {
 
     pub fn process(&self) -> ProgramResult {
         // check market and data, briber, incentive mint
-        let (total_incentive_amount, market_bump) = {
-            let market_data = self.accounts.market.try_borrow()?;
-            let market = Market::load(&market_data)?;
+        let (
+            total_incentive_amount,
+            yes_penalties,
+            no_penalties,
+            fee_recipient,
+            market_seed,
+            market_bump,
+        ) = {
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
 
+            let market_seed = market.seed();
             let market_address = Address::derive_address(
-                &[constants::MARKET_SEED, self.data.id],
+                &[constants::MARKET_SEED, market_seed.as_ref()],
                 Some(market.bump),
                 &crate::ID,
             );
             if &market_address != self.accounts.market.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            // just check available fees because staked tokens can be withdrawn by users but fees can only when market is settled
-            if market.available_yes_fees() > 0 || market.available_no_fees() > 0 {
+            // farmers' tokens and fees in the outcome vaults can only leave through the market
+            if !market.has_no_stakes()
+                || market.available_yes_fees() > 0
+                || market.available_no_fees() > 0
+            {
                 return Err(ProgramError::InvalidAccountData);
             }
             if &market.briber() != self.accounts.briber.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            if market.is_settled() {
+            if !market.is_open() {
                 return Err(ProgramError::InvalidAccountData);
             }
 
             // check market vaults
-            let (market_incentive_vault_address, _) = Address::find_program_address(
+            let market_incentive_vault_address = Address::derive_address(
                 &[
                     constants::MARKET_SEED,
                     self.accounts.market.address().as_ref(),
                     market.incentive_mint().as_ref(),
                 ],
+                Some(market.incentive_vault_bump),
                 &crate::ID,
             );
             if &market_incentive_vault_address != self.accounts.market_incentive_vault.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
 
-            let (market_yes_vault_address, _) = Address::find_program_address(
+            let market_yes_vault_address = Address::derive_address(
                 &[
                     constants::MARKET_SEED,
                     self.accounts.market.address().as_ref(),
                     market.outcome_yes_mint().as_ref(),
                 ],
+                Some(market.yes_vault_bump),
                 &crate::ID,
             );
             if &market_yes_vault_address != self.accounts.market_yes_vault.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
 
-            let (market_no_vault_address, _) = Address::find_program_address(
+            let market_no_vault_address = Address::derive_address(
                 &[
                     constants::MARKET_SEED,
                     self.accounts.market.address().as_ref(),
                     market.outcome_no_mint().as_ref(),
                 ],
+                Some(market.no_vault_bump),
                 &crate::ID,
             );
             if &market_no_vault_address != self.accounts.market_no_vault.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
 
-            (market.total_incentive_amount(), market.bump)
+            // every co-funder must be refunded
+            if self.accounts.contributions.len() != market.contributor_count() as usize * 3 {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+
+            // penalties left without stakers belong to the fee recipient
+            (
+                market.total_incentive_amount(),
+                market.take_unclaimable_penalties(true),
+                market.take_unclaimable_penalties(false),
+                market.fee_recipient(),
+                market_seed,
+                market.bump,
+            )
         };
 
-        // transfer back to briber ata
         let bump_binding = &[market_bump];
         let seeds = &[
             Seed::from(constants::MARKET_SEED),
-            Seed::from(self.data.id),
+            Seed::from(market_seed.as_ref()),
             Seed::from(bump_binding),
         ];
 
+        // refund co-funders pro rata to their contribution
+        let market_incentive_vault_amount =
+            TokenAccount::from_account_view(self.accounts.market_incentive_vault)?.amount();
+        let mut briber_refund = market_incentive_vault_amount;
+
+        for contribution_accounts in self.accounts.contributions.chunks_exact(3) {
+            let [briber_contribution, contributor_ata, contributor] = contribution_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            Account::program_account_check(briber_contribution)?;
+            let refund = {
+                let briber_contribution_data = briber_contribution.try_borrow()?;
+                let contribution = BriberContribution::load(&briber_contribution_data)?;
+
+                let briber_contribution_address = Address::derive_address(
+                    &[
+                        constants::BRIBER_CONTRIBUTION_SEED,
+                        self.accounts.market.address().as_ref(),
+                        contributor.address().as_ref(),
+                    ],
+                    Some(contribution.bump),
+                    &crate::ID,
+                );
+                if &briber_contribution_address != briber_contribution.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                math::pro_rata(
+                    market_incentive_vault_amount,
+                    contribution.amount(),
+                    total_incentive_amount,
+                )?
+            };
+
+            TokenAccountInterface::owner_check(contributor_ata, contributor.address())?;
+
+            if refund > 0 {
+                MintInterface::transfer_signed(
+                    self.accounts.market_incentive_vault,
+                    contributor_ata,
+                    self.accounts.market,
+                    refund,
+                    seeds,
+                )?;
+            }
+            briber_refund = briber_refund
+                .checked_sub(refund)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // closing the contribution also rejects duplicated entries
+            Account::close(briber_contribution, contributor)?;
+        }
+
+        // transfer the rest back to briber ata
         MintInterface::transfer_signed(
             self.accounts.market_incentive_vault,
             self.accounts.briber_ata,
             self.accounts.market,
-            total_incentive_amount,
+            briber_refund,
             seeds,
         )?;
 
+        // pay out the early-exit penalties, which empties the outcome vaults
+        for (penalties, market_outcome_vault, recipient_ata) in [
+            (
+                yes_penalties,
+                self.accounts.market_yes_vault,
+                self.accounts.recipient_yes_ata,
+            ),
+            (
+                no_penalties,
+                self.accounts.market_no_vault,
+                self.accounts.recipient_no_ata,
+            ),
+        ] {
+            if penalties > 0 {
+                TokenAccountInterface::owner_check(recipient_ata, &fee_recipient)?;
+                MintInterface::transfer_signed(
+                    market_outcome_vault,
+                    recipient_ata,
+                    self.accounts.market,
+                    penalties,
+                    seeds,
+                )?;
+            }
+        }
+
         // close accounts
         let (market_yes_vault_amount, market_no_vault_amount) = {
             (
@@ -1,37 +1,26 @@
-use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
 
 use crate::{
-    states::Market,
-    utils::{Account, MintInterface, constants},
+    states::{BriberContribution, Market},
+    utils::{Account, MintInterface, TokenAccountInterface, constants, math},
 };
 
 pub struct ClaimFees<'a> {
     accounts: ClaimFeesAccounts<'a>,
-    data: ClaimFeesData<'a>,
 }
 
 struct ClaimFeesAccounts<'a> {
-    briber: &'a AccountView,
+    claimer: &'a AccountView, // market briber or co-funder
     market: &'a AccountView,
     outcome_mint: &'a AccountView,
-    briber_ata: &'a AccountView,
+    recipient_ata: &'a AccountView,
     market_outcome_vault: &'a AccountView,
-}
-
-struct ClaimFeesData<'a> {
-    id: &'a [u8],
-}
-
-impl<'a> TryFrom<&'a [u8]> for ClaimFeesData<'a> {
-    type Error = ProgramError;
-
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() < constants::MIN_ID_LENGTH {
-            return Err(ProgramError::InvalidInstructionData);
-        };
-
-        Ok(Self { id: data })
-    }
+    briber_contribution: Option<&'a AccountView>, // only for co-funders
 }
 
 impl<'a> TryFrom<&'a [AccountView]> for ClaimFeesAccounts<'a> {
@@ -39,48 +28,37 @@ impl<'a> TryFrom<&'a [AccountView]> for ClaimFeesAccounts<'a> {
 
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
         let [
-            briber,
+            claimer,
             market,
             outcome_mint,
-            briber_ata,
+            recipient_ata,
             market_outcome_vault,
             _token_program,
+            rest @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        Account::signer_check(briber)?;
-
-        let (market_outcome_vault_address, _bump) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                outcome_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_outcome_vault_address != market_outcome_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Account::signer_check(claimer)?;
 
         Ok(Self {
-            briber,
+            claimer,
             market,
             outcome_mint,
-            briber_ata,
+            recipient_ata,
             market_outcome_vault,
+            briber_contribution: rest.first(),
         })
     }
 }
 
-impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for ClaimFees<'a> {
+impl<'a> TryFrom<&'a [AccountView]> for ClaimFees<'a> {
     type Error = ProgramError;
 
-    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
         Ok(Self {
             accounts: ClaimFeesAccounts::try_from(accounts)?,
-            data: ClaimFeesData::try_from(data)?,
         })
     }
 }
@@ -93,8 +71,9 @@ impl<'a> ClaimFees<'a> {
         let mut market_data = self.accounts.market.try_borrow_mut()?;
         let market = Market::load_mut(&mut market_data)?;
 
+        let market_seed = market.seed();
         let market_address = Address::derive_address(
-            &[constants::MARKET_SEED, self.data.id],
+            &[constants::MARKET_SEED, market_seed.as_ref()],
             Some(market.bump),
             &crate::ID,
         );
@@ -102,44 +81,135 @@ impl<'a> ClaimFees<'a> {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        if &market.briber() != self.accounts.briber.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // claims open once the dispute window is over
+        market.finalize(Clock::get()?.unix_timestamp)?;
 
-        let (amount, outcome_mint) = if market.is_resolved_yes() {
-            (market.available_yes_fees(), market.outcome_yes_mint())
+        let (outcome_mint, market_outcome_vault_bump) = if market.is_resolved_yes() {
+            (market.outcome_yes_mint(), market.yes_vault_bump)
         } else if market.is_resolved_no() {
-            (market.available_no_fees(), market.outcome_no_mint())
+            (market.outcome_no_mint(), market.no_vault_bump)
         } else {
             return Err(ProgramError::InvalidAccountData);
         };
 
-        if amount == 0 {
+        if &outcome_mint != self.accounts.outcome_mint.address() {
             return Err(ProgramError::InvalidAccountData);
         }
-        if &outcome_mint != self.accounts.outcome_mint.address() {
+
+        // fees are split pro rata to the incentives each funder added
+        let total_fees = market.total_fees()?;
+        let (amount, recipient, close_contribution) = match self.accounts.briber_contribution {
+            None => {
+                if &market.briber() != self.accounts.claimer.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                if market.briber_fees_claimed {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                // the briber share absorbs the rounding of every co-funder share
+                let co_funded = market
+                    .total_incentive_amount()
+                    .checked_sub(market.briber_contribution())
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                let amount = total_fees
+                    - math::pro_rata(total_fees, co_funded, market.total_incentive_amount())?;
+                if amount == 0 {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                market.briber_fees_claimed = true;
+                (amount, market.fee_recipient(), false)
+            }
+            Some(briber_contribution) => {
+                Account::program_account_check(briber_contribution)?;
+
+                let mut briber_contribution_data = briber_contribution.try_borrow_mut()?;
+                let contribution = BriberContribution::load_mut(&mut briber_contribution_data)?;
+
+                if &contribution.contributor() != self.accounts.claimer.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let briber_contribution_address = Address::derive_address(
+                    &[
+                        constants::BRIBER_CONTRIBUTION_SEED,
+                        self.accounts.market.address().as_ref(),
+                        self.accounts.claimer.address().as_ref(),
+                    ],
+                    Some(contribution.bump),
+                    &crate::ID,
+                );
+                if &briber_contribution_address != briber_contribution.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                if contribution.fees_claimed {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                // kept open while the co-funder may still reclaim reward overflow
+                contribution.fees_claimed = true;
+                let close_contribution = market.is_overflow_settled()?
+                    && market.co_funder_overflow_share(contribution.amount())?
+                        == contribution.returned_overflow();
+
+                (
+                    math::pro_rata(
+                        total_fees,
+                        contribution.amount(),
+                        market.total_incentive_amount(),
+                    )?,
+                    contribution.contributor(),
+                    close_contribution,
+                )
+            }
+        };
+
+        // fees are paid to the recipient chosen by the briber, or to the co-funder
+        TokenAccountInterface::owner_check(self.accounts.recipient_ata, &recipient)?;
+
+        let market_outcome_vault_address = Address::derive_address(
+            &[
+                constants::MARKET_SEED,
+                self.accounts.market.address().as_ref(),
+                outcome_mint.as_ref(),
+            ],
+            Some(market_outcome_vault_bump),
+            &crate::ID,
+        );
+        if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // set available fees to 0
-        market.clean_available_fees();
+        market.claim_fees(amount)?;
 
         // transfer
         let bump_binding = &[market.bump];
         let seeds = &[
             Seed::from(constants::MARKET_SEED),
-            Seed::from(self.data.id),
+            Seed::from(market_seed.as_ref()),
             Seed::from(bump_binding),
         ];
 
         drop(market_data);
 
-        MintInterface::transfer_signed(
-            self.accounts.market_outcome_vault,
-            self.accounts.briber_ata,
-            self.accounts.market,
-            amount,
-            seeds,
-        )
+        if amount > 0 {
+            MintInterface::transfer_signed(
+                self.accounts.market_outcome_vault,
+                self.accounts.recipient_ata,
+                self.accounts.market,
+                amount,
+                seeds,
+            )?;
+        }
+
+        // a co-funder claims once, closing its contribution when nothing else is owed
+        match self.accounts.briber_contribution {
+            Some(briber_contribution) if close_contribution => {
+                Account::close(briber_contribution, self.accounts.claimer)
+            }
+            _ => Ok(()),
+        }
     }
 }
@@ -0,0 +1,136 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::Market,
+    utils::{Account, MintInterface, TokenAccountInterface, constants},
+};
+
+pub struct ClaimPenalties<'a> {
+    accounts: ClaimPenaltiesAccounts<'a>,
+}
+
+struct ClaimPenaltiesAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+    outcome_mint: &'a AccountView,
+    recipient_ata: &'a AccountView,
+    market_outcome_vault: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimPenaltiesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            briber,
+            market,
+            outcome_mint,
+            recipient_ata,
+            market_outcome_vault,
+            _token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self {
+            briber,
+            market,
+            outcome_mint,
+            recipient_ata,
+            market_outcome_vault,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimPenalties<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ClaimPenaltiesAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ClaimPenalties<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &40;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and data
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_seed = market.seed();
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market_seed.as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // the resolution is final once the dispute window is over
+        market.finalize(Clock::get()?.unix_timestamp)?;
+
+        let (is_yes, market_outcome_vault_bump) =
+            if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                (true, market.yes_vault_bump)
+            } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
+                (false, market.no_vault_bump)
+            } else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+        let market_outcome_vault_address = Address::derive_address(
+            &[
+                constants::MARKET_SEED,
+                self.accounts.market.address().as_ref(),
+                self.accounts.outcome_mint.address().as_ref(),
+            ],
+            Some(market_outcome_vault_bump),
+            &crate::ID,
+        );
+        if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // only the losing side's penalties, or those of a side without winners left
+        let amount = market.take_unclaimable_penalties(is_yes);
+        if amount == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // penalties are paid to the recipient chosen by the briber
+        TokenAccountInterface::owner_check(self.accounts.recipient_ata, &market.fee_recipient())?;
+
+        // transfer
+        let bump_binding = &[market.bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        drop(market_data);
+
+        MintInterface::transfer_signed(
+            self.accounts.market_outcome_vault,
+            self.accounts.recipient_ata,
+            self.accounts.market,
+            amount,
+            seeds,
+        )
+    }
+}
@@ -1,7 +1,7 @@
 use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
 
 use crate::{
-    states::{Config, Market},
+    states::{Config, Market, MarketCreationMode, MarketSeed, OutcomeMints},
     utils::{Account, MintInterface, TokenAccountInterface, constants, math},
 };
 
@@ -11,7 +11,7 @@ pub struct CreateMarket<'a> {
 }
 
 pub struct CreateMarketAccounts<'a> {
-    authority: &'a AccountView,
+    gatekeeper: &'a AccountView, // depends on the config market creation mode
     config: &'a AccountView,
     briber: &'a AccountView,
     market: &'a AccountView,
@@ -31,6 +31,12 @@ pub struct CreateMarketAccounts<'a> {
 
 pub struct CreateMarketData<'a> {
     amount: u64,
+    briber_fee_bps: u16,
+    max_fee_bps: u16, // protocol fee slippage guard
+    min_stake: u64,
+    max_stake_per_farmer: u64,      // zero for no cap
+    max_total_stake: u64,           // per side, zero for no cap
+    max_reward_bps_per_farmer: u16, // zero for no cap
     id: &'a [u8],
 }
 
@@ -38,8 +44,10 @@ impl<'a> TryFrom<&'a [u8]> for CreateMarketData<'a> {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        // 8 bytes for amount, rest for ID
-        if data.len() < constants::MIN_ID_LENGTH + 8 {
+        // 8 bytes for amount, 2 for briber fee, 2 for max protocol fee,
+        // 24 for stake limits, 2 for reward cap, rest for ID
+        if data.len() < constants::MIN_ID_LENGTH + 38 || data.len() > constants::MAX_ID_LENGTH + 38
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -48,9 +56,36 @@ impl<'a> TryFrom<&'a [u8]> for CreateMarketData<'a> {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let id = &data[8..];
+        let briber_fee_bps = u16::from_le_bytes(data[8..10].try_into().unwrap());
+        let max_fee_bps = u16::from_le_bytes(data[10..12].try_into().unwrap());
 
-        Ok(Self { amount, id })
+        // a cap below the minimum stake would reject every stake
+        let min_stake = u64::from_le_bytes(data[12..20].try_into().unwrap());
+        let max_stake_per_farmer = u64::from_le_bytes(data[20..28].try_into().unwrap());
+        let max_total_stake = u64::from_le_bytes(data[28..36].try_into().unwrap());
+        if (max_stake_per_farmer > 0 && max_stake_per_farmer < min_stake)
+            || (max_total_stake > 0 && max_total_stake < min_stake)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_reward_bps_per_farmer = u16::from_le_bytes(data[36..38].try_into().unwrap());
+        if max_reward_bps_per_farmer > constants::MAX_REWARD_BPS_PER_FARMER {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let id = &data[38..];
+
+        Ok(Self {
+            amount,
+            briber_fee_bps,
+            max_fee_bps,
+            min_stake,
+            max_stake_per_farmer,
+            max_total_stake,
+            max_reward_bps_per_farmer,
+            id,
+        })
     }
 }
 
@@ -59,7 +94,7 @@ impl<'a> TryFrom<&'a [AccountView]> for CreateMarketAccounts<'a> {
 
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
         let [
-            authority,
+            gatekeeper, // authority (signer), briber allowlist or the ticker's outcome mints
             config,
             briber,
             market,
@@ -67,8 +102,8 @@ impl<'a> TryFrom<&'a [AccountView]> for CreateMarketAccounts<'a> {
             briber_ata,
             market_incentive_vault,
             treasury,
-            outcome_yes_mint, // on-chain validation
-            outcome_no_mint,  // on-chain validation
+            outcome_yes_mint, // trusted from the gate, or checked against the outcome mints
+            outcome_no_mint,  // trusted from the gate, or checked against the outcome mints
             market_yes_vault,
             market_no_vault,
             token_program,
@@ -79,7 +114,6 @@ impl<'a> TryFrom<&'a [AccountView]> for CreateMarketAccounts<'a> {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        Account::signer_check(authority)?;
         Account::signer_check(briber)?;
 
         MintInterface::valid_mint_check(incentive_mint)?;
@@ -130,7 +164,7 @@ impl<'a> TryFrom<&'a [AccountView]> for CreateMarketAccounts<'a> {
         }
 
         Ok(Self {
-            authority,
+            gatekeeper,
             config,
             briber,
             market,
@@ -166,7 +200,7 @@ impl<'a> CreateMarket<'a> {
 
     pub fn process(&self) -> ProgramResult {
         // create market account and set data
-        let (fee_bps, briber_fee_bps) = {
+        let (fee_bps, market_creation_mode) = {
             let config_data = self.accounts.config.try_borrow()?;
             let config = Config::load(&config_data)?;
 
@@ -175,14 +209,29 @@ impl<'a> CreateMarket<'a> {
             if &config_address != self.accounts.config.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            if &config.authority() != self.accounts.authority.address() {
-                return Err(ProgramError::InvalidAccountData);
+            Account::briber_allowed_check(config, self.accounts.gatekeeper, self.accounts.briber)?;
+            if !config.is_valid_briber_fee(self.data.briber_fee_bps) {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            if config.fee_bps() > self.data.max_fee_bps {
+                return Err(ProgramError::InvalidInstructionData);
             }
-            (config.fee_bps(), config.briber_fee_bps())
+            (config.fee_bps(), config.market_creation_mode())
         };
 
-        let (market_address, market_bump) =
-            Address::find_program_address(&[constants::MARKET_SEED, self.data.id], &crate::ID);
+        // market ids can exceed the 32-byte seed limit, so the PDA is derived from their hash
+        let market_seed = MarketSeed::from_id(self.data.id);
+
+        // without a trusted creator, the market must trade the ticker's registered
+        // mints, so nobody can take a ticker's PDA with mints of their own
+        if let MarketCreationMode::Permissionless = market_creation_mode {
+            self.outcome_mints_check(&market_seed)?;
+        }
+
+        let (market_address, market_bump) = Address::find_program_address(
+            &[constants::MARKET_SEED, market_seed.as_ref()],
+            &crate::ID,
+        );
         if &market_address != self.accounts.market.address() {
             return Err(ProgramError::InvalidAccountData);
         }
@@ -190,7 +239,7 @@ impl<'a> CreateMarket<'a> {
         let bump_binding = &[market_bump];
         let seeds = &[
             Seed::from(constants::MARKET_SEED),
-            Seed::from(self.data.id),
+            Seed::from(market_seed.as_ref()),
             Seed::from(bump_binding),
         ];
         Account::init_pda::<Market>(self.accounts.market, self.accounts.briber, seeds)?;
@@ -204,9 +253,22 @@ impl<'a> CreateMarket<'a> {
             self.accounts.outcome_yes_mint.address(),
             self.accounts.outcome_no_mint.address(),
             self.data.amount,
-            briber_fee_bps,
+            self.data.briber_fee_bps,
+            fee_bps,
             market_bump,
         )?;
+        market.set_id(self.data.id, &market_seed);
+        market.set_stake_limits(
+            self.data.min_stake,
+            self.data.max_stake_per_farmer,
+            self.data.max_total_stake,
+        );
+        market.set_max_reward_bps_per_farmer(self.data.max_reward_bps_per_farmer);
+        market.set_vault_bumps(
+            self.accounts.market_incentive_vault_bump,
+            self.accounts.market_yes_vault_bump,
+            self.accounts.market_no_vault_bump,
+        );
 
         // create atas
         // market_incentive_vault
@@ -276,4 +338,28 @@ impl<'a> CreateMarket<'a> {
             self.data.amount,
         )
     }
+
+    fn outcome_mints_check(&self, market_seed: &MarketSeed) -> ProgramResult {
+        let outcome_mints_account = self.accounts.gatekeeper;
+        Account::program_account_check(outcome_mints_account)?;
+
+        let outcome_mints_data = outcome_mints_account.try_borrow()?;
+        let outcome_mints = OutcomeMints::load(&outcome_mints_data)?;
+
+        let outcome_mints_address = Address::derive_address(
+            &[constants::OUTCOME_MINTS_SEED, market_seed.as_ref()],
+            Some(outcome_mints.bump),
+            &crate::ID,
+        );
+        if &outcome_mints_address != outcome_mints_account.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &outcome_mints.outcome_yes_mint() != self.accounts.outcome_yes_mint.address()
+            || &outcome_mints.outcome_no_mint() != self.accounts.outcome_no_mint.address()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
 }
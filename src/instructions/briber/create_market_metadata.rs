@@ -0,0 +1,156 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+
+use crate::{
+    states::{Market, MarketMetadata},
+    utils::{Account, constants},
+};
+
+pub struct CreateMarketMetadata<'a> {
+    accounts: CreateMarketMetadataAccounts<'a>,
+    data: MarketMetadataData<'a>,
+}
+
+struct CreateMarketMetadataAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+    market_metadata: &'a AccountView,
+    market_metadata_bump: u8,
+}
+
+/// Editable metadata fields, shared with `UpdateMarketMetadata`.
+pub struct MarketMetadataData<'a> {
+    pub expected_resolution_timestamp: i64,
+    pub title: &'a [u8],
+    pub category: &'a [u8],
+    pub uri: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for MarketMetadataData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 8 bytes for expected resolution timestamp, then u8 length-prefixed title, category and uri
+        if data.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let expected_resolution_timestamp = i64::from_le_bytes(data[..8].try_into().unwrap());
+        let (title, data) = split_length_prefixed(&data[8..], constants::MAX_TITLE_LENGTH)?;
+        let (category, data) = split_length_prefixed(data, constants::MAX_CATEGORY_LENGTH)?;
+        let (uri, data) = split_length_prefixed(data, constants::MAX_URI_LENGTH)?;
+
+        if !data.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            expected_resolution_timestamp,
+            title,
+            category,
+            uri,
+        })
+    }
+}
+
+fn split_length_prefixed(data: &[u8], max_len: usize) -> Result<(&[u8], &[u8]), ProgramError> {
+    let Some((len, data)) = data.split_first() else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let len = *len as usize;
+    if len > max_len || len > data.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(data.split_at(len))
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CreateMarketMetadataAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market, market_metadata, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        let (market_metadata_address, market_metadata_bump) = Address::find_program_address(
+            &[constants::MARKET_METADATA_SEED, market.address().as_ref()],
+            &crate::ID,
+        );
+        if &market_metadata_address != market_metadata.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            briber,
+            market,
+            market_metadata,
+            market_metadata_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for CreateMarketMetadata<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateMarketMetadataAccounts::try_from(accounts)?,
+            data: MarketMetadataData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CreateMarketMetadata<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and briber
+        let market_data = self.accounts.market.try_borrow()?;
+        let market = Market::load(&market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // create market metadata account and set data
+        let bump_binding = &[self.accounts.market_metadata_bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_METADATA_SEED),
+            Seed::from(self.accounts.market.address().as_ref()),
+            Seed::from(bump_binding),
+        ];
+        Account::init_pda::<MarketMetadata>(
+            self.accounts.market_metadata,
+            self.accounts.briber,
+            seeds,
+        )?;
+
+        let mut market_metadata_data = self.accounts.market_metadata.try_borrow_mut()?;
+        let market_metadata = MarketMetadata::load_mut(&mut market_metadata_data)?;
+
+        market_metadata.set_inner(
+            self.accounts.market.address(),
+            market.id(),
+            self.accounts.market_metadata_bump,
+        );
+        market_metadata.update(
+            self.data.title,
+            self.data.category,
+            self.data.expected_resolution_timestamp,
+            self.data.uri,
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,22 @@
+mod accept_market_ownership;
 mod add_incentives;
 mod cancel_market;
 mod claim_fees;
+mod claim_penalties;
 mod create_market;
+mod create_market_metadata;
+mod reclaim_reward_overflow;
+mod set_fee_recipient;
+mod set_imbalance_boost;
+mod set_lockup_tiers;
+mod set_unstake_rules;
+mod transfer_market_ownership;
+mod update_market_fee;
+mod update_market_metadata;
 
-pub use {add_incentives::*, cancel_market::*, claim_fees::*, create_market::*};
+pub use {
+    accept_market_ownership::*, add_incentives::*, cancel_market::*, claim_fees::*,
+    claim_penalties::*, create_market::*, create_market_metadata::*, reclaim_reward_overflow::*,
+    set_fee_recipient::*, set_imbalance_boost::*, set_lockup_tiers::*, set_unstake_rules::*,
+    transfer_market_ownership::*, update_market_fee::*, update_market_metadata::*,
+};
@@ -0,0 +1,181 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::{BriberContribution, Market},
+    utils::{Account, MintInterface, TokenAccountInterface, constants},
+};
+
+pub struct ReclaimRewardOverflow<'a> {
+    accounts: ReclaimRewardOverflowAccounts<'a>,
+}
+
+struct ReclaimRewardOverflowAccounts<'a> {
+    claimer: &'a AccountView, // market briber or co-funder
+    market: &'a AccountView,
+    claimer_ata: &'a AccountView,
+    market_incentive_vault: &'a AccountView,
+    briber_contribution: Option<&'a AccountView>, // only for co-funders
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ReclaimRewardOverflowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            claimer,
+            market,
+            claimer_ata,
+            market_incentive_vault,
+            _token_program,
+            rest @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(claimer)?;
+
+        Ok(Self {
+            claimer,
+            market,
+            claimer_ata,
+            market_incentive_vault,
+            briber_contribution: rest.first(),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ReclaimRewardOverflow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ReclaimRewardOverflowAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ReclaimRewardOverflow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &38;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and data, incentive vault
+        let (amount, close_contribution, market_seed, market_bump) = {
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_seed = market.seed();
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market_seed.as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // claims open once the dispute window is over
+            let now = Clock::get()?.unix_timestamp;
+            market.finalize(now)?;
+
+            let market_incentive_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    market.incentive_mint().as_ref(),
+                ],
+                Some(market.incentive_vault_bump),
+                &crate::ID,
+            );
+            if &market_incentive_vault_address != self.accounts.market_incentive_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // only once every winning position has claimed, or winners had their time
+            market.return_reward_overflow(now)?;
+
+            // the overflow is split pro rata to the incentives each funder added
+            let (amount, close_contribution) = match self.accounts.briber_contribution {
+                None => {
+                    if &market.briber() != self.accounts.claimer.address() {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    (market.take_briber_overflow()?, false)
+                }
+                Some(briber_contribution) => {
+                    Account::program_account_check(briber_contribution)?;
+
+                    let mut briber_contribution_data = briber_contribution.try_borrow_mut()?;
+                    let contribution = BriberContribution::load_mut(&mut briber_contribution_data)?;
+
+                    if &contribution.contributor() != self.accounts.claimer.address() {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    let briber_contribution_address = Address::derive_address(
+                        &[
+                            constants::BRIBER_CONTRIBUTION_SEED,
+                            self.accounts.market.address().as_ref(),
+                            self.accounts.claimer.address().as_ref(),
+                        ],
+                        Some(contribution.bump),
+                        &crate::ID,
+                    );
+                    if &briber_contribution_address != briber_contribution.address() {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    let share = market.co_funder_overflow_share(contribution.amount())?;
+                    let amount = share
+                        .checked_sub(contribution.returned_overflow())
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    contribution.set_returned_overflow(share);
+
+                    (
+                        amount,
+                        contribution.fees_claimed && market.is_overflow_settled()?,
+                    )
+                }
+            };
+            if amount == 0 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            (amount, close_contribution, market_seed, market.bump)
+        };
+
+        TokenAccountInterface::owner_check(
+            self.accounts.claimer_ata,
+            self.accounts.claimer.address(),
+        )?;
+
+        let bump_binding = &[market_bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        MintInterface::transfer_signed(
+            self.accounts.market_incentive_vault,
+            self.accounts.claimer_ata,
+            self.accounts.market,
+            amount,
+            seeds,
+        )?;
+
+        // a co-funder that claimed its fees has nothing left once the overflow is settled
+        match self.accounts.briber_contribution {
+            Some(briber_contribution) if close_contribution => {
+                Account::close(briber_contribution, self.accounts.claimer)
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct SetFeeRecipient<'a> {
+    accounts: SetFeeRecipientAccounts<'a>,
+    data: SetFeeRecipientData,
+}
+
+struct SetFeeRecipientAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct SetFeeRecipientData {
+    fee_recipient: Address,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetFeeRecipientData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let fee_recipient = Address::new_from_array(data.try_into().unwrap());
+
+        Ok(Self { fee_recipient })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetFeeRecipientAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self { briber, market })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetFeeRecipient<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetFeeRecipientAccounts::try_from(accounts)?,
+            data: SetFeeRecipientData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetFeeRecipient<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and briber
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        market.set_fee_recipient(&self.data.fee_recipient);
+
+        Ok(())
+    }
+}
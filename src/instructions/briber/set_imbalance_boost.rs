@@ -0,0 +1,102 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct SetImbalanceBoost<'a> {
+    accounts: SetImbalanceBoostAccounts<'a>,
+    data: SetImbalanceBoostData,
+}
+
+struct SetImbalanceBoostAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct SetImbalanceBoostData {
+    imbalance_boost_bps: u16, // zero disables the curve
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetImbalanceBoostData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let imbalance_boost_bps = u16::from_le_bytes(data.try_into().unwrap());
+        if imbalance_boost_bps > constants::MAX_IMBALANCE_BOOST_BPS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            imbalance_boost_bps,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetImbalanceBoostAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self { briber, market })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetImbalanceBoost<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetImbalanceBoostAccounts::try_from(accounts)?,
+            data: SetImbalanceBoostData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetImbalanceBoost<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &37;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !market.is_open() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // the curve is known to every farmer of the market
+        if market.total_yes_staked() > 0 || market.total_no_staked() > 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // boosted stakes must not be free to unstake and stake again
+        if self.data.imbalance_boost_bps > 0 && !market.has_exit_cost() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        market.set_imbalance_boost_bps(self.data.imbalance_boost_bps);
+
+        Ok(())
+    }
+}
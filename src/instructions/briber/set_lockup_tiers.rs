@@ -0,0 +1,103 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct SetLockupTiers<'a> {
+    accounts: SetLockupTiersAccounts<'a>,
+    data: SetLockupTiersData<'a>,
+}
+
+struct SetLockupTiersAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct SetLockupTiersData<'a> {
+    tiers: &'a [u8], // packed (i64 duration, u16 multiplier_bps) entries, empty removes them
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetLockupTiersData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if !data.len().is_multiple_of(constants::LOCKUP_TIER_LEN)
+            || data.len() > constants::LOCKUP_TIER_LEN * constants::MAX_LOCKUP_TIERS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // a lock never weighs less than an unlocked stake
+        for entry in data.chunks_exact(constants::LOCKUP_TIER_LEN) {
+            let duration = i64::from_le_bytes(entry[..8].try_into().unwrap());
+            let multiplier_bps = u16::from_le_bytes(entry[8..].try_into().unwrap());
+            if !(1..=constants::MAX_LOCKUP_DURATION).contains(&duration)
+                || !(10_000..=constants::MAX_LOCKUP_MULTIPLIER_BPS).contains(&multiplier_bps)
+            {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        Ok(Self { tiers: data })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetLockupTiersAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self { briber, market })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetLockupTiers<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetLockupTiersAccounts::try_from(accounts)?,
+            data: SetLockupTiersData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetLockupTiers<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &36;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !market.is_open() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // farmers pick a tier knowing every other one
+        if market.total_yes_staked() > 0 || market.total_no_staked() > 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        market.set_lockup_tiers(self.data.tiers);
+
+        Ok(())
+    }
+}
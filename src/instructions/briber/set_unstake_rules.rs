@@ -0,0 +1,108 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct SetUnstakeRules<'a> {
+    accounts: SetUnstakeRulesAccounts<'a>,
+    data: SetUnstakeRulesData,
+}
+
+struct SetUnstakeRulesAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct SetUnstakeRulesData {
+    unstake_cooldown: i64,       // seconds, zero removes the rules
+    early_exit_penalty_bps: u16, // zero queues unstakes for the cooldown
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetUnstakeRulesData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 + 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let unstake_cooldown = i64::from_le_bytes(data[..8].try_into().unwrap());
+        let early_exit_penalty_bps = u16::from_le_bytes(data[8..].try_into().unwrap());
+        if !(0..=constants::MAX_UNSTAKE_COOLDOWN).contains(&unstake_cooldown)
+            || early_exit_penalty_bps > constants::MAX_EARLY_EXIT_PENALTY_BPS
+            || (unstake_cooldown == 0 && early_exit_penalty_bps > 0)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            unstake_cooldown,
+            early_exit_penalty_bps,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetUnstakeRulesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self { briber, market })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetUnstakeRules<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetUnstakeRulesAccounts::try_from(accounts)?,
+            data: SetUnstakeRulesData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetUnstakeRules<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &34;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !market.is_open() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // farmers staked under the current rules, which can then only be removed
+        let has_stakes = market.total_yes_staked() > 0 || market.total_no_staked() > 0;
+        if has_stakes && self.data.unstake_cooldown > 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // a boosted market keeps some cost to leaving it
+        market.set_unstake_rules(self.data.unstake_cooldown, self.data.early_exit_penalty_bps);
+        if market.imbalance_boost_bps() > 0 && !market.has_exit_cost() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::Market,
+    utils::{Account, constants},
+};
+
+pub struct TransferMarketOwnership<'a> {
+    accounts: TransferMarketOwnershipAccounts<'a>,
+    data: TransferMarketOwnershipData,
+}
+
+struct TransferMarketOwnershipAccounts<'a> {
+    briber: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct TransferMarketOwnershipData {
+    new_briber: Address, // zero address cancels a pending transfer
+}
+
+impl<'a> TryFrom<&'a [u8]> for TransferMarketOwnershipData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let new_briber = Address::new_from_array(data.try_into().unwrap());
+
+        Ok(Self { new_briber })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for TransferMarketOwnershipAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self { briber, market })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for TransferMarketOwnership<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: TransferMarketOwnershipAccounts::try_from(accounts)?,
+            data: TransferMarketOwnershipData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> TransferMarketOwnership<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and briber
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // the new briber takes over once they accept
+        market.set_pending_briber(&self.data.new_briber);
+
+        Ok(())
+    }
+}
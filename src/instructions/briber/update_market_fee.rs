@@ -0,0 +1,114 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::{Config, Market},
+    utils::{Account, constants},
+};
+
+pub struct UpdateMarketFee<'a> {
+    accounts: UpdateMarketFeeAccounts<'a>,
+    data: UpdateMarketFeeData,
+}
+
+struct UpdateMarketFeeAccounts<'a> {
+    briber: &'a AccountView,
+    config: &'a AccountView,
+    market: &'a AccountView,
+}
+
+struct UpdateMarketFeeData {
+    new_fee_bps: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateMarketFeeData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let new_fee_bps = u16::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { new_fee_bps })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for UpdateMarketFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [briber, config, market] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(briber)?;
+
+        Ok(Self {
+            briber,
+            config,
+            market,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for UpdateMarketFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: UpdateMarketFeeAccounts::try_from(accounts)?,
+            data: UpdateMarketFeeData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> UpdateMarketFee<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // check market and briber
+        let mut market_data = self.accounts.market.try_borrow_mut()?;
+        let market = Market::load_mut(&mut market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if &market.briber() != self.accounts.briber.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !market.is_open() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // farmers staked under the current fee, so it can only go down
+        if self.data.new_fee_bps > market.fee_bps()
+            || !config.is_valid_briber_fee(self.data.new_fee_bps)
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // a boosted market keeps some cost to leaving it
+        market.set_fee_bps(self.data.new_fee_bps);
+        if market.imbalance_boost_bps() > 0 && !market.has_exit_cost() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
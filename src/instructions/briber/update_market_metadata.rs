@@ -0,0 +1,110 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    instructions::MarketMetadataData,
+    states::{Config, Market, MarketMetadata},
+    utils::{Account, constants},
+};
+
+pub struct UpdateMarketMetadata<'a> {
+    accounts: UpdateMarketMetadataAccounts<'a>,
+    data: MarketMetadataData<'a>,
+}
+
+struct UpdateMarketMetadataAccounts<'a> {
+    signer: &'a AccountView, // briber or config authority
+    config: &'a AccountView,
+    market: &'a AccountView,
+    market_metadata: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for UpdateMarketMetadataAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [signer, config, market, market_metadata] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(signer)?;
+
+        Ok(Self {
+            signer,
+            config,
+            market,
+            market_metadata,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for UpdateMarketMetadata<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: UpdateMarketMetadataAccounts::try_from(accounts)?,
+            data: MarketMetadataData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> UpdateMarketMetadata<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&self) -> ProgramResult {
+        // check config
+        let config_data = self.accounts.config.try_borrow()?;
+        let config = Config::load(&config_data)?;
+
+        let config_address =
+            Address::derive_address(&[constants::CONFIG_SEED], Some(config.bump), &crate::ID);
+        if &config_address != self.accounts.config.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // check market
+        let market_data = self.accounts.market.try_borrow()?;
+        let market = Market::load(&market_data)?;
+
+        let market_address = Address::derive_address(
+            &[constants::MARKET_SEED, market.seed().as_ref()],
+            Some(market.bump),
+            &crate::ID,
+        );
+        if &market_address != self.accounts.market.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // only the briber or the admin can edit metadata
+        if &market.briber() != self.accounts.signer.address()
+            && &config.authority() != self.accounts.signer.address()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // check market metadata and update
+        let mut market_metadata_data = self.accounts.market_metadata.try_borrow_mut()?;
+        let market_metadata = MarketMetadata::load_mut(&mut market_metadata_data)?;
+
+        let market_metadata_address = Address::derive_address(
+            &[
+                constants::MARKET_METADATA_SEED,
+                self.accounts.market.address().as_ref(),
+            ],
+            Some(market_metadata.bump),
+            &crate::ID,
+        );
+        if &market_metadata_address != self.accounts.market_metadata.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        market_metadata.update(
+            self.data.title,
+            self.data.category,
+            self.data.expected_resolution_timestamp,
+            self.data.uri,
+        );
+
+        Ok(())
+    }
+}
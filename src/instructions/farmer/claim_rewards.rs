@@ -1,16 +1,21 @@
-use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
 
 use crate::{
     states::{FarmerPosition, Market},
-    utils::{Account, MintInterface, constants},
+    utils::{Account, MintInterface, TokenAccountInterface, constants},
 };
 
 pub struct ClaimRewards<'a> {
     accounts: ClaimRewardsAccounts<'a>,
-    data: ClaimRewardsData<'a>,
 }
 
-struct ClaimRewardsAccounts<'a> {
+pub(crate) struct ClaimRewardsAccounts<'a> {
+    authority: &'a AccountView, // the farmer or its delegate
     farmer: &'a AccountView,
     market: &'a AccountView,
     farmer_position: &'a AccountView,
@@ -22,28 +27,40 @@ struct ClaimRewardsAccounts<'a> {
     farmer_outcome_ata: &'a AccountView,
 }
 
-struct ClaimRewardsData<'a> {
-    market_id: &'a [u8],
-}
-
-impl<'a> TryFrom<&'a [u8]> for ClaimRewardsData<'a> {
+impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsAccounts<'a> {
     type Error = ProgramError;
 
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() < constants::MIN_ID_LENGTH {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [authority, rest @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let (position, rest) = rest
+            .split_at_checked(Self::POSITION_LEN)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        // a trailing farmer account means the signer claims as its delegate
+        let farmer = match rest {
+            [_token_program] => authority,
+            [_token_program, farmer] => farmer,
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
 
-        Ok(Self { market_id: data })
+        Account::signer_check(authority)?;
+
+        Self::from_position(authority, farmer, position)
     }
 }
 
-impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsAccounts<'a> {
-    type Error = ProgramError;
+impl<'a> ClaimRewardsAccounts<'a> {
+    /// Number of accounts describing one position, after the farmer.
+    pub(crate) const POSITION_LEN: usize = 8;
 
-    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+    pub(crate) fn from_position(
+        authority: &'a AccountView,
+        farmer: &'a AccountView,
+        position: &'a [AccountView],
+    ) -> Result<Self, ProgramError> {
         let [
-            farmer,
             market,
             farmer_position,
             reward_mint,
@@ -52,39 +69,13 @@ impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsAccounts<'a> {
             market_outcome_vault,
             farmer_reward_ata,
             farmer_outcome_ata,
-            _token_program,
-        ] = accounts
+        ] = position
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        Account::signer_check(farmer)?;
-
-        let (market_reward_vault_address, _) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                reward_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_reward_vault_address != market_reward_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let (market_outcome_vault_address, _) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                outcome_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_outcome_vault_address != market_outcome_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
         Ok(Self {
+            authority,
             farmer,
             market,
             farmer_position,
@@ -96,119 +87,198 @@ impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsAccounts<'a> {
             farmer_outcome_ata,
         })
     }
-}
-
-impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for ClaimRewards<'a> {
-    type Error = ProgramError;
-
-    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
-        Ok(Self {
-            accounts: ClaimRewardsAccounts::try_from(accounts)?,
-            data: ClaimRewardsData::try_from(data)?,
-        })
-    }
-}
 
-impl<'a> ClaimRewards<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &9;
-
-    pub fn process(&self) -> ProgramResult {
-        let (staked_amount, reward_amount, market_bump) = {
+    /// Pays out a winning position of a settled market and closes it. Returns
+    /// `false`, moving no funds, when the market is still pending settlement,
+    /// the outcome mint lost, the position does not exist (anymore) or still
+    /// has queued withdrawals.
+    pub(crate) fn claim(&self) -> Result<bool, ProgramError> {
+        let (outcome_amount, reward_amount, market_seed, market_bump) = {
             // check market and its data
-            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let mut market_data = self.market.try_borrow_mut()?;
             let market = Market::load_mut(&mut market_data)?;
 
+            let market_seed = market.seed();
             let market_address = Address::derive_address(
-                &[constants::MARKET_SEED, self.data.market_id],
+                &[constants::MARKET_SEED, market_seed.as_ref()],
                 Some(market.bump),
                 &crate::ID,
             );
-            if &market_address != self.accounts.market.address() {
+            if &market_address != self.market.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            if !market.is_settled() {
+            // claims open once the dispute window is over
+            let now = Clock::get()?.unix_timestamp;
+            if market.finalize(now).is_err() {
+                return Ok(false);
+            }
+            if &market.incentive_mint() != self.reward_mint.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            if &market.incentive_mint() != self.accounts.reward_mint.address() {
+
+            // check market reward vault
+            let market_reward_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.market.address().as_ref(),
+                    self.reward_mint.address().as_ref(),
+                ],
+                Some(market.incentive_vault_bump),
+                &crate::ID,
+            );
+            if &market_reward_vault_address != self.market_reward_vault.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
 
             // check farmer position and its data (mint, amount)
-            let mut farmer_position_data = self.accounts.farmer_position.try_borrow_mut()?;
+            if Account::program_account_check(self.farmer_position).is_err() {
+                return Ok(false);
+            }
+            let mut farmer_position_data = self.farmer_position.try_borrow_mut()?;
             let farmer_position = FarmerPosition::load_mut(&mut farmer_position_data)?;
 
             let farmer_position_address = Address::derive_address(
                 &[
                     constants::FARMER_POSITION_SEED,
-                    self.accounts.market.address().as_ref(),
-                    self.accounts.farmer.address().as_ref(),
+                    self.market.address().as_ref(),
+                    self.farmer.address().as_ref(),
                 ],
                 Some(farmer_position.bump),
                 &crate::ID,
             );
-            if &farmer_position_address != self.accounts.farmer_position.address() {
+            if &farmer_position_address != self.farmer_position.address() {
                 return Err(ProgramError::InvalidAccountData);
             }
             if !farmer_position.is_initialized {
                 return Err(ProgramError::UninitializedAccount);
             }
 
+            // a delegate may claim, but only into the recipient's accounts
+            let is_delegated = self.authority.address() != self.farmer.address();
+            if is_delegated && farmer_position.delegate().as_ref() != Some(self.authority.address())
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let recipient = farmer_position
+                .reward_recipient()
+                .or_else(|| is_delegated.then(|| self.farmer.address().clone()));
+            if let Some(recipient) = recipient {
+                TokenAccountInterface::owner_check(self.farmer_reward_ata, &recipient)?;
+                TokenAccountInterface::owner_check(self.farmer_outcome_ata, &recipient)?;
+            }
+
+            // closing the position would drop queued withdrawals, take them first
+            if farmer_position.has_pending_withdrawal() {
+                return Ok(false);
+            }
+
             // check mint, mint winner, staked amount
-            let (staked_amount, reward_amount) = if market.is_resolved_yes()
-                && &market.outcome_yes_mint() == self.accounts.outcome_mint.address()
+            let (is_yes, staked_amount, weight, total_weight, market_outcome_vault_bump) = if market
+                .is_resolved_yes()
+                && &market.outcome_yes_mint() == self.outcome_mint.address()
             {
                 (
+                    true,
                     farmer_position.yes_staked(),
-                    market.calculate_reward(
-                        market.total_yes_staked(),
-                        farmer_position.yes_staked(),
-                    )?,
+                    farmer_position.yes_weight(),
+                    market.total_yes_weight(),
+                    market.yes_vault_bump,
                 )
             } else if market.is_resolved_no()
-                && &market.outcome_no_mint() == self.accounts.outcome_mint.address()
+                && &market.outcome_no_mint() == self.outcome_mint.address()
             {
                 (
+                    false,
                     farmer_position.no_staked(),
-                    market
-                        .calculate_reward(market.total_no_staked(), farmer_position.no_staked())?,
+                    farmer_position.no_weight(),
+                    market.total_no_weight(),
+                    market.no_vault_bump,
                 )
             } else {
-                return Err(ProgramError::InvalidAccountData);
+                return Ok(false);
             };
+            // incentives follow the boosted weight, penalties the raw stake
+            let reward_amount = if market.max_reward_bps_per_farmer() == 0 {
+                market.calculate_reward(total_weight, weight)?
+            } else {
+                market.claim_capped_reward(total_weight, weight, now)?
+            };
+            // winners also share their side's early-exit penalties
+            let outcome_amount = staked_amount
+                .checked_add(market.claim_penalty_share(is_yes, staked_amount)?)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // check market outcome vault
+            let market_outcome_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.market.address().as_ref(),
+                    self.outcome_mint.address().as_ref(),
+                ],
+                Some(market_outcome_vault_bump),
+                &crate::ID,
+            );
+            if &market_outcome_vault_address != self.market_outcome_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
 
-            (staked_amount, reward_amount, market.bump)
+            (outcome_amount, reward_amount, market_seed, market.bump)
         };
 
         // transfer rewards
         let bump_binding = &[market_bump];
         let seeds = &[
             Seed::from(constants::MARKET_SEED),
-            Seed::from(self.data.market_id),
+            Seed::from(market_seed.as_ref()),
             Seed::from(bump_binding),
         ];
 
         if reward_amount > 0 {
             MintInterface::transfer_signed(
-                self.accounts.market_reward_vault,
-                self.accounts.farmer_reward_ata,
-                self.accounts.market,
+                self.market_reward_vault,
+                self.farmer_reward_ata,
+                self.market,
                 reward_amount,
                 seeds,
             )?;
         }
 
         // transfer back staked outcome tokens
-        if staked_amount > 0 {
+        if outcome_amount > 0 {
             MintInterface::transfer_signed(
-                self.accounts.market_outcome_vault,
-                self.accounts.farmer_outcome_ata,
-                self.accounts.market,
-                staked_amount,
+                self.market_outcome_vault,
+                self.farmer_outcome_ata,
+                self.market,
+                outcome_amount,
                 seeds,
             )?;
         }
 
         // close farmer position account
-        Account::close(self.accounts.farmer_position, self.accounts.farmer)
+        Account::close(self.farmer_position, self.farmer)?;
+
+        Ok(true)
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimRewards<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ClaimRewardsAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ClaimRewards<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&self) -> ProgramResult {
+        if !self.accounts.claim()? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
     }
 }
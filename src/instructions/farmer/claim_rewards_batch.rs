@@ -0,0 +1,74 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+use pinocchio_log::log;
+
+use crate::{
+    instructions::ClaimRewardsAccounts,
+    utils::{Account, constants},
+};
+
+pub struct ClaimRewardsBatch<'a> {
+    accounts: ClaimRewardsBatchAccounts<'a>,
+}
+
+struct ClaimRewardsBatchAccounts<'a> {
+    farmer: &'a AccountView,
+    positions: &'a [AccountView], // ClaimRewards accounts after the farmer, per position
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsBatchAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [farmer, _token_program, positions @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(farmer)?;
+
+        if positions.is_empty()
+            || !positions
+                .len()
+                .is_multiple_of(ClaimRewardsAccounts::POSITION_LEN)
+            || positions.len() > ClaimRewardsAccounts::POSITION_LEN * constants::MAX_BATCH_CLAIMS
+        {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self { farmer, positions })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for ClaimRewardsBatch<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ClaimRewardsBatchAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ClaimRewardsBatch<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &28;
+
+    pub fn process(&self) -> ProgramResult {
+        let positions = self
+            .accounts
+            .positions
+            .chunks_exact(ClaimRewardsAccounts::POSITION_LEN);
+
+        // positions that cannot be claimed yet are skipped, invalid accounts still fail
+        for (index, position) in positions.enumerate() {
+            let claim_accounts = ClaimRewardsAccounts::from_position(
+                self.accounts.farmer,
+                self.accounts.farmer,
+                position,
+            )?;
+            if !claim_accounts.claim()? {
+                log!("position {} skipped", index);
+            }
+        }
+
+        Ok(())
+    }
+}
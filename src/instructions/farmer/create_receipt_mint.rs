@@ -0,0 +1,122 @@
+use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    states::Market,
+    utils::{Account, MintInterface, constants},
+};
+
+pub struct CreateReceiptMint<'a> {
+    accounts: CreateReceiptMintAccounts<'a>,
+}
+
+struct CreateReceiptMintAccounts<'a> {
+    payer: &'a AccountView,
+    market: &'a AccountView,
+    outcome_mint: &'a AccountView,
+    receipt_mint: &'a AccountView,
+    token_program: &'a AccountView,
+    receipt_mint_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CreateReceiptMintAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            payer,
+            market,
+            outcome_mint,
+            receipt_mint,
+            token_program,
+            _system_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(payer)?;
+
+        let (receipt_mint_address, receipt_mint_bump) = Address::find_program_address(
+            &[
+                constants::RECEIPT_MINT_SEED,
+                market.address().as_ref(),
+                outcome_mint.address().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if &receipt_mint_address != receipt_mint.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            payer,
+            market,
+            outcome_mint,
+            receipt_mint,
+            token_program,
+            receipt_mint_bump,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CreateReceiptMint<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateReceiptMintAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CreateReceiptMint<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &30;
+
+    pub fn process(&self) -> ProgramResult {
+        // check market and outcome mint, and store the receipt mint bump
+        {
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market.seed().as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if !market.is_open() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                market.receipt_yes_mint_bump = self.accounts.receipt_mint_bump;
+            } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
+                market.receipt_no_mint_bump = self.accounts.receipt_mint_bump;
+            } else {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // receipts use the decimals of the outcome token they stand for
+        let decimals = Mint::from_account_view(self.accounts.outcome_mint)?.decimals();
+
+        let bump_binding = &[self.accounts.receipt_mint_bump];
+        let seeds = &[
+            Seed::from(constants::RECEIPT_MINT_SEED),
+            Seed::from(self.accounts.market.address().as_ref()),
+            Seed::from(self.accounts.outcome_mint.address().as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        MintInterface::init_with_seeds(
+            self.accounts.receipt_mint,
+            self.accounts.payer,
+            self.accounts.market.address(),
+            decimals,
+            self.accounts.token_program,
+            seeds,
+        )
+    }
+}
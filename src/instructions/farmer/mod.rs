@@ -1,5 +1,16 @@
 mod claim_rewards;
+mod claim_rewards_batch;
+mod create_receipt_mint;
+mod redeem_receipt;
+mod set_position_delegate;
 mod stake_outcome_token;
+mod stake_outcome_token_for;
+mod switch_side;
 mod unstake_outcome_token;
+mod withdraw_unstaked;
 
-pub use {claim_rewards::*, stake_outcome_token::*, unstake_outcome_token::*};
+pub use {
+    claim_rewards::*, claim_rewards_batch::*, create_receipt_mint::*, redeem_receipt::*,
+    set_position_delegate::*, stake_outcome_token::*, stake_outcome_token_for::*, switch_side::*,
+    unstake_outcome_token::*, withdraw_unstaked::*,
+};
@@ -0,0 +1,255 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::Market,
+    utils::{Account, MintInterface, constants},
+};
+
+pub struct RedeemReceipt<'a> {
+    accounts: RedeemReceiptAccounts<'a>,
+    data: RedeemReceiptData,
+}
+
+struct RedeemReceiptAccounts<'a> {
+    holder: &'a AccountView,
+    market: &'a AccountView,
+    outcome_mint: &'a AccountView,
+    receipt_mint: &'a AccountView,
+    reward_mint: &'a AccountView,
+    holder_receipt_ata: &'a AccountView,
+    holder_outcome_ata: &'a AccountView,
+    holder_reward_ata: &'a AccountView,
+    market_outcome_vault: &'a AccountView,
+    market_reward_vault: &'a AccountView,
+}
+
+struct RedeemReceiptData {
+    amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RedeemReceiptData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { amount })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for RedeemReceiptAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            holder,
+            market,
+            outcome_mint,
+            receipt_mint,
+            reward_mint,
+            holder_receipt_ata,
+            holder_outcome_ata,
+            holder_reward_ata,
+            market_outcome_vault,
+            market_reward_vault,
+            _token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(holder)?;
+
+        Ok(Self {
+            holder,
+            market,
+            outcome_mint,
+            receipt_mint,
+            reward_mint,
+            holder_receipt_ata,
+            holder_outcome_ata,
+            holder_reward_ata,
+            market_outcome_vault,
+            market_reward_vault,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for RedeemReceipt<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: RedeemReceiptAccounts::try_from(accounts)?,
+            data: RedeemReceiptData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> RedeemReceipt<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &31;
+
+    pub fn process(&self) -> ProgramResult {
+        let (reward_amount, outcome_amount, market_seed, market_bump) = {
+            // check market and its data
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_seed = market.seed();
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market_seed.as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let is_yes = if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                true
+            } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
+                false
+            } else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+            // check receipt mint
+            let receipt_mint_bump = if is_yes {
+                market.receipt_yes_mint_bump
+            } else {
+                market.receipt_no_mint_bump
+            };
+            let receipt_mint_address = Address::derive_address(
+                &[
+                    constants::RECEIPT_MINT_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.outcome_mint.address().as_ref(),
+                ],
+                Some(receipt_mint_bump),
+                &crate::ID,
+            );
+            if &receipt_mint_address != self.accounts.receipt_mint.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let (reward_amount, penalty_share) = if market.is_open() {
+                // open market: burning receipts unstakes, unless unstake rules apply
+                if market.unstake_cooldown() > 0 {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                // receipts are never locked and weigh 1x
+                if is_yes {
+                    market.sub_yes_staked(self.data.amount, self.data.amount)?;
+                } else {
+                    market.sub_no_staked(self.data.amount, self.data.amount)?;
+                }
+                (0, 0)
+            } else {
+                // settled market: burning winning receipts claims once the dispute window is over
+                market.finalize(Clock::get()?.unix_timestamp)?;
+                if &market.incentive_mint() != self.accounts.reward_mint.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let market_reward_vault_address = Address::derive_address(
+                    &[
+                        constants::MARKET_SEED,
+                        self.accounts.market.address().as_ref(),
+                        self.accounts.reward_mint.address().as_ref(),
+                    ],
+                    Some(market.incentive_vault_bump),
+                    &crate::ID,
+                );
+                if &market_reward_vault_address != self.accounts.market_reward_vault.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let total_weight = if is_yes && market.is_resolved_yes() {
+                    market.total_yes_weight()
+                } else if !is_yes && market.is_resolved_no() {
+                    market.total_no_weight()
+                } else {
+                    return Err(ProgramError::InvalidAccountData);
+                };
+                (
+                    market.calculate_reward(total_weight, self.data.amount)?,
+                    market.claim_penalty_share(is_yes, self.data.amount)?,
+                )
+            };
+
+            // check market outcome vault
+            let market_outcome_vault_bump = if is_yes {
+                market.yes_vault_bump
+            } else {
+                market.no_vault_bump
+            };
+            let market_outcome_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.outcome_mint.address().as_ref(),
+                ],
+                Some(market_outcome_vault_bump),
+                &crate::ID,
+            );
+            if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let outcome_amount = self
+                .data
+                .amount
+                .checked_add(penalty_share)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            (reward_amount, outcome_amount, market_seed, market.bump)
+        };
+
+        // burn receipts, which fails if the holder does not own enough of them
+        MintInterface::burn(
+            self.accounts.holder_receipt_ata,
+            self.accounts.receipt_mint,
+            self.accounts.holder,
+            self.data.amount,
+        )?;
+
+        let bump_binding = &[market_bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        // transfer rewards
+        if reward_amount > 0 {
+            MintInterface::transfer_signed(
+                self.accounts.market_reward_vault,
+                self.accounts.holder_reward_ata,
+                self.accounts.market,
+                reward_amount,
+                seeds,
+            )?;
+        }
+
+        // transfer back staked outcome tokens, with any share of early-exit penalties
+        MintInterface::transfer_signed(
+            self.accounts.market_outcome_vault,
+            self.accounts.holder_outcome_ata,
+            self.accounts.market,
+            outcome_amount,
+            seeds,
+        )
+    }
+}
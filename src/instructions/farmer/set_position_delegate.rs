@@ -0,0 +1,97 @@
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::FarmerPosition,
+    utils::{Account, constants},
+};
+
+pub struct SetPositionDelegate<'a> {
+    accounts: SetPositionDelegateAccounts<'a>,
+    data: SetPositionDelegateData,
+}
+
+struct SetPositionDelegateAccounts<'a> {
+    farmer: &'a AccountView,
+    market: &'a AccountView,
+    farmer_position: &'a AccountView,
+}
+
+struct SetPositionDelegateData {
+    delegate: Address,         // zero address removes the delegate
+    reward_recipient: Address, // zero address pays the farmer
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetPositionDelegateData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let delegate = Address::new_from_array(data[..32].try_into().unwrap());
+        let reward_recipient = Address::new_from_array(data[32..].try_into().unwrap());
+
+        Ok(Self {
+            delegate,
+            reward_recipient,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetPositionDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [farmer, market, farmer_position] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(farmer)?;
+        Account::program_account_check(farmer_position)?;
+
+        Ok(Self {
+            farmer,
+            market,
+            farmer_position,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SetPositionDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetPositionDelegateAccounts::try_from(accounts)?,
+            data: SetPositionDelegateData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetPositionDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &33;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut farmer_position_data = self.accounts.farmer_position.try_borrow_mut()?;
+        let farmer_position = FarmerPosition::load_mut(&mut farmer_position_data)?;
+
+        // only the farmer's own position matches these seeds
+        let farmer_position_address = Address::derive_address(
+            &[
+                constants::FARMER_POSITION_SEED,
+                self.accounts.market.address().as_ref(),
+                self.accounts.farmer.address().as_ref(),
+            ],
+            Some(farmer_position.bump),
+            &crate::ID,
+        );
+        if &farmer_position_address != self.accounts.farmer_position.address() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        farmer_position.set_delegate(&self.data.delegate, &self.data.reward_recipient);
+
+        Ok(())
+    }
+}
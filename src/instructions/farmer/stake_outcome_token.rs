@@ -1,4 +1,9 @@
-use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
 
 use crate::{
     states::{FarmerPosition, Market},
@@ -7,41 +12,51 @@ use crate::{
 
 pub struct StakeOutcomeToken<'a> {
     accounts: StakeOutcomeTokenAccounts<'a>,
-    data: StakeOutcomeTokenData<'a>,
+    data: StakeOutcomeTokenData,
 }
 
-struct StakeOutcomeTokenAccounts<'a> {
+pub(crate) struct StakeOutcomeTokenAccounts<'a> {
+    payer: &'a AccountView,
     farmer: &'a AccountView,
     market: &'a AccountView,
     farmer_position: &'a AccountView,
     outcome_mint: &'a AccountView,
-    farmer_ata: &'a AccountView,
+    payer_ata: &'a AccountView,
     market_outcome_vault: &'a AccountView,
-    farmer_position_bump: u8,
+    receipt: Option<StakeReceiptAccounts<'a>>,
+}
+
+/// Optional trailing accounts: when present the stake is represented by
+/// transferable receipt tokens instead of the farmer position.
+struct StakeReceiptAccounts<'a> {
+    receipt_mint: &'a AccountView,
+    farmer_receipt_ata: &'a AccountView,
 }
 
-struct StakeOutcomeTokenData<'a> {
+struct StakeOutcomeTokenData {
     amount: u64,
-    market_id: &'a [u8],
+    lockup_tier: Option<u8>, // index into the market's lockup tiers, unlocked if absent
 }
 
-impl<'a> TryFrom<&'a [u8]> for StakeOutcomeTokenData<'a> {
+impl<'a> TryFrom<&'a [u8]> for StakeOutcomeTokenData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        // 8 bytes for amount, rest for market ID
-        if data.len() < constants::MIN_ID_LENGTH + 8 {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        let lockup_tier = match data.len() {
+            8 => None,
+            9 => Some(data[8]),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
         let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
         if amount == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let market_id = &data[8..];
-
-        Ok(Self { amount, market_id })
+        Ok(Self {
+            amount,
+            lockup_tier,
+        })
     }
 }
 
@@ -49,54 +64,68 @@ impl<'a> TryFrom<&'a [AccountView]> for StakeOutcomeTokenAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [farmer, rest @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let (stake, receipt) = rest
+            .split_at_checked(Self::STAKE_LEN)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        Account::signer_check(farmer)?;
+
+        Self::from_stake(farmer, farmer, stake, receipt)
+    }
+}
+
+impl<'a> StakeOutcomeTokenAccounts<'a> {
+    /// Number of accounts describing the stake, after the payer and farmer.
+    pub(crate) const STAKE_LEN: usize = 7;
+
+    /// Whether the stake is funded by someone other than the farmer.
+    #[inline(always)]
+    fn is_gift(&self) -> bool {
+        self.payer.address() != self.farmer.address()
+    }
+
+    /// The `payer` signs for the tokens and the position rent, while the
+    /// stake is credited to `farmer`.
+    pub(crate) fn from_stake(
+        payer: &'a AccountView,
+        farmer: &'a AccountView,
+        stake: &'a [AccountView],
+        receipt: &'a [AccountView],
+    ) -> Result<Self, ProgramError> {
         let [
-            farmer,
             market,
             farmer_position,
             outcome_mint,
-            farmer_ata,
+            payer_ata,
             market_outcome_vault,
             _token_program,
             _system_program,
-        ] = accounts
+        ] = stake
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        Account::signer_check(farmer)?;
-
-        let (farmer_position_address, farmer_position_bump) = Address::find_program_address(
-            &[
-                constants::FARMER_POSITION_SEED,
-                market.address().as_ref(),
-                farmer.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &farmer_position_address != farmer_position.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let (market_outcome_vault_address, _) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                outcome_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_outcome_vault_address != market_outcome_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let receipt = match receipt {
+            [] => None,
+            [receipt_mint, farmer_receipt_ata] => Some(StakeReceiptAccounts {
+                receipt_mint,
+                farmer_receipt_ata,
+            }),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
 
         Ok(Self {
+            payer,
             farmer,
             market,
             farmer_position,
             outcome_mint,
-            farmer_ata,
+            payer_ata,
             market_outcome_vault,
-            farmer_position_bump,
+            receipt,
         })
     }
 }
@@ -115,24 +144,196 @@ impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for StakeOutcomeToken<'a> {
 impl<'a> StakeOutcomeToken<'a> {
     pub const DISCRIMINATOR: &'a u8 = &7;
 
+    pub(crate) fn with_accounts(
+        accounts: StakeOutcomeTokenAccounts<'a>,
+        data: &'a [u8],
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts,
+            data: StakeOutcomeTokenData::try_from(data)?,
+        })
+    }
+
     pub fn process(&self) -> ProgramResult {
-        // check market and its data, and update
-        let mut market_data = self.accounts.market.try_borrow_mut()?;
-        let market = Market::load_mut(&mut market_data)?;
+        // receipts can change hands, so they cannot be locked, and only the
+        // farmer may lock its own stake
+        if (self.accounts.receipt.is_some() || self.accounts.is_gift())
+            && self.data.lockup_tier.is_some()
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (amount_sub_fees, market_seed, market_bump) = {
+            // check market and its data, and update
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_seed = market.seed();
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market_seed.as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if !market.is_open() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let fees = math::fee_calculation(self.data.amount, market.fee_bps())?;
+            let amount_sub_fees = self
+                .data
+                .amount
+                .checked_sub(fees)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            let is_yes = if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                true
+            } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
+                false
+            } else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+            // locked stakes and stakes on the thinner side weigh more in the reward split
+            let (lockup_duration, lockup_multiplier_bps) = match self.data.lockup_tier {
+                None => (0, 10_000),
+                Some(tier) => market
+                    .lockup_tier(tier)
+                    .map(|(duration, multiplier_bps)| (duration, multiplier_bps as u64))
+                    .ok_or(ProgramError::InvalidInstructionData)?,
+            };
+            // receipts can be redeemed by anyone and always weigh 1x
+            let weight = if self.accounts.receipt.is_some() {
+                amount_sub_fees
+            } else {
+                math::pro_rata(
+                    math::pro_rata(amount_sub_fees, lockup_multiplier_bps, 10_000)?,
+                    market.imbalance_multiplier_bps(is_yes, amount_sub_fees)?,
+                    10_000,
+                )?
+            };
+
+            // update market state within the market's stake limits
+            market.check_min_stake(amount_sub_fees)?;
+            let market_outcome_vault_bump = if is_yes {
+                market.add_yes_fees(fees)?;
+                market.add_yes_staked(amount_sub_fees, weight)?;
+                market.check_total_stake(market.total_yes_staked())?;
+                market.yes_vault_bump
+            } else {
+                market.add_no_fees(fees)?;
+                market.add_no_staked(amount_sub_fees, weight)?;
+                market.check_total_stake(market.total_no_staked())?;
+                market.no_vault_bump
+            };
+
+            // check market vault
+            let market_outcome_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.outcome_mint.address().as_ref(),
+                ],
+                Some(market_outcome_vault_bump),
+                &crate::ID,
+            );
+            if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // credit the stake to the farmer position unless receipts are minted,
+            // which can change hands and so cannot be capped per farmer
+            match &self.accounts.receipt {
+                None => {
+                    let farmer_staked = self.credit_farmer_position(
+                        is_yes,
+                        amount_sub_fees,
+                        weight,
+                        lockup_duration,
+                    )?;
+                    market.check_farmer_stake(farmer_staked)?;
+                }
+                Some(receipt) => {
+                    if market.max_stake_per_farmer() > 0 || market.max_reward_bps_per_farmer() > 0 {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+
+                    // check receipt mint
+                    let receipt_mint_bump = if is_yes {
+                        market.receipt_yes_mint_bump
+                    } else {
+                        market.receipt_no_mint_bump
+                    };
+                    let receipt_mint_address = Address::derive_address(
+                        &[
+                            constants::RECEIPT_MINT_SEED,
+                            self.accounts.market.address().as_ref(),
+                            self.accounts.outcome_mint.address().as_ref(),
+                        ],
+                        Some(receipt_mint_bump),
+                        &crate::ID,
+                    );
+                    if &receipt_mint_address != receipt.receipt_mint.address() {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+                }
+            }
+
+            (amount_sub_fees, market_seed, market.bump)
+        };
+
+        // transfer from payer to market vault
+        MintInterface::transfer(
+            self.accounts.payer_ata,
+            self.accounts.market_outcome_vault,
+            self.accounts.payer,
+            self.data.amount,
+        )?;
 
-        let market_address = Address::derive_address(
-            &[constants::MARKET_SEED, self.data.market_id],
-            Some(market.bump),
+        // mint receipts for the staked amount
+        if let Some(receipt) = &self.accounts.receipt {
+            let bump_binding = &[market_bump];
+            let seeds = &[
+                Seed::from(constants::MARKET_SEED),
+                Seed::from(market_seed.as_ref()),
+                Seed::from(bump_binding),
+            ];
+
+            MintInterface::mint_to_signed(
+                receipt.receipt_mint,
+                receipt.farmer_receipt_ata,
+                self.accounts.market,
+                amount_sub_fees,
+                seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn credit_farmer_position(
+        &self,
+        is_yes: bool,
+        amount: u64,
+        weight: u64,
+        lockup_duration: i64,
+    ) -> Result<u64, ProgramError> {
+        // only derived here, receipt stakes never touch the farmer position
+        let (farmer_position_address, farmer_position_bump) = Address::find_program_address(
+            &[
+                constants::FARMER_POSITION_SEED,
+                self.accounts.market.address().as_ref(),
+                self.accounts.farmer.address().as_ref(),
+            ],
             &crate::ID,
         );
-        if &market_address != self.accounts.market.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        if !market.is_open() {
+        if &farmer_position_address != self.accounts.farmer_position.address() {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        let bump_binding = &[self.accounts.farmer_position_bump];
+        let bump_binding = &[farmer_position_bump];
         let seeds = &[
             Seed::from(constants::FARMER_POSITION_SEED),
             Seed::from(self.accounts.market.address().as_ref()),
@@ -141,7 +342,7 @@ impl<'a> StakeOutcomeToken<'a> {
         ];
         Account::init_if_needed::<FarmerPosition>(
             self.accounts.farmer_position,
-            self.accounts.farmer,
+            self.accounts.payer,
             seeds,
         )?;
 
@@ -150,35 +351,24 @@ impl<'a> StakeOutcomeToken<'a> {
 
         // check farmer position
         if !farmer_position.is_initialized {
-            farmer_position.set_inner(self.accounts.farmer_position_bump);
+            farmer_position.set_inner(farmer_position_bump);
+        }
+        // restarts the early-exit penalty decay, which a gift must not do to
+        // someone else's position
+        let now = Clock::get()?.unix_timestamp;
+        if !self.accounts.is_gift() {
+            farmer_position.set_last_stake_timestamp(now);
+        }
+        if lockup_duration > 0 {
+            farmer_position.lock(is_yes, amount, now, now.saturating_add(lockup_duration))?;
         }
 
-        let fees = math::fee_calculation(self.data.amount, market.fee_bps())?;
-        let amount_sub_fees = self
-            .data
-            .amount
-            .checked_sub(fees)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-
-        // update market and farmer position state
-        if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
-            market.add_yes_fees(fees)?;
-            market.add_yes_staked(amount_sub_fees)?;
-            farmer_position.add_yes_staked(amount_sub_fees)?;
-        } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
-            market.add_no_fees(fees)?;
-            market.add_no_staked(amount_sub_fees)?;
-            farmer_position.add_no_staked(amount_sub_fees)?;
+        if is_yes {
+            farmer_position.add_yes_staked(amount, weight)?;
+            Ok(farmer_position.yes_staked())
         } else {
-            return Err(ProgramError::InvalidAccountData);
-        };
-
-        // transfer from farmer to market vault
-        MintInterface::transfer(
-            self.accounts.farmer_ata,
-            self.accounts.market_outcome_vault,
-            self.accounts.farmer,
-            self.data.amount,
-        )
+            farmer_position.add_no_staked(amount, weight)?;
+            Ok(farmer_position.no_staked())
+        }
     }
 }
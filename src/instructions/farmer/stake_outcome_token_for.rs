@@ -0,0 +1,40 @@
+use pinocchio::{AccountView, ProgramResult, error::ProgramError};
+
+use crate::{
+    instructions::{StakeOutcomeToken, StakeOutcomeTokenAccounts},
+    utils::Account,
+};
+
+/// `StakeOutcomeToken` funded by one signer and credited to a beneficiary's
+/// position, which only the beneficiary can unstake or claim. Gifts are
+/// unlocked, leave the position's penalty decay alone and count against the
+/// beneficiary's per-farmer cap.
+pub struct StakeOutcomeTokenFor<'a> {
+    stake: StakeOutcomeToken<'a>,
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for StakeOutcomeTokenFor<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        let [payer, beneficiary, stake @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(payer)?;
+
+        let accounts = StakeOutcomeTokenAccounts::from_stake(payer, beneficiary, stake, &[])?;
+
+        Ok(Self {
+            stake: StakeOutcomeToken::with_accounts(accounts, data)?,
+        })
+    }
+}
+
+impl<'a> StakeOutcomeTokenFor<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &32;
+
+    pub fn process(&self) -> ProgramResult {
+        self.stake.process()
+    }
+}
@@ -0,0 +1,253 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::{FarmerPosition, Market},
+    utils::{Account, MintInterface, constants, math},
+};
+
+pub struct SwitchSide<'a> {
+    accounts: SwitchSideAccounts<'a>,
+    data: SwitchSideData,
+}
+
+struct SwitchSideAccounts<'a> {
+    farmer: &'a AccountView,
+    market: &'a AccountView,
+    farmer_position: &'a AccountView,
+    from_mint: &'a AccountView, // outcome currently staked
+    to_mint: &'a AccountView,   // opposite outcome
+    farmer_from_ata: &'a AccountView,
+    farmer_to_ata: &'a AccountView,
+    market_from_vault: &'a AccountView,
+    market_to_vault: &'a AccountView,
+}
+
+struct SwitchSideData {
+    amount: u64,    // staked tokens moved off the current side
+    to_amount: u64, // opposite outcome tokens supplied by the farmer
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwitchSideData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+        let to_amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        if amount == 0 || to_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { amount, to_amount })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SwitchSideAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            farmer,
+            market,
+            farmer_position,
+            from_mint,
+            to_mint,
+            farmer_from_ata,
+            farmer_to_ata,
+            market_from_vault,
+            market_to_vault,
+            _token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(farmer)?;
+
+        Ok(Self {
+            farmer,
+            market,
+            farmer_position,
+            from_mint,
+            to_mint,
+            farmer_from_ata,
+            farmer_to_ata,
+            market_from_vault,
+            market_to_vault,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a [AccountView], &'a [u8])> for SwitchSide<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, data): (&'a [AccountView], &'a [u8])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SwitchSideAccounts::try_from(accounts)?,
+            data: SwitchSideData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SwitchSide<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &29;
+
+    pub fn process(&self) -> ProgramResult {
+        let (market_seed, market_bump) = {
+            // check market and its data
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_seed = market.seed();
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market_seed.as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if !market.is_open() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // switching releases staked tokens at once, which unstake rules forbid
+            if market.unstake_cooldown() > 0 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // check farmer position
+            let mut farmer_position_data = self.accounts.farmer_position.try_borrow_mut()?;
+            let farmer_position = FarmerPosition::load_mut(&mut farmer_position_data)?;
+
+            let farmer_position_address = Address::derive_address(
+                &[
+                    constants::FARMER_POSITION_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.farmer.address().as_ref(),
+                ],
+                Some(farmer_position.bump),
+                &crate::ID,
+            );
+            if &farmer_position_address != self.accounts.farmer_position.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // a locked stake cannot leave its side, and the boost does not follow it
+            let now = Clock::get()?.unix_timestamp;
+
+            // the new side's stake pays the fee like any stake, so switching back and
+            // forth is never free
+            let fees = math::fee_calculation(self.data.to_amount, market.fee_bps())?;
+            let to_amount_sub_fees = self
+                .data
+                .to_amount
+                .checked_sub(fees)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            market.check_min_stake(to_amount_sub_fees)?;
+
+            // update market and farmer position state, weighing the new side's stake on
+            // the imbalance curve and within its stake caps
+            let (yes_mint, no_mint) = (market.outcome_yes_mint(), market.outcome_no_mint());
+            let (from_vault_bump, to_vault_bump) = if &yes_mint == self.accounts.from_mint.address()
+                && &no_mint == self.accounts.to_mint.address()
+            {
+                if self.data.amount > farmer_position.unlocked_yes_staked(now) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let weight = farmer_position.sub_yes_staked(self.data.amount)?;
+                market.sub_yes_staked(self.data.amount, weight)?;
+                let to_weight = math::pro_rata(
+                    to_amount_sub_fees,
+                    market.imbalance_multiplier_bps(false, to_amount_sub_fees)?,
+                    10_000,
+                )?;
+                market.add_no_fees(fees)?;
+                market.add_no_staked(to_amount_sub_fees, to_weight)?;
+                farmer_position.add_no_staked(to_amount_sub_fees, to_weight)?;
+                market.check_total_stake(market.total_no_staked())?;
+                market.check_farmer_stake(farmer_position.no_staked())?;
+                (market.yes_vault_bump, market.no_vault_bump)
+            } else if &no_mint == self.accounts.from_mint.address()
+                && &yes_mint == self.accounts.to_mint.address()
+            {
+                if self.data.amount > farmer_position.unlocked_no_staked(now) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let weight = farmer_position.sub_no_staked(self.data.amount)?;
+                market.sub_no_staked(self.data.amount, weight)?;
+                let to_weight = math::pro_rata(
+                    to_amount_sub_fees,
+                    market.imbalance_multiplier_bps(true, to_amount_sub_fees)?,
+                    10_000,
+                )?;
+                market.add_yes_fees(fees)?;
+                market.add_yes_staked(to_amount_sub_fees, to_weight)?;
+                farmer_position.add_yes_staked(to_amount_sub_fees, to_weight)?;
+                market.check_total_stake(market.total_yes_staked())?;
+                market.check_farmer_stake(farmer_position.yes_staked())?;
+                (market.no_vault_bump, market.yes_vault_bump)
+            } else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+            // check market vaults
+            let market_from_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.from_mint.address().as_ref(),
+                ],
+                Some(from_vault_bump),
+                &crate::ID,
+            );
+            if &market_from_vault_address != self.accounts.market_from_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let market_to_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.to_mint.address().as_ref(),
+                ],
+                Some(to_vault_bump),
+                &crate::ID,
+            );
+            if &market_to_vault_address != self.accounts.market_to_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            (market_seed, market.bump)
+        };
+
+        // take the opposite tokens, then give back the unstaked ones
+        MintInterface::transfer(
+            self.accounts.farmer_to_ata,
+            self.accounts.market_to_vault,
+            self.accounts.farmer,
+            self.data.to_amount,
+        )?;
+
+        let bump_binding = &[market_bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        MintInterface::transfer_signed(
+            self.accounts.market_from_vault,
+            self.accounts.farmer_from_ata,
+            self.accounts.market,
+            self.data.amount,
+            seeds,
+        )
+    }
+}
@@ -1,4 +1,9 @@
-use pinocchio::{AccountView, Address, ProgramResult, cpi::Seed, error::ProgramError};
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
 
 use crate::{
     states::{FarmerPosition, Market},
@@ -7,7 +12,7 @@ use crate::{
 
 pub struct UnstakeOutcomeToken<'a> {
     accounts: UnstakeOutcomeTokenAccounts<'a>,
-    data: UnstakeOutcomeTokenData<'a>,
+    data: UnstakeOutcomeTokenData,
 }
 
 struct UnstakeOutcomeTokenAccounts<'a> {
@@ -19,17 +24,15 @@ struct UnstakeOutcomeTokenAccounts<'a> {
     market_outcome_vault: &'a AccountView,
 }
 
-struct UnstakeOutcomeTokenData<'a> {
+struct UnstakeOutcomeTokenData {
     amount: u64,
-    market_id: &'a [u8],
 }
 
-impl<'a> TryFrom<&'a [u8]> for UnstakeOutcomeTokenData<'a> {
+impl<'a> TryFrom<&'a [u8]> for UnstakeOutcomeTokenData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        // 8 bytes for amount, rest for market ID
-        if data.len() < constants::MIN_ID_LENGTH + 8 {
+        if data.len() != size_of::<Self>() {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -38,9 +41,7 @@ impl<'a> TryFrom<&'a [u8]> for UnstakeOutcomeTokenData<'a> {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let market_id = &data[8..];
-
-        Ok(Self { amount, market_id })
+        Ok(Self { amount })
     }
 }
 
@@ -63,18 +64,6 @@ impl<'a> TryFrom<&'a [AccountView]> for UnstakeOutcomeTokenAccounts<'a> {
 
         Account::signer_check(farmer)?;
 
-        let (market_outcome_vault_address, _) = Address::find_program_address(
-            &[
-                constants::MARKET_SEED,
-                market.address().as_ref(),
-                outcome_mint.address().as_ref(),
-            ],
-            &crate::ID,
-        );
-        if &market_outcome_vault_address != market_outcome_vault.address() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
         Ok(Self {
             farmer,
             market,
@@ -101,13 +90,14 @@ impl<'a> UnstakeOutcomeToken<'a> {
     pub const DISCRIMINATOR: &'a u8 = &8;
 
     pub fn process(&self) -> ProgramResult {
-        let (market_bump, should_close_position) = {
+        let (market_seed, market_bump, withdraw_amount, should_close_position) = {
             // check market and its data (mint)
             let mut market_data = self.accounts.market.try_borrow_mut()?;
             let market = Market::load_mut(&mut market_data)?;
 
+            let market_seed = market.seed();
             let market_address = Address::derive_address(
-                &[constants::MARKET_SEED, self.data.market_id],
+                &[constants::MARKET_SEED, market_seed.as_ref()],
                 Some(market.bump),
                 &crate::ID,
             );
@@ -135,20 +125,84 @@ impl<'a> UnstakeOutcomeToken<'a> {
                 return Err(ProgramError::InvalidAccountData);
             }
 
+            // locked stakes stay until the lock expires
+            let now = Clock::get()?.unix_timestamp;
+
             // update accounts
-            if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
-                farmer_position.sub_yes_staked(self.data.amount)?;
-                market.sub_yes_staked(self.data.amount)?;
+            let is_yes = if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                if self.data.amount > farmer_position.unlocked_yes_staked(now) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let weight = farmer_position.sub_yes_staked(self.data.amount)?;
+                market.sub_yes_staked(self.data.amount, weight)?;
+                true
             } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
-                farmer_position.sub_no_staked(self.data.amount)?;
-                market.sub_no_staked(self.data.amount)?;
+                if self.data.amount > farmer_position.unlocked_no_staked(now) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                let weight = farmer_position.sub_no_staked(self.data.amount)?;
+                market.sub_no_staked(self.data.amount, weight)?;
+                false
             } else {
                 return Err(ProgramError::InvalidAccountData);
+            };
+
+            // with a cooldown, either queue the withdrawal or charge the early-exit penalty
+            let unstake_cooldown = market.unstake_cooldown();
+            let withdraw_amount = if unstake_cooldown == 0 {
+                self.data.amount
+            } else if market.early_exit_penalty_bps() == 0 {
+                let unlock = now.saturating_add(unstake_cooldown);
+                if is_yes {
+                    farmer_position.queue_yes_withdrawal(self.data.amount, unlock)?;
+                } else {
+                    farmer_position.queue_no_withdrawal(self.data.amount, unlock)?;
+                }
+                market.add_pending_withdrawals(self.data.amount);
+                0
+            } else {
+                let penalty = market.early_exit_penalty(
+                    self.data.amount,
+                    farmer_position.last_stake_timestamp(),
+                    now,
+                )?;
+                if is_yes {
+                    market.add_yes_penalties(penalty)?;
+                } else {
+                    market.add_no_penalties(penalty)?;
+                }
+                self.data
+                    .amount
+                    .checked_sub(penalty)
+                    .ok_or(ProgramError::ArithmeticOverflow)?
+            };
+
+            // check market vault
+            let market_outcome_vault_bump = if is_yes {
+                market.yes_vault_bump
+            } else {
+                market.no_vault_bump
+            };
+            let market_outcome_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.outcome_mint.address().as_ref(),
+                ],
+                Some(market_outcome_vault_bump),
+                &crate::ID,
+            );
+            if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
             }
 
             (
+                market_seed,
                 market.bump,
-                farmer_position.yes_staked() == 0 && farmer_position.no_staked() == 0,
+                withdraw_amount,
+                farmer_position.yes_staked() == 0
+                    && farmer_position.no_staked() == 0
+                    && !farmer_position.has_pending_withdrawal(),
             )
         };
 
@@ -157,11 +211,16 @@ impl<'a> UnstakeOutcomeToken<'a> {
             Account::close(self.accounts.farmer_position, self.accounts.farmer)?;
         }
 
+        // queued withdrawals are paid by WithdrawUnstaked once the cooldown is over
+        if withdraw_amount == 0 {
+            return Ok(());
+        }
+
         // transfer back to farmer ata
         let bump_binding = &[market_bump];
         let seeds = &[
             Seed::from(constants::MARKET_SEED),
-            Seed::from(self.data.market_id),
+            Seed::from(market_seed.as_ref()),
             Seed::from(bump_binding),
         ];
 
@@ -169,7 +228,7 @@ impl<'a> UnstakeOutcomeToken<'a> {
             self.accounts.market_outcome_vault,
             self.accounts.farmer_ata,
             self.accounts.market,
-            self.data.amount,
+            withdraw_amount,
             seeds,
         )
     }
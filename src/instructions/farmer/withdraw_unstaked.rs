@@ -0,0 +1,163 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult,
+    cpi::Seed,
+    error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    states::{FarmerPosition, Market},
+    utils::{Account, MintInterface, constants},
+};
+
+pub struct WithdrawUnstaked<'a> {
+    accounts: WithdrawUnstakedAccounts<'a>,
+}
+
+struct WithdrawUnstakedAccounts<'a> {
+    farmer: &'a AccountView,
+    market: &'a AccountView,
+    farmer_position: &'a AccountView,
+    outcome_mint: &'a AccountView,
+    farmer_ata: &'a AccountView,
+    market_outcome_vault: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for WithdrawUnstakedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [
+            farmer,
+            market,
+            farmer_position,
+            outcome_mint,
+            farmer_ata,
+            market_outcome_vault,
+            _token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Account::signer_check(farmer)?;
+
+        Ok(Self {
+            farmer,
+            market,
+            farmer_position,
+            outcome_mint,
+            farmer_ata,
+            market_outcome_vault,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for WithdrawUnstaked<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: WithdrawUnstakedAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> WithdrawUnstaked<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &35;
+
+    pub fn process(&self) -> ProgramResult {
+        let (market_seed, market_bump, amount, should_close_position) = {
+            // check market, withdrawals are allowed whatever its status
+            let mut market_data = self.accounts.market.try_borrow_mut()?;
+            let market = Market::load_mut(&mut market_data)?;
+
+            let market_seed = market.seed();
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, market_seed.as_ref()],
+                Some(market.bump),
+                &crate::ID,
+            );
+            if &market_address != self.accounts.market.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // check farmer position and its cooldown
+            let mut farmer_position_data = self.accounts.farmer_position.try_borrow_mut()?;
+            let farmer_position = FarmerPosition::load_mut(&mut farmer_position_data)?;
+
+            let farmer_position_address = Address::derive_address(
+                &[
+                    constants::FARMER_POSITION_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.farmer.address().as_ref(),
+                ],
+                Some(farmer_position.bump),
+                &crate::ID,
+            );
+            if &farmer_position_address != self.accounts.farmer_position.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if Clock::get()?.unix_timestamp < farmer_position.withdrawal_unlock() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let (amount, market_outcome_vault_bump) =
+                if &market.outcome_yes_mint() == self.accounts.outcome_mint.address() {
+                    (farmer_position.take_yes_withdrawal(), market.yes_vault_bump)
+                } else if &market.outcome_no_mint() == self.accounts.outcome_mint.address() {
+                    (farmer_position.take_no_withdrawal(), market.no_vault_bump)
+                } else {
+                    return Err(ProgramError::InvalidAccountData);
+                };
+            if amount == 0 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            market.sub_pending_withdrawals(amount);
+
+            // check market vault
+            let market_outcome_vault_address = Address::derive_address(
+                &[
+                    constants::MARKET_SEED,
+                    self.accounts.market.address().as_ref(),
+                    self.accounts.outcome_mint.address().as_ref(),
+                ],
+                Some(market_outcome_vault_bump),
+                &crate::ID,
+            );
+            if &market_outcome_vault_address != self.accounts.market_outcome_vault.address() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            (
+                market_seed,
+                market.bump,
+                amount,
+                farmer_position.yes_staked() == 0
+                    && farmer_position.no_staked() == 0
+                    && !farmer_position.has_pending_withdrawal(),
+            )
+        };
+
+        // nothing left on the position, close it
+        if should_close_position {
+            Account::close(self.accounts.farmer_position, self.accounts.farmer)?;
+        }
+
+        // transfer back to farmer ata
+        let bump_binding = &[market_bump];
+        let seeds = &[
+            Seed::from(constants::MARKET_SEED),
+            Seed::from(market_seed.as_ref()),
+            Seed::from(bump_binding),
+        ];
+
+        MintInterface::transfer_signed(
+            self.accounts.market_outcome_vault,
+            self.accounts.farmer_ata,
+            self.accounts.market,
+            amount,
+            seeds,
+        )
+    }
+}
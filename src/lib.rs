@@ -1,5 +1,6 @@
 #![no_std]
 
+mod errors;
 mod instructions;
 mod states;
 mod utils;
@@ -10,8 +11,15 @@ use pinocchio::{
 };
 
 use crate::instructions::{
-    AddIncentives, CancelMarket, ClaimFees, ClaimRewards, CreateMarket, Initialize, SettleMarket,
-    StakeOutcomeToken, UnstakeOutcomeToken, UpdateConfig, WithdrawTreasury,
+    AcceptMarketOwnership, AddBriberToAllowlist, AddIncentives, CancelMarket, ClaimFees,
+    ClaimPenalties, ClaimRewards, ClaimRewardsBatch, CreateMarket, CreateMarketMetadata,
+    CreateReceiptMint, DistributeTreasury, Initialize, MigrateAccount, ReclaimRewardOverflow,
+    RedeemReceipt, RegisterOutcomeMints, RemoveBriberFromAllowlist, SetDisputeWindow,
+    SetFeeRecipient, SetImbalanceBoost, SetLockupTiers, SetPositionDelegate, SetRevenueSplit,
+    SetSettlementCommittee, SetSettlementOracle, SetUnstakeRules, SettleMarket,
+    SettleMarketWithAttestation, StakeOutcomeToken, StakeOutcomeTokenFor, SwitchSide,
+    TransferMarketOwnership, UnstakeOutcomeToken, UpdateConfig, UpdateMarketFee,
+    UpdateMarketMetadata, VoteSettlement, WithdrawTreasury, WithdrawTreasuryMint, WithdrawUnstaked,
 };
 
 no_allocator!();
@@ -35,10 +43,8 @@ fn process_instruction(
         Some((AddIncentives::DISCRIMINATOR, data)) => {
             AddIncentives::try_from((accounts, data))?.process()
         }
-        Some((CancelMarket::DISCRIMINATOR, data)) => {
-            CancelMarket::try_from((accounts, data))?.process()
-        }
-        Some((ClaimFees::DISCRIMINATOR, data)) => ClaimFees::try_from((accounts, data))?.process(),
+        Some((CancelMarket::DISCRIMINATOR, _)) => CancelMarket::try_from(accounts)?.process(),
+        Some((ClaimFees::DISCRIMINATOR, _)) => ClaimFees::try_from(accounts)?.process(),
         Some((SettleMarket::DISCRIMINATOR, data)) => {
             SettleMarket::try_from((accounts, data))?.process()
         }
@@ -51,12 +57,98 @@ fn process_instruction(
         Some((UnstakeOutcomeToken::DISCRIMINATOR, data)) => {
             UnstakeOutcomeToken::try_from((accounts, data))?.process()
         }
-        Some((ClaimRewards::DISCRIMINATOR, data)) => {
-            ClaimRewards::try_from((accounts, data))?.process()
-        }
+        Some((ClaimRewards::DISCRIMINATOR, _)) => ClaimRewards::try_from(accounts)?.process(),
         Some((UpdateConfig::DISCRIMINATOR, data)) => {
             UpdateConfig::try_from((accounts, data))?.process()
         }
+        Some((MigrateAccount::DISCRIMINATOR, data)) => {
+            MigrateAccount::try_from((accounts, data))?.process()
+        }
+        Some((CreateMarketMetadata::DISCRIMINATOR, data)) => {
+            CreateMarketMetadata::try_from((accounts, data))?.process()
+        }
+        Some((UpdateMarketMetadata::DISCRIMINATOR, data)) => {
+            UpdateMarketMetadata::try_from((accounts, data))?.process()
+        }
+        Some((UpdateMarketFee::DISCRIMINATOR, data)) => {
+            UpdateMarketFee::try_from((accounts, data))?.process()
+        }
+        Some((AddBriberToAllowlist::DISCRIMINATOR, _)) => {
+            AddBriberToAllowlist::try_from(accounts)?.process()
+        }
+        Some((RemoveBriberFromAllowlist::DISCRIMINATOR, _)) => {
+            RemoveBriberFromAllowlist::try_from(accounts)?.process()
+        }
+        Some((TransferMarketOwnership::DISCRIMINATOR, data)) => {
+            TransferMarketOwnership::try_from((accounts, data))?.process()
+        }
+        Some((AcceptMarketOwnership::DISCRIMINATOR, _)) => {
+            AcceptMarketOwnership::try_from(accounts)?.process()
+        }
+        Some((SetFeeRecipient::DISCRIMINATOR, data)) => {
+            SetFeeRecipient::try_from((accounts, data))?.process()
+        }
+        Some((WithdrawTreasuryMint::DISCRIMINATOR, data)) => {
+            WithdrawTreasuryMint::try_from((accounts, data))?.process()
+        }
+        Some((SetRevenueSplit::DISCRIMINATOR, data)) => {
+            SetRevenueSplit::try_from((accounts, data))?.process()
+        }
+        Some((DistributeTreasury::DISCRIMINATOR, _)) => {
+            DistributeTreasury::try_from(accounts)?.process()
+        }
+        Some((SetSettlementOracle::DISCRIMINATOR, data)) => {
+            SetSettlementOracle::try_from((accounts, data))?.process()
+        }
+        Some((SettleMarketWithAttestation::DISCRIMINATOR, data)) => {
+            SettleMarketWithAttestation::try_from((accounts, data))?.process()
+        }
+        Some((SetSettlementCommittee::DISCRIMINATOR, data)) => {
+            SetSettlementCommittee::try_from((accounts, data))?.process()
+        }
+        Some((VoteSettlement::DISCRIMINATOR, data)) => {
+            VoteSettlement::try_from((accounts, data))?.process()
+        }
+        Some((SetDisputeWindow::DISCRIMINATOR, data)) => {
+            SetDisputeWindow::try_from((accounts, data))?.process()
+        }
+        Some((ClaimRewardsBatch::DISCRIMINATOR, _)) => {
+            ClaimRewardsBatch::try_from(accounts)?.process()
+        }
+        Some((SwitchSide::DISCRIMINATOR, data)) => {
+            SwitchSide::try_from((accounts, data))?.process()
+        }
+        Some((CreateReceiptMint::DISCRIMINATOR, _)) => {
+            CreateReceiptMint::try_from(accounts)?.process()
+        }
+        Some((RedeemReceipt::DISCRIMINATOR, data)) => {
+            RedeemReceipt::try_from((accounts, data))?.process()
+        }
+        Some((StakeOutcomeTokenFor::DISCRIMINATOR, data)) => {
+            StakeOutcomeTokenFor::try_from((accounts, data))?.process()
+        }
+        Some((SetPositionDelegate::DISCRIMINATOR, data)) => {
+            SetPositionDelegate::try_from((accounts, data))?.process()
+        }
+        Some((SetUnstakeRules::DISCRIMINATOR, data)) => {
+            SetUnstakeRules::try_from((accounts, data))?.process()
+        }
+        Some((WithdrawUnstaked::DISCRIMINATOR, _)) => {
+            WithdrawUnstaked::try_from(accounts)?.process()
+        }
+        Some((SetLockupTiers::DISCRIMINATOR, data)) => {
+            SetLockupTiers::try_from((accounts, data))?.process()
+        }
+        Some((SetImbalanceBoost::DISCRIMINATOR, data)) => {
+            SetImbalanceBoost::try_from((accounts, data))?.process()
+        }
+        Some((ReclaimRewardOverflow::DISCRIMINATOR, _)) => {
+            ReclaimRewardOverflow::try_from(accounts)?.process()
+        }
+        Some((RegisterOutcomeMints::DISCRIMINATOR, data)) => {
+            RegisterOutcomeMints::try_from((accounts, data))?.process()
+        }
+        Some((ClaimPenalties::DISCRIMINATOR, _)) => ClaimPenalties::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
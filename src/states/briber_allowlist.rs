@@ -0,0 +1,53 @@
+use pinocchio::{Address, error::ProgramError};
+
+use crate::states::{AccountHeader, BRIBER_ALLOWLIST_DISCRIMINATOR, Versioned};
+
+#[repr(C)]
+pub struct BriberAllowlist {
+    header: AccountHeader,
+    briber: [u8; 32], // Address
+    pub bump: u8,
+}
+
+impl Versioned for BriberAllowlist {
+    const DISCRIMINATOR: u8 = BRIBER_ALLOWLIST_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            1 => Some(AccountHeader::LEN + 32 + 1), // briber, bump
+            _ => None,
+        }
+    }
+}
+
+impl BriberAllowlist {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, briber: &Address, bump: u8) {
+        self.briber = briber.to_bytes();
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn briber(&self) -> Address {
+        Address::new_from_array(self.briber)
+    }
+}
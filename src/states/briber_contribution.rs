@@ -0,0 +1,99 @@
+use pinocchio::{Address, ProgramResult, error::ProgramError};
+
+use crate::states::{AccountHeader, BRIBER_CONTRIBUTION_DISCRIMINATOR, Versioned};
+
+/// Incentives funded by a co-funder, i.e. anyone other than the market briber.
+#[repr(C)]
+pub struct BriberContribution {
+    header: AccountHeader,
+    market: [u8; 32],      // Address
+    contributor: [u8; 32], // Address
+    amount: [u8; 8],       // u64
+    pub bump: u8,
+    // v2
+    pub fees_claimed: bool,
+    returned_overflow: [u8; 8], // u64, reward overflow already paid back
+}
+
+impl Versioned for BriberContribution {
+    const DISCRIMINATOR: u8 = BRIBER_CONTRIBUTION_DISCRIMINATOR;
+    const VERSION: u8 = 2;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            1 => Some(AccountHeader::LEN + 32 + 32 + 8 + 1), // market, contributor, amount, bump
+            2 => Self::len_for_version(1).map(|len| len + 9), // fees claimed, returned overflow
+            _ => None,
+        }
+    }
+
+    fn migrate(&mut self, _address: &Address, from_version: u8, _data: &[u8]) -> ProgramResult {
+        // contributions used to close once their fees were claimed
+        if from_version < 2 {
+            self.fees_claimed = false;
+            self.returned_overflow = [0; 8];
+        }
+
+        Ok(())
+    }
+}
+
+impl BriberContribution {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, market: &Address, contributor: &Address, bump: u8) {
+        self.market = market.to_bytes();
+        self.contributor = contributor.to_bytes();
+        self.amount = 0u64.to_le_bytes();
+        self.bump = bump;
+        self.fees_claimed = false;
+        self.returned_overflow = [0; 8];
+    }
+
+    #[inline(always)]
+    pub fn contributor(&self) -> Address {
+        Address::new_from_array(self.contributor)
+    }
+
+    #[inline(always)]
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+
+    #[inline(always)]
+    pub fn add_amount(&mut self, amount: u64) -> ProgramResult {
+        let new_amount = self
+            .amount()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.amount = new_amount.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn returned_overflow(&self) -> u64 {
+        u64::from_le_bytes(self.returned_overflow)
+    }
+
+    #[inline(always)]
+    pub fn set_returned_overflow(&mut self, returned_overflow: u64) {
+        self.returned_overflow = returned_overflow.to_le_bytes();
+    }
+}
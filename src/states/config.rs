@@ -1,41 +1,100 @@
-use pinocchio::{Address, error::ProgramError};
+use pinocchio::{Address, ProgramResult, error::ProgramError};
+
+use crate::states::{AccountHeader, CONFIG_DISCRIMINATOR, Versioned};
 
 #[repr(C)]
 pub struct Config {
+    header: AccountHeader,
     authority: [u8; 32],     // Address
     fee_bps: [u8; 2],        // u16
     briber_fee_bps: [u8; 2], // u16
     pub bump: u8,
+    min_briber_fee_bps: [u8; 2], // u16
+    max_briber_fee_bps: [u8; 2], // u16
+    market_creation_mode: MarketCreationMode,
+    settlement_oracle: [u8; 32], // Address, zeroed when disabled
+    dispute_window: [u8; 8],     // i64, seconds before claims open on a settled market
 }
 
-impl Config {
-    pub const LEN: usize = size_of::<Self>();
+impl Versioned for Config {
+    const DISCRIMINATOR: u8 = CONFIG_DISCRIMINATOR;
+    const VERSION: u8 = 5;
+    const LEGACY_LEN: usize = 37;
 
     #[inline(always)]
-    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            0 => Some(Self::LEGACY_LEN),
+            1 => Some(AccountHeader::LEN + Self::LEGACY_LEN),
+            2 => Self::len_for_version(1).map(|len| len + 4), // briber fee bounds
+            3 => Self::len_for_version(2).map(|len| len + 1), // market creation mode
+            4 => Self::len_for_version(3).map(|len| len + 32), // settlement oracle
+            5 => Self::len_for_version(4).map(|len| len + 8), // dispute window
+            _ => None,
+        }
+    }
+
+    fn migrate(&mut self, _address: &Address, from_version: u8, _data: &[u8]) -> ProgramResult {
+        // pin both bounds to the global briber fee, so existing deployments keep
+        // charging it until the authority widens them
+        if from_version < 2 {
+            self.min_briber_fee_bps = self.briber_fee_bps;
+            self.max_briber_fee_bps = self.briber_fee_bps;
+        }
+
+        if from_version < 3 {
+            self.market_creation_mode = MarketCreationMode::Authority;
+        }
+
+        if from_version < 4 {
+            self.settlement_oracle = [0; 32];
+        }
+
+        if from_version < 5 {
+            self.dispute_window = [0; 8];
         }
 
+        Ok(())
+    }
+}
+
+impl Config {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
         // SAFETY: everything is u8 aligned and length checked
         Ok(unsafe { &*(data.as_ptr() as *const Self) })
     }
 
     #[inline(always)]
     pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::check_header(data)?;
 
         // SAFETY: everything is u8 aligned and length checked
         Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
     }
 
+    #[allow(clippy::too_many_arguments)]
     #[inline(always)]
-    pub fn set_inner(&mut self, authority: &Address, fee_bps: u16, briber_fee_bps: u16, bump: u8) {
+    pub fn set_inner(
+        &mut self,
+        authority: &Address,
+        fee_bps: u16,
+        briber_fee_bps: u16,
+        min_briber_fee_bps: u16,
+        max_briber_fee_bps: u16,
+        market_creation_mode: MarketCreationMode,
+        bump: u8,
+    ) {
         self.authority = authority.to_bytes();
         self.fee_bps = fee_bps.to_le_bytes();
         self.briber_fee_bps = briber_fee_bps.to_le_bytes();
+        self.min_briber_fee_bps = min_briber_fee_bps.to_le_bytes();
+        self.max_briber_fee_bps = max_briber_fee_bps.to_le_bytes();
+        self.market_creation_mode = market_creation_mode;
+        self.settlement_oracle = [0; 32];
+        self.dispute_window = [0; 8];
         self.bump = bump;
     }
 
@@ -50,14 +109,87 @@ impl Config {
     }
 
     #[inline(always)]
-    pub fn briber_fee_bps(&self) -> u16 {
-        u16::from_le_bytes(self.briber_fee_bps)
+    pub fn min_briber_fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.min_briber_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn max_briber_fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.max_briber_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn market_creation_mode(&self) -> MarketCreationMode {
+        self.market_creation_mode
+    }
+
+    /// Key whose ed25519 attestations can settle markets, if any.
+    #[inline(always)]
+    pub fn settlement_oracle(&self) -> Option<Address> {
+        (self.settlement_oracle != [0; 32]).then(|| Address::new_from_array(self.settlement_oracle))
+    }
+
+    #[inline(always)]
+    pub fn set_settlement_oracle(&mut self, settlement_oracle: &Address) {
+        self.settlement_oracle = settlement_oracle.to_bytes();
+    }
+
+    #[inline(always)]
+    pub fn dispute_window(&self) -> i64 {
+        i64::from_le_bytes(self.dispute_window)
+    }
+
+    #[inline(always)]
+    pub fn set_dispute_window(&mut self, dispute_window: i64) {
+        self.dispute_window = dispute_window.to_le_bytes();
+    }
+
+    /// Whether a briber may charge `briber_fee_bps` on a market.
+    #[inline(always)]
+    pub fn is_valid_briber_fee(&self, briber_fee_bps: u16) -> bool {
+        briber_fee_bps >= self.min_briber_fee_bps() && briber_fee_bps <= self.max_briber_fee_bps()
     }
 
     #[inline(always)]
-    pub fn update(&mut self, new_authority: &Address, new_fee_bps: u16, new_briber_fee_bps: u16) {
+    pub fn update(
+        &mut self,
+        new_authority: &Address,
+        new_fee_bps: u16,
+        new_briber_fee_bps: u16,
+        new_min_briber_fee_bps: u16,
+        new_max_briber_fee_bps: u16,
+        new_market_creation_mode: MarketCreationMode,
+    ) {
         self.authority = new_authority.to_bytes();
         self.fee_bps = new_fee_bps.to_le_bytes();
         self.briber_fee_bps = new_briber_fee_bps.to_le_bytes();
+        self.min_briber_fee_bps = new_min_briber_fee_bps.to_le_bytes();
+        self.max_briber_fee_bps = new_max_briber_fee_bps.to_le_bytes();
+        self.market_creation_mode = new_market_creation_mode;
+    }
+}
+
+/// Who may call `CreateMarket`.
+#[repr(u8)]
+#[derive(Clone, Copy)]
+pub enum MarketCreationMode {
+    /// The config authority must co-sign every market.
+    Authority = 0,
+    /// The briber must hold a `BriberAllowlist` entry.
+    Allowlist = 1,
+    /// Any briber can create markets.
+    Permissionless = 2,
+}
+
+impl TryFrom<u8> for MarketCreationMode {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Authority),
+            1 => Ok(Self::Allowlist),
+            2 => Ok(Self::Permissionless),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
     }
 }
@@ -1,21 +1,88 @@
-use pinocchio::{ProgramResult, error::ProgramError};
+use pinocchio::{Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::{AccountHeader, FARMER_POSITION_DISCRIMINATOR, Versioned},
+    utils::math,
+};
 
 #[repr(C)]
 pub struct FarmerPosition {
+    header: AccountHeader,
     yes_staked: [u8; 8], // u64
     no_staked: [u8; 8],  // u64
     pub is_initialized: bool,
     pub bump: u8,
+    // v2
+    delegate: [u8; 32],         // may claim on the farmer's behalf, zero if none
+    reward_recipient: [u8; 32], // owner of the payout accounts, zero for the farmer
+    // v3
+    last_stake_timestamp: [u8; 8],   // i64
+    pending_yes_withdrawal: [u8; 8], // u64, unstaked and waiting for the cooldown
+    pending_no_withdrawal: [u8; 8],  // u64, unstaked and waiting for the cooldown
+    withdrawal_unlock: [u8; 8],      // i64, when pending withdrawals can be taken
+    // v4
+    locked_until: [u8; 8], // i64, the locked stake cannot leave before, zero if never locked
+    yes_weight: [u8; 8],   // u64, YES stake boosted by lockup multipliers
+    no_weight: [u8; 8],    // u64, NO stake boosted by lockup multipliers
+    // v5
+    locked_yes_staked: [u8; 8], // u64, part of the YES stake held until locked_until
+    locked_no_staked: [u8; 8],  // u64, part of the NO stake held until locked_until
 }
 
-impl FarmerPosition {
-    pub const LEN: usize = size_of::<Self>();
+impl Versioned for FarmerPosition {
+    const DISCRIMINATOR: u8 = FARMER_POSITION_DISCRIMINATOR;
+    const VERSION: u8 = 5;
+    const LEGACY_LEN: usize = 18;
 
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            0 => Some(Self::LEGACY_LEN),
+            1 => Some(AccountHeader::LEN + Self::LEGACY_LEN),
+            2 => Self::len_for_version(1).map(|len| len + 64), // delegate, reward recipient
+            3 => Self::len_for_version(2).map(|len| len + 32), // unstake cooldown, withdrawals
+            4 => Self::len_for_version(3).map(|len| len + 24), // lockup, boosted weights
+            5 => Self::len_for_version(4).map(|len| len + 16), // locked stakes
+            _ => None,
+        }
+    }
+
+    fn migrate(&mut self, _address: &Address, from_version: u8, _data: &[u8]) -> ProgramResult {
+        if from_version < 2 {
+            self.delegate = [0; 32];
+            self.reward_recipient = [0; 32];
+        }
+
+        // no early-exit penalty for stakes made before cooldowns existed
+        if from_version < 3 {
+            self.last_stake_timestamp = [0; 8];
+            self.pending_yes_withdrawal = [0; 8];
+            self.pending_no_withdrawal = [0; 8];
+            self.withdrawal_unlock = [0; 8];
+        }
+
+        // stakes made before lockups weigh 1x
+        if from_version < 4 {
+            self.locked_until = [0; 8];
+            self.yes_weight = self.yes_staked;
+            self.no_weight = self.no_staked;
+        }
+
+        // locks used to cover the whole position
+        if from_version < 5 {
+            let is_locked = self.locked_until() > 0;
+            self.locked_yes_staked = if is_locked { self.yes_staked } else { [0; 8] };
+            self.locked_no_staked = if is_locked { self.no_staked } else { [0; 8] };
+        }
+
+        Ok(())
+    }
+}
+
+impl FarmerPosition {
     #[inline(always)]
     pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        };
+        Self::check_header(data)?;
 
         // SAFETY: everything is u8 aligned and length checked
         Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
@@ -25,6 +92,34 @@ impl FarmerPosition {
     pub fn set_inner(&mut self, bump: u8) {
         self.bump = bump;
         self.is_initialized = true;
+        self.delegate = [0; 32];
+        self.reward_recipient = [0; 32];
+        self.last_stake_timestamp = [0; 8];
+        self.pending_yes_withdrawal = [0; 8];
+        self.pending_no_withdrawal = [0; 8];
+        self.withdrawal_unlock = [0; 8];
+        self.locked_until = [0; 8];
+        self.yes_weight = [0; 8];
+        self.no_weight = [0; 8];
+        self.locked_yes_staked = [0; 8];
+        self.locked_no_staked = [0; 8];
+    }
+
+    #[inline(always)]
+    pub fn delegate(&self) -> Option<Address> {
+        (self.delegate != [0; 32]).then(|| Address::new_from_array(self.delegate))
+    }
+
+    #[inline(always)]
+    pub fn reward_recipient(&self) -> Option<Address> {
+        (self.reward_recipient != [0; 32]).then(|| Address::new_from_array(self.reward_recipient))
+    }
+
+    /// The zero address clears either field.
+    #[inline(always)]
+    pub fn set_delegate(&mut self, delegate: &Address, reward_recipient: &Address) {
+        self.delegate = delegate.to_bytes();
+        self.reward_recipient = reward_recipient.to_bytes();
     }
 
     #[inline(always)]
@@ -38,42 +133,193 @@ impl FarmerPosition {
     }
 
     #[inline(always)]
-    pub fn add_yes_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn yes_weight(&self) -> u64 {
+        u64::from_le_bytes(self.yes_weight)
+    }
+
+    #[inline(always)]
+    pub fn no_weight(&self) -> u64 {
+        u64::from_le_bytes(self.no_weight)
+    }
+
+    #[inline(always)]
+    pub fn add_yes_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = self
             .yes_staked()
             .checked_add(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .yes_weight()
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.yes_staked = new_amount.to_le_bytes();
+        self.yes_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
     #[inline(always)]
-    pub fn add_no_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn add_no_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = self
             .no_staked()
             .checked_add(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .no_weight()
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.no_staked = new_amount.to_le_bytes();
+        self.no_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
+    /// Removes `amount` from the YES stake along with its pro rata share of the
+    /// weight, which is returned.
     #[inline(always)]
-    pub fn sub_yes_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn sub_yes_staked(&mut self, amount: u64) -> Result<u64, ProgramError> {
         let new_amount = self
             .yes_staked()
             .checked_sub(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let weight = math::pro_rata(self.yes_weight(), amount, self.yes_staked())?;
+        let new_weight = self
+            .yes_weight()
+            .checked_sub(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.yes_staked = new_amount.to_le_bytes();
-        Ok(())
+        self.yes_weight = new_weight.to_le_bytes();
+        Ok(weight)
     }
 
+    /// Removes `amount` from the NO stake along with its pro rata share of the
+    /// weight, which is returned.
     #[inline(always)]
-    pub fn sub_no_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn sub_no_staked(&mut self, amount: u64) -> Result<u64, ProgramError> {
         let new_amount = self
             .no_staked()
             .checked_sub(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let weight = math::pro_rata(self.no_weight(), amount, self.no_staked())?;
+        let new_weight = self
+            .no_weight()
+            .checked_sub(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.no_staked = new_amount.to_le_bytes();
+        self.no_weight = new_weight.to_le_bytes();
+        Ok(weight)
+    }
+
+    #[inline(always)]
+    pub fn locked_until(&self) -> i64 {
+        i64::from_le_bytes(self.locked_until)
+    }
+
+    /// Locks `amount` of one side's stake until `until`. What an active lock
+    /// already holds is held until then too, an expired lock is replaced.
+    #[inline(always)]
+    pub fn lock(&mut self, is_yes: bool, amount: u64, now: i64, until: i64) -> ProgramResult {
+        if now >= self.locked_until() {
+            self.locked_yes_staked = [0; 8];
+            self.locked_no_staked = [0; 8];
+        }
+
+        let locked_staked = if is_yes {
+            &mut self.locked_yes_staked
+        } else {
+            &mut self.locked_no_staked
+        };
+        let new_amount = u64::from_le_bytes(*locked_staked)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *locked_staked = new_amount.to_le_bytes();
+        self.locked_until = until.max(self.locked_until()).to_le_bytes();
         Ok(())
     }
+
+    /// Part of the YES stake free to leave its side at `now`.
+    #[inline(always)]
+    pub fn unlocked_yes_staked(&self, now: i64) -> u64 {
+        if now >= self.locked_until() {
+            return self.yes_staked();
+        }
+        self.yes_staked()
+            .saturating_sub(u64::from_le_bytes(self.locked_yes_staked))
+    }
+
+    /// Part of the NO stake free to leave its side at `now`.
+    #[inline(always)]
+    pub fn unlocked_no_staked(&self, now: i64) -> u64 {
+        if now >= self.locked_until() {
+            return self.no_staked();
+        }
+        self.no_staked()
+            .saturating_sub(u64::from_le_bytes(self.locked_no_staked))
+    }
+
+    #[inline(always)]
+    pub fn last_stake_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.last_stake_timestamp)
+    }
+
+    #[inline(always)]
+    pub fn set_last_stake_timestamp(&mut self, now: i64) {
+        self.last_stake_timestamp = now.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn pending_yes_withdrawal(&self) -> u64 {
+        u64::from_le_bytes(self.pending_yes_withdrawal)
+    }
+
+    #[inline(always)]
+    pub fn pending_no_withdrawal(&self) -> u64 {
+        u64::from_le_bytes(self.pending_no_withdrawal)
+    }
+
+    #[inline(always)]
+    pub fn withdrawal_unlock(&self) -> i64 {
+        i64::from_le_bytes(self.withdrawal_unlock)
+    }
+
+    #[inline(always)]
+    pub fn has_pending_withdrawal(&self) -> bool {
+        self.pending_yes_withdrawal() > 0 || self.pending_no_withdrawal() > 0
+    }
+
+    /// Queues `amount` until `unlock`, which also delays earlier pending withdrawals.
+    #[inline(always)]
+    pub fn queue_yes_withdrawal(&mut self, amount: u64, unlock: i64) -> ProgramResult {
+        let new_amount = self
+            .pending_yes_withdrawal()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.pending_yes_withdrawal = new_amount.to_le_bytes();
+        self.withdrawal_unlock = unlock.to_le_bytes();
+        Ok(())
+    }
+
+    /// Queues `amount` until `unlock`, which also delays earlier pending withdrawals.
+    #[inline(always)]
+    pub fn queue_no_withdrawal(&mut self, amount: u64, unlock: i64) -> ProgramResult {
+        let new_amount = self
+            .pending_no_withdrawal()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.pending_no_withdrawal = new_amount.to_le_bytes();
+        self.withdrawal_unlock = unlock.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn take_yes_withdrawal(&mut self) -> u64 {
+        let amount = self.pending_yes_withdrawal();
+        self.pending_yes_withdrawal = [0; 8];
+        amount
+    }
+
+    #[inline(always)]
+    pub fn take_no_withdrawal(&mut self) -> u64 {
+        let amount = self.pending_no_withdrawal();
+        self.pending_no_withdrawal = [0; 8];
+        amount
+    }
 }
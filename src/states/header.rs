@@ -0,0 +1,118 @@
+use pinocchio::{Address, ProgramResult, error::ProgramError};
+
+/// Leading bytes shared by every program-owned state account.
+///
+/// Layouts are append-only: a new version may only add fields at the end of
+/// the struct, so an account can always be upgraded in place by `MigrateAccount`.
+/// Headerless (version 0) accounts are recognized by their exact size, so no
+/// versioned layout may reuse the `LEGACY_LEN` of any account type.
+#[repr(C)]
+pub struct AccountHeader {
+    discriminator: u8,
+    version: u8,
+}
+
+impl AccountHeader {
+    pub const LEN: usize = size_of::<Self>();
+
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, discriminator: u8, version: u8) {
+        self.discriminator = discriminator;
+        self.version = version;
+    }
+
+    #[inline(always)]
+    pub fn discriminator(&self) -> u8 {
+        self.discriminator
+    }
+
+    #[inline(always)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// Implemented by every state account that carries an [`AccountHeader`].
+pub trait Versioned: Sized {
+    /// Account-kind discriminator stored in the header.
+    const DISCRIMINATOR: u8;
+    /// Current layout version written by `set_inner` and `MigrateAccount`.
+    const VERSION: u8;
+    /// Size of the headerless layout deployed before versioning (version 0).
+    const LEGACY_LEN: usize;
+    /// Size of the current layout.
+    const LEN: usize = size_of::<Self>();
+
+    /// Size of the layout for `version`, or `None` if the version is unknown.
+    /// Every version is written as the previous one plus the bytes it appends,
+    /// never as `size_of::<Self>()`, which grows with each new version.
+    fn len_for_version(version: u8) -> Option<usize>;
+
+    /// Initializes the fields introduced after `from_version` once the account
+    /// stored at `address` has been resized to [`Self::LEN`]. `data` carries any
+    /// off-chain values the older layout did not record.
+    fn migrate(&mut self, _address: &Address, _from_version: u8, _data: &[u8]) -> ProgramResult {
+        Ok(())
+    }
+
+    /// Whether an account stored with `version` can be read with the current
+    /// layout, i.e. the version is known and did not change the account size.
+    #[inline(always)]
+    fn is_supported_version(version: u8) -> bool {
+        version != 0
+            && version <= Self::VERSION
+            && Self::len_for_version(version) == Some(Self::LEN)
+    }
+
+    /// Checks the header of `data` against the current layout, so an account of
+    /// another kind can never be loaded as `Self`, whatever its size.
+    #[inline(always)]
+    fn check_header(data: &[u8]) -> Result<(), ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let header = AccountHeader::load(data)?;
+        if header.discriminator() != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !Self::is_supported_version(header.version()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
+
+// account discriminators
+pub const CONFIG_DISCRIMINATOR: u8 = 1;
+pub const MARKET_DISCRIMINATOR: u8 = 2;
+pub const FARMER_POSITION_DISCRIMINATOR: u8 = 3;
+pub const MARKET_METADATA_DISCRIMINATOR: u8 = 4;
+pub const BRIBER_ALLOWLIST_DISCRIMINATOR: u8 = 5;
+pub const BRIBER_CONTRIBUTION_DISCRIMINATOR: u8 = 6;
+pub const REVENUE_SPLIT_DISCRIMINATOR: u8 = 7;
+pub const SETTLEMENT_COMMITTEE_DISCRIMINATOR: u8 = 8;
+pub const SETTLEMENT_VOTES_DISCRIMINATOR: u8 = 9;
+pub const OUTCOME_MINTS_DISCRIMINATOR: u8 = 10;
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: u8 = 0xff;
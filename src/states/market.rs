@@ -4,15 +4,22 @@ use pinocchio::{
     sysvars::{Sysvar, clock::Clock},
 };
 
+use crate::{
+    errors::ReflexError,
+    states::{AccountHeader, MARKET_DISCRIMINATOR, Versioned},
+    utils::{constants, math},
+};
+
 #[repr(C)]
 pub struct Market {
+    header: AccountHeader,
     briber: [u8; 32],                // Address
     incentive_mint: [u8; 32],        // Address
     outcome_yes_mint: [u8; 32],      // Address
     outcome_no_mint: [u8; 32],       // Address
     total_incentive_amount: [u8; 8], // u64
-    total_yes_staked: [u8; 8],       // u64
-    total_no_staked: [u8; 8],        // u64
+    total_yes_staked: [u8; 8],       // u64, once resolved the winning stake left to claim
+    total_no_staked: [u8; 8],        // u64, once resolved the winning stake left to claim
     available_yes_fees: [u8; 8],     // u64
     available_no_fees: [u8; 8],      // u64
     creation_timestamp: [u8; 8],     // i64
@@ -20,16 +27,207 @@ pub struct Market {
     status: MarketStatus,
     resolution: MarketResolution,
     pub bump: u8,
+    pub incentive_vault_bump: u8,
+    pub yes_vault_bump: u8,
+    pub no_vault_bump: u8,
+    seed: [u8; 32],
+    seed_len: u8,
+    id: [u8; constants::MAX_ID_LENGTH],
+    id_len: u8,
+    pending_briber: [u8; 32], // Address, zeroed when no transfer is pending
+    fee_recipient: [u8; 32],  // Address
+    briber_contribution: [u8; 8], // u64, funded by the briber
+    claimed_fees: [u8; 8],    // u64
+    contributor_count: u8,    // co-funders with a BriberContribution
+    pub briber_fees_claimed: bool,
+    protocol_fee_bps: [u8; 2],       // u16, snapshot of Config.fee_bps
+    settled_at: [u8; 8],             // i64, when the current resolution was set
+    dispute_window: [u8; 8],         // i64, snapshot of Config.dispute_window
+    unstake_cooldown: [u8; 8],       // i64, seconds, zero unstakes immediately
+    early_exit_penalty_bps: [u8; 2], // u16, zero queues unstakes for the cooldown instead
+    yes_penalties: [u8; 8],          // u64, early-exit penalties kept for YES stakers
+    no_penalties: [u8; 8],           // u64, early-exit penalties kept for NO stakers
+    lockup_tier_count: u8,
+    lockup_durations: [[u8; 8]; constants::MAX_LOCKUP_TIERS], // i64, seconds
+    lockup_multipliers_bps: [[u8; 2]; constants::MAX_LOCKUP_TIERS], // u16, 10_000 is 1x
+    total_yes_weight: [u8; 8], // u64, YES stakes boosted by their lockup multiplier
+    total_no_weight: [u8; 8],  // u64, NO stakes boosted by their lockup multiplier
+    imbalance_boost_bps: [u8; 2], // u16, extra weight for the thinner side, zero disables it
+    min_stake: [u8; 8],        // u64, smallest stake accepted, zero for any
+    max_stake_per_farmer: [u8; 8], // u64, per farmer and side, zero for no cap
+    max_total_stake: [u8; 8],  // u64, per side, zero for no cap
+    max_reward_bps_per_farmer: [u8; 2], // u16, of the incentives, zero for no cap
+    reward_overflow: [u8; 8],  // u64, rewards over the cap, owed to later claimers
+    claimed_weight: [u8; 8],   // u64, winning weight that claimed on a capped market
+    pending_withdrawals: [u8; 8], // u64, queued unstakes still in the outcome vaults
+    returned_overflow: [u8; 8], // u64, overflow handed back to the funders so far
+    briber_returned_overflow: [u8; 8], // u64, part of it the briber reclaimed
+    pub receipt_yes_mint_bump: u8, // set by CreateReceiptMint
+    pub receipt_no_mint_bump: u8, // set by CreateReceiptMint
+}
+
+impl Versioned for Market {
+    const DISCRIMINATOR: u8 = MARKET_DISCRIMINATOR;
+    const VERSION: u8 = 15;
+    const LEGACY_LEN: usize = 181;
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            0 => Some(Self::LEGACY_LEN),
+            1 => Some(AccountHeader::LEN + Self::LEGACY_LEN),
+            2 => Self::len_for_version(1).map(|len| len + 3), // vault bumps
+            3 => Self::len_for_version(2).map(|len| len + 34 + constants::MAX_ID_LENGTH), // seed, id
+            4 => Self::len_for_version(3).map(|len| len + 64), // pending briber, fee recipient
+            5 => Self::len_for_version(4).map(|len| len + 18), // co-funding
+            6 => Self::len_for_version(5).map(|len| len + 2),  // protocol fee snapshot
+            7 => Self::len_for_version(6).map(|len| len + 16), // settlement time, dispute window
+            8 => Self::len_for_version(7).map(|len| len + 26), // unstake rules, penalties
+            9 => Self::len_for_version(8).map(|len| len + 57), // lockup tiers, boosted totals
+            10 => Self::len_for_version(9).map(|len| len + 2), // imbalance boost
+            11 => Self::len_for_version(10).map(|len| len + 24), // stake limits
+            12 => Self::len_for_version(11).map(|len| len + 18), // reward cap, overflow
+            13 => Self::len_for_version(12).map(|len| len + 8), // pending withdrawals
+            14 => Self::len_for_version(13).map(|len| len + 16), // returned overflow
+            15 => Self::len_for_version(14).map(|len| len + 2), // receipt mint bumps
+            _ => None,
+        }
+    }
+
+    fn migrate(&mut self, address: &Address, from_version: u8, data: &[u8]) -> ProgramResult {
+        if from_version < 2 {
+            let find_vault_bump = |mint: &[u8; 32]| {
+                Address::find_program_address(
+                    &[constants::MARKET_SEED, address.as_ref(), mint],
+                    &crate::ID,
+                )
+                .1
+            };
+
+            self.incentive_vault_bump = find_vault_bump(&self.incentive_mint);
+            self.yes_vault_bump = find_vault_bump(&self.outcome_yes_mint);
+            self.no_vault_bump = find_vault_bump(&self.outcome_no_mint);
+        }
+
+        // markets created before hashed seeds were derived from the raw id,
+        // which must be supplied and is checked against the market address
+        if from_version < 3 {
+            if data.len() < constants::MIN_ID_LENGTH || data.len() > constants::MAX_LEGACY_ID_LENGTH
+            {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            let market_address = Address::derive_address(
+                &[constants::MARKET_SEED, data],
+                Some(self.bump),
+                &crate::ID,
+            );
+            if &market_address != address {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            self.set_id(data, &MarketSeed::from_legacy_id(data));
+        }
+
+        if from_version < 4 {
+            self.pending_briber = [0; 32];
+            self.fee_recipient = self.briber;
+        }
+
+        // every incentive so far was funded by the briber
+        if from_version < 5 {
+            self.briber_contribution = self.total_incentive_amount;
+            self.claimed_fees = [0; 8];
+            self.contributor_count = 0;
+            self.briber_fees_claimed = false;
+        }
+
+        // the fee in force at creation is unknown, the next AddIncentives takes it from Config
+        if from_version < 6 {
+            self.protocol_fee_bps = Self::UNSET_PROTOCOL_FEE_BPS.to_le_bytes();
+        }
+
+        // markets settled so far had no dispute window
+        if from_version < 7 {
+            self.settled_at = [0; 8];
+            self.dispute_window = [0; 8];
+        }
+
+        // existing markets keep unstaking immediately
+        if from_version < 8 {
+            self.unstake_cooldown = [0; 8];
+            self.early_exit_penalty_bps = [0; 2];
+            self.yes_penalties = [0; 8];
+            self.no_penalties = [0; 8];
+        }
+
+        // existing stakes were never locked and weigh 1x
+        if from_version < 9 {
+            self.lockup_tier_count = 0;
+            self.lockup_durations = [[0; 8]; constants::MAX_LOCKUP_TIERS];
+            self.lockup_multipliers_bps = [[0; 2]; constants::MAX_LOCKUP_TIERS];
+            self.total_yes_weight = self.total_yes_staked;
+            self.total_no_weight = self.total_no_staked;
+        }
+
+        if from_version < 10 {
+            self.imbalance_boost_bps = [0; 2];
+        }
+
+        // existing markets stay uncapped
+        if from_version < 11 {
+            self.set_stake_limits(0, 0, 0);
+        }
+
+        if from_version < 12 {
+            self.max_reward_bps_per_farmer = [0; 2];
+            self.reward_overflow = [0; 8];
+            self.claimed_weight = [0; 8];
+        }
+
+        // withdrawals queued before they were counted are unknown, which keeps
+        // those markets from being cancelled under their farmers
+        if from_version < 13 {
+            let may_have_queued = from_version >= 8
+                && self.unstake_cooldown() > 0
+                && self.early_exit_penalty_bps() == 0;
+            self.pending_withdrawals = if may_have_queued {
+                Self::UNKNOWN_PENDING_WITHDRAWALS.to_le_bytes()
+            } else {
+                [0; 8]
+            };
+        }
+
+        if from_version < 14 {
+            self.returned_overflow = [0; 8];
+            self.briber_returned_overflow = [0; 8];
+        }
+
+        // receipt mints may already exist, so their bumps are found once here
+        if from_version < 15 {
+            let find_receipt_mint_bump = |mint: &[u8; 32]| {
+                Address::find_program_address(
+                    &[constants::RECEIPT_MINT_SEED, address.as_ref(), mint],
+                    &crate::ID,
+                )
+                .1
+            };
+
+            self.receipt_yes_mint_bump = find_receipt_mint_bump(&self.outcome_yes_mint);
+            self.receipt_no_mint_bump = find_receipt_mint_bump(&self.outcome_no_mint);
+        }
+
+        Ok(())
+    }
 }
 
 impl Market {
-    pub const LEN: usize = size_of::<Self>();
+    const UNSET_PROTOCOL_FEE_BPS: u16 = u16::MAX;
+    const UNKNOWN_PENDING_WITHDRAWALS: u64 = u64::MAX;
 
     #[inline(always)]
     pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::check_header(data)?;
 
         // SAFETY: everything is u8 aligned and length checked
         Ok(unsafe { &*(data.as_ptr() as *const Self) })
@@ -37,14 +235,13 @@ impl Market {
 
     #[inline(always)]
     pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::check_header(data)?;
 
         // SAFETY: everything is u8 aligned and length checked
         Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
     }
 
+    #[allow(clippy::too_many_arguments)]
     #[inline(always)]
     pub fn set_inner(
         &mut self,
@@ -54,31 +251,175 @@ impl Market {
         outcome_no_mint: &Address,
         total_incentive_amount: u64,
         fee_bps: u16,
+        protocol_fee_bps: u16,
         bump: u8,
     ) -> ProgramResult {
         self.briber = briber.to_bytes();
+        self.pending_briber = [0; 32];
+        self.fee_recipient = briber.to_bytes();
         self.incentive_mint = incentive_mint.to_bytes();
         self.outcome_yes_mint = outcome_yes_mint.to_bytes();
         self.outcome_no_mint = outcome_no_mint.to_bytes();
         self.total_incentive_amount = total_incentive_amount.to_le_bytes();
+        self.briber_contribution = total_incentive_amount.to_le_bytes();
+        self.claimed_fees = 0u64.to_le_bytes();
+        self.contributor_count = 0;
+        self.briber_fees_claimed = false;
         self.total_yes_staked = 0u64.to_le_bytes();
         self.total_no_staked = 0u64.to_le_bytes();
         self.available_yes_fees = 0u64.to_le_bytes();
         self.available_no_fees = 0u64.to_le_bytes();
         self.creation_timestamp = Clock::get()?.unix_timestamp.to_le_bytes();
         self.fee_bps = fee_bps.to_le_bytes();
+        self.protocol_fee_bps = protocol_fee_bps.to_le_bytes();
         self.status = MarketStatus::Open;
         self.resolution = MarketResolution::None;
         self.bump = bump;
+        self.unstake_cooldown = [0; 8];
+        self.early_exit_penalty_bps = [0; 2];
+        self.yes_penalties = [0; 8];
+        self.no_penalties = [0; 8];
+        self.set_lockup_tiers(&[]);
+        self.total_yes_weight = [0; 8];
+        self.total_no_weight = [0; 8];
+        self.imbalance_boost_bps = [0; 2];
+        self.set_stake_limits(0, 0, 0);
+        self.max_reward_bps_per_farmer = [0; 2];
+        self.reward_overflow = [0; 8];
+        self.claimed_weight = [0; 8];
+        self.pending_withdrawals = [0; 8];
+        self.returned_overflow = [0; 8];
+        self.briber_returned_overflow = [0; 8];
+        self.receipt_yes_mint_bump = 0;
+        self.receipt_no_mint_bump = 0;
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn set_id(&mut self, id: &[u8], seed: &MarketSeed) {
+        self.id[..id.len()].copy_from_slice(id);
+        self.id_len = id.len() as u8;
+        self.seed = seed.bytes;
+        self.seed_len = seed.len;
+    }
+
+    #[inline(always)]
+    pub fn set_vault_bumps(
+        &mut self,
+        incentive_vault_bump: u8,
+        yes_vault_bump: u8,
+        no_vault_bump: u8,
+    ) {
+        self.incentive_vault_bump = incentive_vault_bump;
+        self.yes_vault_bump = yes_vault_bump;
+        self.no_vault_bump = no_vault_bump;
+    }
+
+    #[inline(always)]
+    pub fn set_stake_limits(
+        &mut self,
+        min_stake: u64,
+        max_stake_per_farmer: u64,
+        max_total_stake: u64,
+    ) {
+        self.min_stake = min_stake.to_le_bytes();
+        self.max_stake_per_farmer = max_stake_per_farmer.to_le_bytes();
+        self.max_total_stake = max_total_stake.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn min_stake(&self) -> u64 {
+        u64::from_le_bytes(self.min_stake)
+    }
+
+    #[inline(always)]
+    pub fn max_stake_per_farmer(&self) -> u64 {
+        u64::from_le_bytes(self.max_stake_per_farmer)
+    }
+
+    #[inline(always)]
+    pub fn max_total_stake(&self) -> u64 {
+        u64::from_le_bytes(self.max_total_stake)
+    }
+
+    #[inline(always)]
+    pub fn check_min_stake(&self, amount: u64) -> ProgramResult {
+        if amount < self.min_stake() {
+            return Err(ReflexError::StakeBelowMinimum.into());
+        }
+        Ok(())
+    }
+
+    /// Checks a farmer's stake on one side, new stake included, against the
+    /// per-farmer cap.
+    #[inline(always)]
+    pub fn check_farmer_stake(&self, farmer_staked: u64) -> ProgramResult {
+        let max_stake_per_farmer = self.max_stake_per_farmer();
+        if max_stake_per_farmer > 0 && farmer_staked > max_stake_per_farmer {
+            return Err(ReflexError::FarmerStakeCapExceeded.into());
+        }
+        Ok(())
+    }
 
+    /// Checks a side's total stake, new stake included, against the cap.
+    #[inline(always)]
+    pub fn check_total_stake(&self, total_staked: u64) -> ProgramResult {
+        let max_total_stake = self.max_total_stake();
+        if max_total_stake > 0 && total_staked > max_total_stake {
+            return Err(ReflexError::MarketStakeCapExceeded.into());
+        }
         Ok(())
     }
 
+    #[inline(always)]
+    pub fn id(&self) -> &[u8] {
+        &self.id[..self.id_len as usize]
+    }
+
+    #[inline(always)]
+    pub fn seed(&self) -> MarketSeed {
+        MarketSeed {
+            bytes: self.seed,
+            len: self.seed_len,
+        }
+    }
+
     #[inline(always)]
     pub fn briber(&self) -> Address {
         Address::new_from_array(self.briber)
     }
 
+    #[inline(always)]
+    pub fn pending_briber(&self) -> Address {
+        Address::new_from_array(self.pending_briber)
+    }
+
+    #[inline(always)]
+    pub fn fee_recipient(&self) -> Address {
+        Address::new_from_array(self.fee_recipient)
+    }
+
+    /// Starts a two-step ownership transfer. The zero address cancels it.
+    #[inline(always)]
+    pub fn set_pending_briber(&mut self, pending_briber: &Address) {
+        self.pending_briber = pending_briber.to_bytes();
+    }
+
+    /// Completes an ownership transfer. Fees go to the new briber until they
+    /// pick another recipient.
+    #[inline(always)]
+    pub fn accept_ownership(&mut self) {
+        self.briber = self.pending_briber;
+        self.fee_recipient = self.pending_briber;
+        self.pending_briber = [0; 32];
+    }
+
+    #[inline(always)]
+    pub fn set_fee_recipient(&mut self, fee_recipient: &Address) {
+        self.fee_recipient = fee_recipient.to_bytes();
+    }
+
     #[inline(always)]
     pub fn incentive_mint(&self) -> Address {
         Address::new_from_array(self.incentive_mint)
@@ -123,11 +464,6 @@ impl Market {
         matches!(self.status, MarketStatus::Open)
     }
 
-    #[inline(always)]
-    pub fn is_settled(&self) -> bool {
-        matches!(self.status, MarketStatus::Settled)
-    }
-
     #[inline(always)]
     pub fn is_resolved_yes(&self) -> bool {
         matches!(self.resolution, MarketResolution::Yes)
@@ -138,25 +474,206 @@ impl Market {
         matches!(self.resolution, MarketResolution::No)
     }
 
+    #[inline(always)]
+    pub fn creation_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.creation_timestamp)
+    }
+
     #[inline(always)]
     pub fn fee_bps(&self) -> u16 {
         u16::from_le_bytes(self.fee_bps)
     }
 
     #[inline(always)]
-    pub fn calculate_reward(
-        &self,
-        total_staked: u64,
-        staked_amount: u64,
-    ) -> Result<u64, ProgramError> {
-        if total_staked == 0 || staked_amount == 0 {
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+
+    /// Protocol fee charged on incentives, or `None` for a migrated market whose
+    /// fee was not snapshotted yet.
+    #[inline(always)]
+    pub fn protocol_fee_bps(&self) -> Option<u16> {
+        let protocol_fee_bps = u16::from_le_bytes(self.protocol_fee_bps);
+        (protocol_fee_bps != Self::UNSET_PROTOCOL_FEE_BPS).then_some(protocol_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn set_protocol_fee_bps(&mut self, protocol_fee_bps: u16) {
+        self.protocol_fee_bps = protocol_fee_bps.to_le_bytes();
+    }
+
+    /// Incentives owed to `weight` out of the winning side's `total_weight`,
+    /// both boosted by lockup multipliers.
+    #[inline(always)]
+    pub fn calculate_reward(&self, total_weight: u64, weight: u64) -> Result<u64, ProgramError> {
+        if total_weight == 0 || weight == 0 {
             return Ok(0);
         }
-        Ok((staked_amount as u128)
+        (weight as u128)
             .checked_mul(self.total_incentive_amount() as u128)
-            .and_then(|v| v.checked_div(total_staked as u128))
+            .and_then(|v| v.checked_div(total_weight as u128))
             .and_then(|v| v.try_into().ok())
-            .ok_or(ProgramError::ArithmeticOverflow)?)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    #[inline(always)]
+    pub fn max_reward_bps_per_farmer(&self) -> u16 {
+        u16::from_le_bytes(self.max_reward_bps_per_farmer)
+    }
+
+    #[inline(always)]
+    pub fn set_max_reward_bps_per_farmer(&mut self, max_reward_bps_per_farmer: u16) {
+        self.max_reward_bps_per_farmer = max_reward_bps_per_farmer.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn reward_overflow(&self) -> u64 {
+        u64::from_le_bytes(self.reward_overflow)
+    }
+
+    #[inline(always)]
+    pub fn claimed_weight(&self) -> u64 {
+        u64::from_le_bytes(self.claimed_weight)
+    }
+
+    #[inline(always)]
+    pub fn returned_overflow(&self) -> u64 {
+        u64::from_le_bytes(self.returned_overflow)
+    }
+
+    #[inline(always)]
+    pub fn briber_returned_overflow(&self) -> u64 {
+        u64::from_le_bytes(self.briber_returned_overflow)
+    }
+
+    #[inline(always)]
+    fn winning_weight(&self) -> Result<u64, ProgramError> {
+        if self.is_resolved_yes() {
+            Ok(self.total_yes_weight())
+        } else if self.is_resolved_no() {
+            Ok(self.total_no_weight())
+        } else {
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+
+    /// Whether the overflow is no longer owed to winners at `now`: every
+    /// winning position claimed, or `REWARD_OVERFLOW_CLAIM_PERIOD` passed
+    /// since claims opened.
+    #[inline(always)]
+    pub fn is_overflow_returnable(&self, now: i64) -> Result<bool, ProgramError> {
+        let claims_open_at = i64::from_le_bytes(self.settled_at)
+            .saturating_add(i64::from_le_bytes(self.dispute_window));
+        Ok(self.claimed_weight() >= self.winning_weight()?
+            || now >= claims_open_at.saturating_add(constants::REWARD_OVERFLOW_CLAIM_PERIOD))
+    }
+
+    /// Whether no more overflow can be handed back to the funders: the market
+    /// is uncapped, or every winning position claimed and the overflow left
+    /// was returned.
+    #[inline(always)]
+    pub fn is_overflow_settled(&self) -> Result<bool, ProgramError> {
+        if self.max_reward_bps_per_farmer() == 0 {
+            return Ok(true);
+        }
+
+        Ok(self.reward_overflow() == 0 && self.claimed_weight() >= self.winning_weight()?)
+    }
+
+    /// Pays out `weight`'s reward on a market with a per-farmer cap. The
+    /// claimer takes its share of the overflow left by earlier claimers, pro
+    /// rata to the winning weight that has not claimed yet, and anything over
+    /// the cap goes back to the overflow for those claiming after it. Once the
+    /// overflow is returnable, late claimers no longer share it.
+    #[inline(always)]
+    pub fn claim_capped_reward(
+        &mut self,
+        total_weight: u64,
+        weight: u64,
+        now: i64,
+    ) -> Result<u64, ProgramError> {
+        let unclaimed_weight = total_weight
+            .checked_sub(self.claimed_weight())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let overflow_share = if unclaimed_weight == 0 || self.is_overflow_returnable(now)? {
+            0
+        } else {
+            math::pro_rata(self.reward_overflow(), weight, unclaimed_weight)?
+        };
+
+        let reward = self
+            .calculate_reward(total_weight, weight)?
+            .checked_add(overflow_share)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let cap = math::fee_calculation(
+            self.total_incentive_amount(),
+            self.max_reward_bps_per_farmer(),
+        )?;
+        let paid = reward.min(cap);
+
+        let excess = reward
+            .checked_sub(paid)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.reward_overflow = self
+            .reward_overflow()
+            .checked_sub(overflow_share)
+            .and_then(|v| v.checked_add(excess))
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        self.claimed_weight = self
+            .claimed_weight()
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        Ok(paid)
+    }
+
+    /// Hands the overflow back to the funders once it is returnable at `now`,
+    /// i.e. what no farmer could or did take under the cap.
+    #[inline(always)]
+    pub fn return_reward_overflow(&mut self, now: i64) -> ProgramResult {
+        if !self.is_overflow_returnable(now)? {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        self.returned_overflow = self
+            .returned_overflow()
+            .checked_add(self.reward_overflow())
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        self.reward_overflow = [0; 8];
+        Ok(())
+    }
+
+    /// Share of the returned overflow owed to a co-funder that added `amount`
+    /// of the incentives.
+    #[inline(always)]
+    pub fn co_funder_overflow_share(&self, amount: u64) -> Result<u64, ProgramError> {
+        math::pro_rata(
+            self.returned_overflow(),
+            amount,
+            self.total_incentive_amount(),
+        )
+    }
+
+    /// Returned overflow the briber has not reclaimed yet. Its share absorbs
+    /// the rounding of every co-funder share.
+    #[inline(always)]
+    pub fn take_briber_overflow(&mut self) -> Result<u64, ProgramError> {
+        let co_funded = self
+            .total_incentive_amount()
+            .checked_sub(self.briber_contribution())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let share = self
+            .returned_overflow()
+            .checked_sub(self.co_funder_overflow_share(co_funded)?)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let amount = share
+            .checked_sub(self.briber_returned_overflow())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.briber_returned_overflow = share.to_le_bytes();
+        Ok(amount)
     }
 
     #[inline(always)]
@@ -169,54 +686,419 @@ impl Market {
     }
 
     #[inline(always)]
-    pub fn clean_available_fees(&mut self) {
-        self.available_yes_fees = 0u64.to_le_bytes();
-        self.available_no_fees = 0u64.to_le_bytes();
+    pub fn briber_contribution(&self) -> u64 {
+        u64::from_le_bytes(self.briber_contribution)
+    }
+
+    #[inline(always)]
+    pub fn add_briber_contribution(&mut self, amount: u64) -> ProgramResult {
+        let new_amount = self
+            .briber_contribution()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.briber_contribution = new_amount.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn contributor_count(&self) -> u8 {
+        self.contributor_count
+    }
+
+    #[inline(always)]
+    pub fn add_contributor(&mut self) -> ProgramResult {
+        if self.contributor_count as usize >= constants::MAX_CONTRIBUTORS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.contributor_count += 1;
+        Ok(())
+    }
+
+    /// Staking fees collected on the resolved side, including those already claimed.
+    #[inline(always)]
+    pub fn total_fees(&self) -> Result<u64, ProgramError> {
+        let available_fees = if self.is_resolved_yes() {
+            self.available_yes_fees()
+        } else if self.is_resolved_no() {
+            self.available_no_fees()
+        } else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        available_fees
+            .checked_add(u64::from_le_bytes(self.claimed_fees))
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Pays `amount` out of the resolved side's available fees.
+    #[inline(always)]
+    pub fn claim_fees(&mut self, amount: u64) -> ProgramResult {
+        let available_fees = if self.is_resolved_yes() {
+            &mut self.available_yes_fees
+        } else if self.is_resolved_no() {
+            &mut self.available_no_fees
+        } else {
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        let new_available_fees = u64::from_le_bytes(*available_fees)
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *available_fees = new_available_fees.to_le_bytes();
+
+        let new_claimed_fees = u64::from_le_bytes(self.claimed_fees)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.claimed_fees = new_claimed_fees.to_le_bytes();
+        Ok(())
     }
 
+    /// Sets the resolution at `now`. Claims open once `dispute_window` seconds
+    /// have passed, until then the authority may still correct it.
     #[inline(always)]
-    pub fn set_resolution(&mut self, resolution: u8) {
+    pub fn set_resolution(&mut self, resolution: u8, now: i64, dispute_window: i64) {
         self.resolution = match resolution {
             1 => MarketResolution::Yes,
             2 => MarketResolution::No,
             _ => return, // invalid resolution, do nothing (validated in instruction data parsing)
         };
+        self.settled_at = now.to_le_bytes();
+        self.dispute_window = dispute_window.to_le_bytes();
+        self.status = if dispute_window == 0 {
+            MarketStatus::Settled
+        } else {
+            MarketStatus::PendingSettlement
+        };
+    }
+
+    /// Whether the resolution can no longer be corrected at `now`.
+    #[inline(always)]
+    pub fn is_final(&self, now: i64) -> bool {
+        match self.status {
+            MarketStatus::Settled => true,
+            MarketStatus::PendingSettlement => {
+                now >= i64::from_le_bytes(self.settled_at)
+                    .saturating_add(i64::from_le_bytes(self.dispute_window))
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves a pending settlement to Settled once its dispute window is over.
+    #[inline(always)]
+    pub fn finalize(&mut self, now: i64) -> ProgramResult {
+        if !self.is_final(now) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
         self.status = MarketStatus::Settled;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn pending_withdrawals(&self) -> u64 {
+        u64::from_le_bytes(self.pending_withdrawals)
+    }
+
+    /// Saturates, so an unknown total stays unknown.
+    #[inline(always)]
+    pub fn add_pending_withdrawals(&mut self, amount: u64) {
+        self.pending_withdrawals = self
+            .pending_withdrawals()
+            .saturating_add(amount)
+            .to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn sub_pending_withdrawals(&mut self, amount: u64) {
+        if self.pending_withdrawals() != Self::UNKNOWN_PENDING_WITHDRAWALS {
+            self.pending_withdrawals = self
+                .pending_withdrawals()
+                .saturating_sub(amount)
+                .to_le_bytes();
+        }
+    }
+
+    /// Whether no farmer has tokens left in the outcome vaults, staked or
+    /// queued for withdrawal. Penalties left without stakers go to the fee
+    /// recipient.
+    #[inline(always)]
+    pub fn has_no_stakes(&self) -> bool {
+        self.total_yes_staked() == 0
+            && self.total_no_staked() == 0
+            && self.pending_withdrawals() == 0
+    }
+
+    #[inline(always)]
+    pub fn unstake_cooldown(&self) -> i64 {
+        i64::from_le_bytes(self.unstake_cooldown)
+    }
+
+    #[inline(always)]
+    pub fn early_exit_penalty_bps(&self) -> u16 {
+        u16::from_le_bytes(self.early_exit_penalty_bps)
+    }
+
+    #[inline(always)]
+    pub fn set_unstake_rules(&mut self, unstake_cooldown: i64, early_exit_penalty_bps: u16) {
+        self.unstake_cooldown = unstake_cooldown.to_le_bytes();
+        self.early_exit_penalty_bps = early_exit_penalty_bps.to_le_bytes();
+    }
+
+    /// Penalty on unstaking `amount` at `now`. It starts at
+    /// `early_exit_penalty_bps` and decays linearly to zero over the cooldown
+    /// following the last stake.
+    #[inline(always)]
+    pub fn early_exit_penalty(
+        &self,
+        amount: u64,
+        last_stake_timestamp: i64,
+        now: i64,
+    ) -> Result<u64, ProgramError> {
+        let cooldown = self.unstake_cooldown();
+        if cooldown <= 0 {
+            return Ok(0);
+        }
+
+        let remaining = last_stake_timestamp
+            .saturating_add(cooldown)
+            .saturating_sub(now)
+            .clamp(0, cooldown);
+        math::pro_rata(
+            math::fee_calculation(amount, self.early_exit_penalty_bps())?,
+            remaining as u64,
+            cooldown as u64,
+        )
     }
 
     #[inline(always)]
-    pub fn add_yes_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn yes_penalties(&self) -> u64 {
+        u64::from_le_bytes(self.yes_penalties)
+    }
+
+    #[inline(always)]
+    pub fn no_penalties(&self) -> u64 {
+        u64::from_le_bytes(self.no_penalties)
+    }
+
+    #[inline(always)]
+    pub fn add_yes_penalties(&mut self, amount: u64) -> ProgramResult {
+        let new_amount = self
+            .yes_penalties()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.yes_penalties = new_amount.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn add_no_penalties(&mut self, amount: u64) -> ProgramResult {
+        let new_amount = self
+            .no_penalties()
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.no_penalties = new_amount.to_le_bytes();
+        Ok(())
+    }
+
+    /// Winning stakers share their side's early-exit penalties pro rata. The
+    /// claimed stake and its share leave the side, so the last claimer takes
+    /// the rounding dust and the side's penalties end at zero.
+    #[inline(always)]
+    pub fn claim_penalty_share(
+        &mut self,
+        is_yes: bool,
+        staked_amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let (total_staked, penalties) = if is_yes {
+            (self.total_yes_staked(), self.yes_penalties())
+        } else {
+            (self.total_no_staked(), self.no_penalties())
+        };
+        if total_staked == 0 || staked_amount == 0 {
+            return Ok(0);
+        }
+
+        let share = math::pro_rata(penalties, staked_amount, total_staked)?;
+        let new_total_staked = total_staked
+            .checked_sub(staked_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_penalties = penalties
+            .checked_sub(share)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if is_yes {
+            self.total_yes_staked = new_total_staked.to_le_bytes();
+            self.yes_penalties = new_penalties.to_le_bytes();
+        } else {
+            self.total_no_staked = new_total_staked.to_le_bytes();
+            self.no_penalties = new_penalties.to_le_bytes();
+        }
+        Ok(share)
+    }
+
+    /// Penalties of a side that no staker can claim anymore: those of the
+    /// losing side once the market is resolved, or of a side without stakes.
+    /// Returns them and empties the side's penalties.
+    #[inline(always)]
+    pub fn take_unclaimable_penalties(&mut self, is_yes: bool) -> u64 {
+        let (is_losing_side, total_staked) = if is_yes {
+            (self.is_resolved_no(), self.total_yes_staked())
+        } else {
+            (self.is_resolved_yes(), self.total_no_staked())
+        };
+        if !is_losing_side && total_staked > 0 {
+            return 0;
+        }
+
+        if is_yes {
+            let penalties = self.yes_penalties();
+            self.yes_penalties = [0; 8];
+            penalties
+        } else {
+            let penalties = self.no_penalties();
+            self.no_penalties = [0; 8];
+            penalties
+        }
+    }
+
+    /// Replaces the lockup tiers. `tiers` holds packed `i64 duration, u16
+    /// multiplier_bps` entries, already validated by the caller.
+    #[inline(always)]
+    pub fn set_lockup_tiers(&mut self, tiers: &[u8]) {
+        self.lockup_durations = [[0; 8]; constants::MAX_LOCKUP_TIERS];
+        self.lockup_multipliers_bps = [[0; 2]; constants::MAX_LOCKUP_TIERS];
+
+        let entries = tiers.chunks_exact(constants::LOCKUP_TIER_LEN);
+        self.lockup_tier_count = entries.len() as u8;
+        for (i, entry) in entries.enumerate() {
+            self.lockup_durations[i] = entry[..8].try_into().unwrap();
+            self.lockup_multipliers_bps[i] = entry[8..].try_into().unwrap();
+        }
+    }
+
+    /// Lock duration and multiplier of tier `index`, if the market offers it.
+    #[inline(always)]
+    pub fn lockup_tier(&self, index: u8) -> Option<(i64, u16)> {
+        let index = index as usize;
+        (index < self.lockup_tier_count as usize).then(|| {
+            (
+                i64::from_le_bytes(self.lockup_durations[index]),
+                u16::from_le_bytes(self.lockup_multipliers_bps[index]),
+            )
+        })
+    }
+
+    #[inline(always)]
+    pub fn imbalance_boost_bps(&self) -> u16 {
+        u16::from_le_bytes(self.imbalance_boost_bps)
+    }
+
+    #[inline(always)]
+    pub fn set_imbalance_boost_bps(&mut self, imbalance_boost_bps: u16) {
+        self.imbalance_boost_bps = imbalance_boost_bps.to_le_bytes();
+    }
+
+    /// Whether leaving the market costs a staking fee or an unstake cooldown.
+    /// Without either, a boosted stake could be unstaked and staked again on
+    /// the thinner side for free.
+    #[inline(always)]
+    pub fn has_exit_cost(&self) -> bool {
+        self.fee_bps() > 0 || self.unstake_cooldown() > 0
+    }
+
+    /// Multiplier for staking `amount` on one side, 10_000 being 1x. It grows
+    /// linearly with how far that side trails the other, up to 1x plus
+    /// `imbalance_boost_bps` when the side is empty. The gap is measured
+    /// halfway through the stake, so splitting a stake gains nothing.
+    #[inline(always)]
+    pub fn imbalance_multiplier_bps(&self, is_yes: bool, amount: u64) -> Result<u64, ProgramError> {
+        let (side_staked, other_staked) = if is_yes {
+            (self.total_yes_staked(), self.total_no_staked())
+        } else {
+            (self.total_no_staked(), self.total_yes_staked())
+        };
+
+        let side_staked = side_staked.saturating_add(amount / 2);
+        if side_staked >= other_staked {
+            return Ok(10_000);
+        }
+
+        let extra_bps = math::pro_rata(
+            self.imbalance_boost_bps() as u64,
+            other_staked - side_staked,
+            other_staked,
+        )?;
+        Ok(10_000 + extra_bps)
+    }
+
+    #[inline(always)]
+    pub fn total_yes_weight(&self) -> u64 {
+        u64::from_le_bytes(self.total_yes_weight)
+    }
+
+    #[inline(always)]
+    pub fn total_no_weight(&self) -> u64 {
+        u64::from_le_bytes(self.total_no_weight)
+    }
+
+    #[inline(always)]
+    pub fn set_disputed(&mut self) {
+        self.status = MarketStatus::Disputed;
+    }
+
+    #[inline(always)]
+    pub fn add_yes_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = u64::from_le_bytes(self.total_yes_staked)
             .checked_add(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .total_yes_weight()
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.total_yes_staked = new_amount.to_le_bytes();
+        self.total_yes_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
     #[inline(always)]
-    pub fn add_no_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn add_no_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = u64::from_le_bytes(self.total_no_staked)
             .checked_add(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .total_no_weight()
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.total_no_staked = new_amount.to_le_bytes();
+        self.total_no_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
     #[inline(always)]
-    pub fn sub_yes_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn sub_yes_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = u64::from_le_bytes(self.total_yes_staked)
             .checked_sub(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .total_yes_weight()
+            .checked_sub(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.total_yes_staked = new_amount.to_le_bytes();
+        self.total_yes_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
     #[inline(always)]
-    pub fn sub_no_staked(&mut self, amount: u64) -> ProgramResult {
+    pub fn sub_no_staked(&mut self, amount: u64, weight: u64) -> ProgramResult {
         let new_amount = u64::from_le_bytes(self.total_no_staked)
             .checked_sub(amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        let new_weight = self
+            .total_no_weight()
+            .checked_sub(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         self.total_no_staked = new_amount.to_le_bytes();
+        self.total_no_weight = new_weight.to_le_bytes();
         Ok(())
     }
 
@@ -244,6 +1126,8 @@ pub enum MarketStatus {
     // Unopen = 0,
     Open = 0,
     Settled = 1,
+    Disputed = 2,          // conflicting committee votes, settled by the authority
+    PendingSettlement = 3, // resolved, claims open after the dispute window
 }
 
 #[repr(u8)]
@@ -252,3 +1136,38 @@ pub enum MarketResolution {
     Yes = 1,
     No = 2,
 }
+
+/// Seed that derives the market PDA: `sha256(id)`, or the raw id for markets
+/// created before ids were hashed.
+#[derive(Clone, Copy)]
+pub struct MarketSeed {
+    bytes: [u8; 32],
+    len: u8,
+}
+
+impl MarketSeed {
+    #[inline(always)]
+    pub fn from_id(id: &[u8]) -> Self {
+        Self {
+            bytes: solana_sha256_hasher::hash(id).to_bytes(),
+            len: 32,
+        }
+    }
+
+    #[inline(always)]
+    fn from_legacy_id(id: &[u8]) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..id.len()].copy_from_slice(id);
+        Self {
+            bytes,
+            len: id.len() as u8,
+        }
+    }
+}
+
+impl AsRef<[u8]> for MarketSeed {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
@@ -0,0 +1,93 @@
+use pinocchio::{Address, error::ProgramError};
+
+use crate::{
+    states::{AccountHeader, MARKET_METADATA_DISCRIMINATOR, Versioned},
+    utils::constants,
+};
+
+#[repr(C)]
+pub struct MarketMetadata {
+    header: AccountHeader,
+    market: [u8; 32], // Address
+    ticker: [u8; constants::MAX_ID_LENGTH],
+    ticker_len: u8,
+    title: [u8; constants::MAX_TITLE_LENGTH],
+    title_len: u8,
+    category: [u8; constants::MAX_CATEGORY_LENGTH],
+    category_len: u8,
+    expected_resolution_timestamp: [u8; 8], // i64
+    uri: [u8; constants::MAX_URI_LENGTH],
+    uri_len: u8,
+    pub bump: u8,
+}
+
+impl Versioned for MarketMetadata {
+    const DISCRIMINATOR: u8 = MARKET_METADATA_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            // market, ticker, title, category, expected resolution, uri, bump
+            1 => Some(
+                AccountHeader::LEN
+                    + 32
+                    + constants::MAX_ID_LENGTH
+                    + 1
+                    + constants::MAX_TITLE_LENGTH
+                    + 1
+                    + constants::MAX_CATEGORY_LENGTH
+                    + 1
+                    + 8
+                    + constants::MAX_URI_LENGTH
+                    + 1
+                    + 1,
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl MarketMetadata {
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, market: &Address, ticker: &[u8], bump: u8) {
+        self.market = market.to_bytes();
+        self.ticker[..ticker.len()].copy_from_slice(ticker);
+        self.ticker_len = ticker.len() as u8;
+        self.bump = bump;
+    }
+
+    /// Overwrites the briber-editable fields. Lengths are validated when the
+    /// instruction data is parsed.
+    #[inline(always)]
+    pub fn update(
+        &mut self,
+        title: &[u8],
+        category: &[u8],
+        expected_resolution_timestamp: i64,
+        uri: &[u8],
+    ) {
+        self.title = [0; constants::MAX_TITLE_LENGTH];
+        self.title[..title.len()].copy_from_slice(title);
+        self.title_len = title.len() as u8;
+
+        self.category = [0; constants::MAX_CATEGORY_LENGTH];
+        self.category[..category.len()].copy_from_slice(category);
+        self.category_len = category.len() as u8;
+
+        self.expected_resolution_timestamp = expected_resolution_timestamp.to_le_bytes();
+
+        self.uri = [0; constants::MAX_URI_LENGTH];
+        self.uri[..uri.len()].copy_from_slice(uri);
+        self.uri_len = uri.len() as u8;
+    }
+}
@@ -1,5 +1,17 @@
+mod briber_allowlist;
+mod briber_contribution;
 mod config;
 mod farmer_position;
+mod header;
 mod market;
+mod market_metadata;
+mod outcome_mints;
+mod revenue_split;
+mod settlement_committee;
+mod settlement_votes;
 
-pub use {config::*, farmer_position::*, market::*};
+pub use {
+    briber_allowlist::*, briber_contribution::*, config::*, farmer_position::*, header::*,
+    market::*, market_metadata::*, outcome_mints::*, revenue_split::*, settlement_committee::*,
+    settlement_votes::*,
+};
@@ -0,0 +1,62 @@
+use pinocchio::{Address, error::ProgramError};
+
+use crate::states::{AccountHeader, OUTCOME_MINTS_DISCRIMINATOR, Versioned};
+
+/// Outcome mints the authority registered for a ticker. Markets created
+/// without the authority must trade these mints.
+#[repr(C)]
+pub struct OutcomeMints {
+    header: AccountHeader,
+    outcome_yes_mint: [u8; 32], // Address
+    outcome_no_mint: [u8; 32],  // Address
+    pub bump: u8,
+}
+
+impl Versioned for OutcomeMints {
+    const DISCRIMINATOR: u8 = OUTCOME_MINTS_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            1 => Some(AccountHeader::LEN + 32 + 32 + 1), // yes mint, no mint, bump
+            _ => None,
+        }
+    }
+}
+
+impl OutcomeMints {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, outcome_yes_mint: &Address, outcome_no_mint: &Address, bump: u8) {
+        self.outcome_yes_mint = outcome_yes_mint.to_bytes();
+        self.outcome_no_mint = outcome_no_mint.to_bytes();
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn outcome_yes_mint(&self) -> Address {
+        Address::new_from_array(self.outcome_yes_mint)
+    }
+
+    #[inline(always)]
+    pub fn outcome_no_mint(&self) -> Address {
+        Address::new_from_array(self.outcome_no_mint)
+    }
+}
@@ -0,0 +1,80 @@
+use pinocchio::{Address, error::ProgramError};
+
+use crate::{
+    states::{AccountHeader, REVENUE_SPLIT_DISCRIMINATOR, Versioned},
+    utils::constants,
+};
+
+/// Recipients of the protocol treasuries, paid by `DistributeTreasury`.
+#[repr(C)]
+pub struct RevenueSplit {
+    header: AccountHeader,
+    recipient_count: u8,
+    recipients: [[u8; 32]; constants::MAX_REVENUE_RECIPIENTS], // Address
+    weights_bps: [[u8; 2]; constants::MAX_REVENUE_RECIPIENTS], // u16, sum to 10_000
+    pub bump: u8,
+}
+
+impl Versioned for RevenueSplit {
+    const DISCRIMINATOR: u8 = REVENUE_SPLIT_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            // recipient count, recipients and weights, bump
+            1 => Some(AccountHeader::LEN + 1 + constants::MAX_REVENUE_RECIPIENTS * (32 + 2) + 1),
+            _ => None,
+        }
+    }
+}
+
+impl RevenueSplit {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Replaces every recipient. `recipients` holds packed `[u8; 32] address,
+    /// u16 weight_bps` entries, already validated by the caller.
+    #[inline(always)]
+    pub fn set_inner(&mut self, recipients: &[u8], bump: u8) {
+        self.recipients = [[0; 32]; constants::MAX_REVENUE_RECIPIENTS];
+        self.weights_bps = [[0; 2]; constants::MAX_REVENUE_RECIPIENTS];
+
+        let entries = recipients.chunks_exact(constants::REVENUE_RECIPIENT_LEN);
+        self.recipient_count = entries.len() as u8;
+        for (i, entry) in entries.enumerate() {
+            self.recipients[i] = entry[..32].try_into().unwrap();
+            self.weights_bps[i] = entry[32..].try_into().unwrap();
+        }
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn recipient_count(&self) -> usize {
+        self.recipient_count as usize
+    }
+
+    #[inline(always)]
+    pub fn recipient(&self, index: usize) -> Address {
+        Address::new_from_array(self.recipients[index])
+    }
+
+    #[inline(always)]
+    pub fn weight_bps(&self, index: usize) -> u16 {
+        u16::from_le_bytes(self.weights_bps[index])
+    }
+}
@@ -0,0 +1,76 @@
+use pinocchio::{Address, error::ProgramError};
+
+use crate::{
+    states::{AccountHeader, SETTLEMENT_COMMITTEE_DISCRIMINATOR, Versioned},
+    utils::constants,
+};
+
+/// Oracles allowed to vote on market resolutions with `VoteSettlement`.
+#[repr(C)]
+pub struct SettlementCommittee {
+    header: AccountHeader,
+    threshold: u8, // matching votes needed to settle a market
+    member_count: u8,
+    members: [[u8; 32]; constants::MAX_COMMITTEE_MEMBERS], // Address
+    pub bump: u8,
+}
+
+impl Versioned for SettlementCommittee {
+    const DISCRIMINATOR: u8 = SETTLEMENT_COMMITTEE_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            // threshold, member count, members, bump
+            1 => Some(AccountHeader::LEN + 2 + constants::MAX_COMMITTEE_MEMBERS * 32 + 1),
+            _ => None,
+        }
+    }
+}
+
+impl SettlementCommittee {
+    #[inline(always)]
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Replaces every member. `members` holds packed addresses, already
+    /// validated by the caller.
+    #[inline(always)]
+    pub fn set_inner(&mut self, threshold: u8, members: &[u8], bump: u8) {
+        self.members = [[0; 32]; constants::MAX_COMMITTEE_MEMBERS];
+
+        let members = members.chunks_exact(32);
+        self.member_count = members.len() as u8;
+        for (i, member) in members.enumerate() {
+            self.members[i] = member.try_into().unwrap();
+        }
+        self.threshold = threshold;
+        self.bump = bump;
+    }
+
+    #[inline(always)]
+    pub fn threshold(&self) -> usize {
+        self.threshold as usize
+    }
+
+    #[inline(always)]
+    pub fn is_member(&self, address: &Address) -> bool {
+        self.members[..self.member_count as usize]
+            .iter()
+            .any(|member| member == address.as_array())
+    }
+}
@@ -0,0 +1,98 @@
+use pinocchio::{Address, ProgramResult, error::ProgramError};
+
+use crate::{
+    states::{AccountHeader, SETTLEMENT_VOTES_DISCRIMINATOR, Versioned},
+    utils::constants,
+};
+
+/// Committee votes cast on a single market, one per oracle.
+#[repr(C)]
+pub struct SettlementVotes {
+    header: AccountHeader,
+    market: [u8; 32], // Address
+    vote_count: u8,
+    voters: [[u8; 32]; constants::MAX_COMMITTEE_MEMBERS], // Address
+    resolutions: [u8; constants::MAX_COMMITTEE_MEMBERS],  // 1 = Yes, 2 = No
+    pub bump: u8,
+}
+
+impl Versioned for SettlementVotes {
+    const DISCRIMINATOR: u8 = SETTLEMENT_VOTES_DISCRIMINATOR;
+    const VERSION: u8 = 1;
+    const LEGACY_LEN: usize = 0; // introduced with the account header
+
+    #[inline(always)]
+    fn len_for_version(version: u8) -> Option<usize> {
+        match version {
+            // market, vote count, voters and resolutions, bump
+            1 => {
+                Some(AccountHeader::LEN + 32 + 1 + constants::MAX_COMMITTEE_MEMBERS * (32 + 1) + 1)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl SettlementVotes {
+    #[inline(always)]
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::check_header(data)?;
+
+        // SAFETY: everything is u8 aligned and length checked
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, market: &Address, bump: u8) {
+        self.market = market.to_bytes();
+        self.vote_count = 0;
+        self.voters = [[0; 32]; constants::MAX_COMMITTEE_MEMBERS];
+        self.resolutions = [0; constants::MAX_COMMITTEE_MEMBERS];
+        self.bump = bump;
+    }
+
+    /// Votes cast so far, as (voter, resolution) pairs.
+    #[inline(always)]
+    pub fn votes(&self) -> impl Iterator<Item = (&[u8; 32], u8)> {
+        let vote_count = self.vote_count as usize;
+        self.voters[..vote_count]
+            .iter()
+            .zip(self.resolutions[..vote_count].iter().copied())
+    }
+
+    /// Drops the votes of voters for which `is_member` is false, e.g. oracles
+    /// removed from the committee, freeing their slots.
+    #[inline(always)]
+    pub fn retain_votes(&mut self, is_member: impl Fn(&Address) -> bool) {
+        let mut kept = 0;
+        for index in 0..self.vote_count as usize {
+            if is_member(&Address::new_from_array(self.voters[index])) {
+                self.voters[kept] = self.voters[index];
+                self.resolutions[kept] = self.resolutions[index];
+                kept += 1;
+            }
+        }
+
+        self.voters[kept..].fill([0; 32]);
+        self.resolutions[kept..].fill(0);
+        self.vote_count = kept as u8;
+    }
+
+    /// Records the vote of `voter`, who must not have voted yet.
+    #[inline(always)]
+    pub fn add_vote(&mut self, voter: &Address, resolution: u8) -> ProgramResult {
+        if self.votes().any(|(v, _)| v == voter.as_array()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let index = self.vote_count as usize;
+        if index == constants::MAX_COMMITTEE_MEMBERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        self.voters[index] = voter.to_bytes();
+        self.resolutions[index] = resolution;
+        self.vote_count += 1;
+        Ok(())
+    }
+}
@@ -5,12 +5,47 @@ pub const WSOL_ADDRESS: &Address =
     &Address::from_str_const("So11111111111111111111111111111111111111112");
 pub const USDC_ADDRESS: &Address =
     &Address::from_str_const("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+pub const ED25519_PROGRAM_ADDRESS: &Address =
+    &Address::from_str_const("Ed25519SigVerify111111111111111111111111111");
 
 // seeds
 pub const CONFIG_SEED: &[u8] = b"config";
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const MARKET_SEED: &[u8] = b"market";
 pub const FARMER_POSITION_SEED: &[u8] = b"farmer_position";
+pub const MARKET_METADATA_SEED: &[u8] = b"market_metadata";
+pub const BRIBER_ALLOWLIST_SEED: &[u8] = b"briber_allowlist";
+pub const BRIBER_CONTRIBUTION_SEED: &[u8] = b"briber_contribution";
+pub const REVENUE_SPLIT_SEED: &[u8] = b"revenue_split";
+pub const SETTLEMENT_COMMITTEE_SEED: &[u8] = b"settlement_committee";
+pub const SETTLEMENT_VOTES_SEED: &[u8] = b"settlement_votes";
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+pub const OUTCOME_MINTS_SEED: &[u8] = b"outcome_mints";
+
+// signed message domains
+pub const ATTESTATION_DOMAIN: &[u8] = b"reflex:settle_market:v1";
 
 // other constants
 pub const MIN_ID_LENGTH: usize = 10; // minimum length for market IDs
+pub const MAX_ID_LENGTH: usize = 64; // maximum length for market IDs
+pub const MAX_LEGACY_ID_LENGTH: usize = 32; // raw IDs used as seeds before hashing
+pub const MAX_TITLE_LENGTH: usize = 128; // market metadata title
+pub const MAX_CATEGORY_LENGTH: usize = 32; // market metadata category
+pub const MAX_URI_LENGTH: usize = 200; // market metadata external URI
+pub const MAX_CONTRIBUTORS: usize = 6; // co-funders per market, all refunded in one CancelMarket
+pub const MIN_CO_FUNDING_BPS: u16 = 500; // a new co-funder adds at least 5% of the incentives so far
+pub const MAX_REVENUE_RECIPIENTS: usize = 5; // treasury revenue split recipients
+pub const REVENUE_RECIPIENT_LEN: usize = 34; // packed address + u16 weight_bps
+pub const MAX_ATTESTATION_LENGTH: usize = ATTESTATION_DOMAIN.len() + 32 + 9 + MAX_ID_LENGTH; // domain, program id, resolution, timestamp, market id
+pub const MAX_COMMITTEE_MEMBERS: usize = 7; // settlement committee oracles
+pub const MAX_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60; // a week, in seconds
+pub const MAX_BATCH_CLAIMS: usize = 6; // positions per ClaimRewardsBatch, bound by the transaction account limit
+pub const MAX_UNSTAKE_COOLDOWN: i64 = 7 * 24 * 60 * 60; // a week, in seconds
+pub const MAX_EARLY_EXIT_PENALTY_BPS: u16 = 5_000; // 50%
+pub const MAX_LOCKUP_TIERS: usize = 4; // lock durations a market can offer
+pub const LOCKUP_TIER_LEN: usize = 10; // packed i64 duration + u16 multiplier_bps
+pub const MAX_LOCKUP_DURATION: i64 = 365 * 24 * 60 * 60; // a year, in seconds
+pub const MAX_LOCKUP_MULTIPLIER_BPS: u16 = 30_000; // 3x
+pub const MAX_IMBALANCE_BOOST_BPS: u16 = 10_000; // up to 2x for the thinner side
+pub const MAX_REWARD_BPS_PER_FARMER: u16 = 10_000; // a farmer may take the whole pool
+pub const REWARD_OVERFLOW_CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60; // 30 days after claims open, in seconds
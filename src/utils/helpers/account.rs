@@ -1,7 +1,15 @@
 use pinocchio::cpi::{Seed, Signer};
 use pinocchio::sysvars::Sysvar;
-use pinocchio::{AccountView, ProgramResult, error::ProgramError, sysvars::rent::Rent};
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio::{AccountView, Address, ProgramResult, error::ProgramError, sysvars::rent::Rent};
+use pinocchio_system::instructions::{CreateAccount, Transfer};
+
+use crate::{
+    states::{
+        AccountHeader, BriberAllowlist, CLOSED_ACCOUNT_DISCRIMINATOR, Config, MarketCreationMode,
+        Versioned,
+    },
+    utils::constants,
+};
 
 pub struct Account;
 impl Account {
@@ -21,12 +29,48 @@ impl Account {
         Ok(())
     }
 
-    pub fn init_pda<T>(
+    /// Checks that `briber` may fund markets under the config market creation
+    /// mode. `gatekeeper` is the authority (signer), the briber's allowlist PDA,
+    /// or not checked here when creation is permissionless.
+    pub fn briber_allowed_check(
+        config: &Config,
+        gatekeeper: &AccountView,
+        briber: &AccountView,
+    ) -> ProgramResult {
+        match config.market_creation_mode() {
+            MarketCreationMode::Authority => {
+                Self::signer_check(gatekeeper)?;
+                if &config.authority() != gatekeeper.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+            MarketCreationMode::Allowlist => {
+                Self::program_account_check(gatekeeper)?;
+
+                let briber_allowlist_data = gatekeeper.try_borrow()?;
+                let briber_allowlist = BriberAllowlist::load(&briber_allowlist_data)?;
+
+                let briber_allowlist_address = Address::derive_address(
+                    &[constants::BRIBER_ALLOWLIST_SEED, briber.address().as_ref()],
+                    Some(briber_allowlist.bump),
+                    &crate::ID,
+                );
+                if &briber_allowlist_address != gatekeeper.address() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+            MarketCreationMode::Permissionless => {}
+        }
+
+        Ok(())
+    }
+
+    pub fn init_pda<T: Versioned>(
         account: &AccountView,
         payer: &AccountView,
         seeds: &[Seed],
     ) -> ProgramResult {
-        let space = size_of::<T>();
+        let space = T::LEN;
         let lamports = Rent::get()?.try_minimum_balance(space)?;
 
         let signer_seeds = &[Signer::from(seeds)];
@@ -38,24 +82,46 @@ impl Account {
             space: space as u64,
             owner: &crate::ID,
         }
-        .invoke_signed(signer_seeds)
+        .invoke_signed(signer_seeds)?;
+
+        let mut data = account.try_borrow_mut()?;
+        AccountHeader::load_mut(&mut data)?.set_inner(T::DISCRIMINATOR, T::VERSION);
+
+        Ok(())
     }
 
-    pub fn init_if_needed<T>(
+    pub fn init_if_needed<T: Versioned>(
         account: &AccountView,
         payer: &AccountView,
         seeds: &[Seed],
     ) -> ProgramResult {
         match Self::program_account_check(account) {
-            Ok(_) => Ok(()),
+            // already initialized, but it must hold a `T`
+            Ok(_) => T::check_header(&account.try_borrow()?),
             Err(_) => Self::init_pda::<T>(account, payer, seeds),
         }
     }
 
+    pub fn realloc(account: &AccountView, payer: &AccountView, new_len: usize) -> ProgramResult {
+        let required_lamports = Rent::get()?.try_minimum_balance(new_len)?;
+        let lamports = required_lamports.saturating_sub(account.lamports());
+
+        if lamports > 0 {
+            Transfer {
+                from: payer,
+                to: account,
+                lamports,
+            }
+            .invoke()?;
+        }
+
+        account.resize(new_len)
+    }
+
     pub fn close(account: &AccountView, destination: &AccountView) -> ProgramResult {
         {
             let mut data = account.try_borrow_mut()?;
-            data[0] = 0xff;
+            data[0] = CLOSED_ACCOUNT_DISCRIMINATOR;
         }
 
         destination.set_lamports(destination.lamports() + account.lamports());
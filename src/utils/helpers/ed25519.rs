@@ -0,0 +1,67 @@
+use pinocchio::{
+    AccountView, Address, ProgramResult, error::ProgramError, sysvars::instructions::Instructions,
+};
+
+use crate::utils::constants;
+
+/// Offsets header of a single-signature ed25519 program instruction.
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// Instruction index meaning "this ed25519 instruction".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub struct Ed25519;
+impl Ed25519 {
+    /// Checks that the instruction right before the current one is an ed25519
+    /// program instruction verifying a single signature by `signer` over
+    /// exactly `message`. The runtime already rejected the transaction if that
+    /// signature was invalid, so only what was verified is checked here.
+    pub fn signature_check(
+        instructions_sysvar: &AccountView,
+        signer: &Address,
+        message: &[u8],
+    ) -> ProgramResult {
+        let instructions = Instructions::try_from(instructions_sysvar)?;
+        let instruction = instructions.get_instruction_relative(-1)?;
+        if instruction.get_program_id() != constants::ED25519_PROGRAM_ADDRESS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let data = instruction.get_instruction_data();
+        let offsets = data
+            .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if data[0] != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let read_u16 = |i: usize| u16::from_le_bytes([offsets[i * 2], offsets[i * 2 + 1]]);
+        let public_key_offset = read_u16(2) as usize;
+        let message_offset = read_u16(4) as usize;
+        let message_size = read_u16(5) as usize;
+
+        // the signature, key and message must all live in the ed25519 instruction
+        if read_u16(1) != CURRENT_INSTRUCTION
+            || read_u16(3) != CURRENT_INSTRUCTION
+            || read_u16(6) != CURRENT_INSTRUCTION
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if public_key != signer.as_ref() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let signed_message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if signed_message != message {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 mod account;
+mod ed25519;
 mod token;
 
-pub use {account::*, token::*};
+pub use {account::*, ed25519::*, token::*};
@@ -1,5 +1,5 @@
 use pinocchio::{
-    AccountView, ProgramResult,
+    AccountView, Address, ProgramResult,
     cpi::{Seed, Signer},
     error::ProgramError,
     sysvars::{Sysvar, rent::Rent},
@@ -44,6 +44,69 @@ impl MintInterface {
         .invoke_signed(&signer_seeds)
     }
 
+    pub fn init_with_seeds(
+        mint: &AccountView,
+        payer: &AccountView,
+        mint_authority: &Address,
+        decimals: u8,
+        token_program: &AccountView,
+        seeds: &[Seed],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.try_minimum_balance(pinocchio_token::state::Mint::LEN)?;
+
+        let signer_seeds = [Signer::from(seeds)];
+
+        CreateAccount {
+            from: payer,
+            to: mint,
+            lamports,
+            space: pinocchio_token::state::Mint::LEN as u64,
+            owner: token_program.address(),
+        }
+        .invoke_signed(&signer_seeds)?;
+
+        pinocchio_token::instructions::InitializeMint2 {
+            mint,
+            decimals,
+            mint_authority,
+            freeze_authority: None,
+        }
+        .invoke()
+    }
+
+    pub fn mint_to_signed(
+        mint: &AccountView,
+        account: &AccountView,
+        mint_authority: &AccountView,
+        amount: u64,
+        seeds: &[Seed],
+    ) -> ProgramResult {
+        let signer_seeds = [Signer::from(seeds)];
+
+        pinocchio_token::instructions::MintTo {
+            mint,
+            account,
+            mint_authority,
+            amount,
+        }
+        .invoke_signed(&signer_seeds)
+    }
+
+    pub fn burn(
+        account: &AccountView,
+        mint: &AccountView,
+        authority: &AccountView,
+        amount: u64,
+    ) -> ProgramResult {
+        pinocchio_token::instructions::Burn {
+            account,
+            mint,
+            authority,
+            amount,
+        }
+        .invoke()
+    }
+
     pub fn valid_mint_check(mint: &AccountView) -> ProgramResult {
         if mint.address() != constants::WSOL_ADDRESS && mint.address() != constants::USDC_ADDRESS {
             return Err(ProgramError::InvalidAccountData);
@@ -54,6 +117,22 @@ impl MintInterface {
 
 pub struct TokenAccountInterface;
 impl TokenAccountInterface {
+    pub fn owner_check(account: &AccountView, owner: &Address) -> ProgramResult {
+        let token_account = pinocchio_token::state::TokenAccount::from_account_view(account)?;
+        if token_account.owner() != owner {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    pub fn mint_check(account: &AccountView, mint: &Address) -> ProgramResult {
+        let token_account = pinocchio_token::state::TokenAccount::from_account_view(account)?;
+        if token_account.mint() != mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
     pub fn init_with_seeds(
         account: &AccountView,
         payer: &AccountView,
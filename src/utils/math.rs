@@ -6,4 +6,13 @@ pub fn fee_calculation(amount: u64, fee_bps: u16) -> Result<u64, ProgramError> {
         .and_then(|v| v.checked_div(10_000))
         .and_then(|v| v.try_into().ok())
         .ok_or(ProgramError::ArithmeticOverflow)
-}
\ No newline at end of file
+}
+
+/// `amount * part / total`, rounded down.
+pub fn pro_rata(amount: u64, part: u64, total: u64) -> Result<u64, ProgramError> {
+    (amount as u128)
+        .checked_mul(part as u128)
+        .and_then(|v| v.checked_div(total as u128))
+        .and_then(|v| v.try_into().ok())
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
@@ -0,0 +1,29 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+
+/**
+ * Builds the `accept_market_ownership` instruction. The new briber also
+ * becomes the fee recipient.
+ *
+ * Accounts (in order):
+ *   new_briber (readonly signer), market (writable)
+ *
+ * Data layout: [u8 discriminator=18]
+ */
+export async function buildAcceptMarketOwnershipIx(
+  id: string,
+  newBriber: Address,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: newBriber, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data: Buffer.from([constants.ACCEPT_MARKET_OWNERSHIP_DISCRIMINATOR]),
+  };
+}
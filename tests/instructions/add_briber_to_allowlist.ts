@@ -0,0 +1,36 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import { getBriberAllowlistPda, getConfigPda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `add_briber_to_allowlist` instruction.
+ *
+ * Accounts (in order):
+ *   authority (writable signer), config, briber, briber_allowlist (writable), system_program
+ *
+ * Data layout: [u8 discriminator=15]
+ */
+export async function buildAddBriberToAllowlistIx(
+  client: Client,
+  briber: Address,
+): Promise<Instruction> {
+  const [configPda, briberAllowlistPda] = await Promise.all([
+    getConfigPda(),
+    getBriberAllowlistPda(briber),
+  ]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: briber, role: AccountRole.READONLY },
+      { address: briberAllowlistPda, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: Buffer.from([constants.ADD_BRIBER_TO_ALLOWLIST_DISCRIMINATOR]),
+  };
+}
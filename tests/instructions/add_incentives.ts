@@ -1,35 +1,69 @@
-import { AccountRole, Instruction } from "@solana/kit";
+import { AccountRole, Address, Instruction, MessageSigner, TransactionSigner } from "@solana/kit";
 import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
-import { getConfigPda, getMarketPda, getMarketVaultPda, getTreasuryPda } from "../utils/pda";
+import {
+  getBriberAllowlistPda,
+  getBriberContributionPda,
+  getConfigPda,
+  getMarketPda,
+  getMarketVaultPda,
+  getTreasuryPda,
+} from "../utils/pda";
 
 import { Accounts } from "../utils/accounts";
+import { MarketCreationMode } from "../utils/fetch/config";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
 import { constants } from "../utils/constants";
 
 export type AddIncentivesParams = {
   id: string;
   /** Amount of incentive tokens to add (in lamports). */
   amount: bigint;
+  /** Highest protocol fee the contributor accepts. Defaults to 5_000, the config cap. */
+  maxFeeBps?: number;
+  /**
+   * Co-funder adding incentives to someone else's market. Defaults to the
+   * market briber, in which case no co-funding accounts are passed.
+   */
+  contributor?: TransactionSigner & MessageSigner;
+  /** Config authority, the gatekeeper for co-funders in Authority mode. */
+  authority?: Address;
+  /** Config market creation mode, selects the gatekeeper account. Defaults to Authority. */
+  creationMode?: MarketCreationMode;
 };
 
 /**
  * Builds the `add_incentives` instruction.
  *
  * Accounts (in order):
- *   briber (writable signer), config, treasury,
- *   market, incentive_mint, briber_ata, market_incentive_vault
+ *   contributor (writable signer), config, treasury,
+ *   market, incentive_mint, contributor_ata, market_incentive_vault, token_program
+ *
+ * Co-funders (anyone but the market briber) also pass:
+ *   gatekeeper, briber_contribution (writable), system_program
+ * where the gatekeeper follows the same rules as in `create_market`, except
+ * that it is unused in Permissionless mode (the contributor is passed).
  *
  * Data layout (after discriminator stripped by the router):
- *   [u8 discriminator=2, u64 amount (LE), ...utf8 id]
+ *   [u8 discriminator=2, u64 amount (LE), u16 max_fee_bps (LE)]
+ *
+ * The protocol fee charged is the one snapshotted on the market at creation.
  */
 export async function buildAddIncentivesIx(
   accounts: Accounts,
-  { id, amount }: AddIncentivesParams,
+  {
+    id,
+    amount,
+    maxFeeBps = 5_000,
+    contributor = accounts.briber,
+    authority,
+    creationMode = MarketCreationMode.Authority,
+  }: AddIncentivesParams,
 ): Promise<Instruction> {
   const marketPda = await getMarketPda(id);
 
-  const [briberAta] = await findAssociatedTokenPda({
+  const [contributorAta] = await findAssociatedTokenPda({
     mint: constants.WSOL_MINT,
-    owner: accounts.briber.address,
+    owner: contributor.address,
     tokenProgram: TOKEN_PROGRAM_ADDRESS,
   });
 
@@ -39,23 +73,50 @@ export async function buildAddIncentivesIx(
     getMarketVaultPda(marketPda, constants.WSOL_MINT),
   ]);
 
-  // Layout: [u8 discriminator=2, u64 amount (LE), ...utf8 id]
-  const header = Buffer.alloc(9);
-  header.writeUInt8(constants.ADD_INCENTIVES_DISCRIMINATOR, 0);
-  header.writeBigUInt64LE(amount, 1);
-  const ixData = Buffer.concat([header, Buffer.from(id, "utf8")]);
+  const coFundingAccounts = [];
+  if (contributor.address !== accounts.briber.address) {
+    let gatekeeper;
+    if (creationMode === MarketCreationMode.Authority) {
+      if (!authority) throw new Error("authority is required to co-fund in Authority mode");
+      gatekeeper = { address: authority, role: AccountRole.READONLY_SIGNER };
+    } else {
+      gatekeeper = {
+        address:
+          creationMode === MarketCreationMode.Allowlist
+            ? await getBriberAllowlistPda(contributor.address)
+            : contributor.address,
+        role: AccountRole.READONLY,
+      };
+    }
+
+    coFundingAccounts.push(
+      gatekeeper,
+      {
+        address: await getBriberContributionPda(marketPda, contributor.address),
+        role: AccountRole.WRITABLE,
+      },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    );
+  }
+
+  // Layout: [u8 discriminator=2, u64 amount (LE), u16 max_fee_bps (LE)]
+  const ixData = Buffer.alloc(11);
+  ixData.writeUInt8(constants.ADD_INCENTIVES_DISCRIMINATOR, 0);
+  ixData.writeBigUInt64LE(amount, 1);
+  ixData.writeUInt16LE(maxFeeBps, 9);
 
   return {
     programAddress: constants.PROGRAM_ID,
     accounts: [
-      { address: accounts.briber.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: contributor.address, role: AccountRole.WRITABLE_SIGNER },
       { address: configPda, role: AccountRole.READONLY },
       { address: wsolTreasuryPda, role: AccountRole.WRITABLE },
       { address: marketPda, role: AccountRole.WRITABLE },
       { address: constants.WSOL_MINT, role: AccountRole.READONLY },
-      { address: briberAta, role: AccountRole.WRITABLE },
+      { address: contributorAta, role: AccountRole.WRITABLE },
       { address: marketIncentiveVaultPda, role: AccountRole.WRITABLE },
       { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...coFundingAccounts,
     ],
     data: ixData,
   };
@@ -1,6 +1,6 @@
 import { AccountRole, Address, Instruction } from "@solana/kit";
 import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
-import { getMarketPda, getMarketVaultPda } from "../utils/pda";
+import { getBriberContributionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
 
 import { Accounts } from "../utils/accounts";
 import { constants } from "../utils/constants";
@@ -11,6 +11,10 @@ export type CancelMarketParams = {
   yesMint: Address;
   /** Address of the no-outcome mint used when the market was created. */
   noMint: Address;
+  /** Every co-funder of the market, refunded pro rata to their contribution. */
+  contributors?: Address[];
+  /** Owner of the ATAs paid leftover penalties, `market.fee_recipient`. Defaults to the briber. */
+  feeRecipient?: Address;
 };
 
 /**
@@ -19,16 +23,27 @@ export type CancelMarketParams = {
  * Accounts (in order):
  *   briber (writable signer), market, incentive_mint,
  *   briber_ata, market_incentive_vault,
- *   market_yes_vault, market_no_vault
+ *   market_yes_vault, market_no_vault, recipient_yes_ata, recipient_no_ata, token_program,
+ *   then for each co-funder:
+ *   briber_contribution (writable), contributor_ata (writable), contributor (writable)
  *
- * Data layout: [u8 discriminator=3, ...utf8 id]
+ * Data layout: [u8 discriminator=3]  — no extra fields.
  *
  * Pre-condition: the market must have no pending fees
  * (available_yes_fees == 0 && available_no_fees == 0).
+ *
+ * Early-exit penalties left in the outcome vaults are paid to the fee recipient's
+ * outcome ATAs, which only need to exist when there are penalties.
  */
 export async function buildCancelMarketIx(
   accounts: Accounts,
-  { id, yesMint, noMint }: CancelMarketParams,
+  {
+    id,
+    yesMint,
+    noMint,
+    contributors = [],
+    feeRecipient = accounts.briber.address,
+  }: CancelMarketParams,
 ): Promise<Instruction> {
   const marketPda = await getMarketPda(id);
 
@@ -43,12 +58,31 @@ export async function buildCancelMarketIx(
     getMarketVaultPda(marketPda, yesMint),
     getMarketVaultPda(marketPda, noMint),
   ]);
+  const [[recipientYesAta], [recipientNoAta]] = await Promise.all(
+    [yesMint, noMint].map((mint) =>
+      findAssociatedTokenPda({ mint, owner: feeRecipient, tokenProgram: TOKEN_PROGRAM_ADDRESS }),
+    ),
+  );
 
-  // Layout: [u8 discriminator=3, ...utf8 id]
-  const ixData = Buffer.concat([
-    Buffer.from([constants.CANCEL_MARKET_DISCRIMINATOR]),
-    Buffer.from(id, "utf8"),
-  ]);
+  const contributorAccounts = [];
+  for (const contributor of contributors) {
+    const [[contributorAta], briberContributionPda] = await Promise.all([
+      findAssociatedTokenPda({
+        mint: constants.WSOL_MINT,
+        owner: contributor,
+        tokenProgram: TOKEN_PROGRAM_ADDRESS,
+      }),
+      getBriberContributionPda(marketPda, contributor),
+    ]);
+    contributorAccounts.push(
+      { address: briberContributionPda, role: AccountRole.WRITABLE },
+      { address: contributorAta, role: AccountRole.WRITABLE },
+      { address: contributor, role: AccountRole.WRITABLE },
+    );
+  }
+
+  // Layout: [u8 discriminator=3]
+  const ixData = Buffer.from([constants.CANCEL_MARKET_DISCRIMINATOR]);
 
   return {
     programAddress: constants.PROGRAM_ID,
@@ -59,7 +93,10 @@ export async function buildCancelMarketIx(
       { address: marketIncentiveVaultPda, role: AccountRole.WRITABLE },
       { address: marketYesVaultPda, role: AccountRole.WRITABLE },
       { address: marketNoVaultPda, role: AccountRole.WRITABLE },
+      { address: recipientYesAta, role: AccountRole.WRITABLE },
+      { address: recipientNoAta, role: AccountRole.WRITABLE },
       { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...contributorAccounts,
     ],
     data: ixData,
   };
@@ -1,10 +1,10 @@
-import { AccountRole, Address, Instruction } from "@solana/kit";
+import { AccountRole, Address, Instruction, MessageSigner, TransactionSigner } from "@solana/kit";
 import {
   TOKEN_PROGRAM_ADDRESS,
   findAssociatedTokenPda,
-  getCreateAssociatedTokenInstructionAsync,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
 } from "@solana-program/token";
-import { getMarketPda, getMarketVaultPda } from "../utils/pda";
+import { getBriberContributionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
 
 import { Accounts } from "../utils/accounts";
 import { constants } from "../utils/constants";
@@ -17,61 +17,79 @@ export type ClaimFeesParams = {
    * `market.outcome_no_mint` read from on-chain state.
    */
   outcomeMint: Address;
+  /** Owner of the destination ATA, must match `market.fee_recipient`. Defaults to the briber. */
+  feeRecipient?: Address;
+  /**
+   * Co-funder claiming its share into its own ATA, closing its contribution.
+   * Defaults to the briber claiming the briber share.
+   */
+  contributor?: TransactionSigner & MessageSigner;
 };
 
 /**
  * Builds the `claim_fees` instruction.
  *
  * Accounts (in order):
- *   briber (writable signer), market (writable), outcome_mint (readonly),
- *   briber_ata (writable), market_outcome_vault (writable), token_program
+ *   claimer (writable signer), market (writable), outcome_mint (readonly),
+ *   recipient_ata (writable), market_outcome_vault (writable), token_program,
+ *   briber_contribution (writable, co-funders only)
  *
- * Data layout: [u8 discriminator=4, ...utf8 id]
+ * Data layout: [u8 discriminator=4]  — no extra fields.
  *
  * Pre-conditions:
  *   - Market must be resolved (yes or no).
  *   - Market must have available fees > 0 on the resolved side.
+ *   - Fees are split pro rata to the incentives each funder added.
  */
 export async function buildClaimFeesIxs(
   accounts: Accounts,
-  { id, outcomeMint }: ClaimFeesParams,
+  { id, outcomeMint, feeRecipient = accounts.briber.address, contributor }: ClaimFeesParams,
 ): Promise<Instruction[]> {
   const marketPda = await getMarketPda(id);
+  const claimer = contributor ?? accounts.briber;
+  const recipient = contributor ? contributor.address : feeRecipient;
 
-  const [[briberAta], marketOutcomeVaultPda] = await Promise.all([
+  const [[recipientAta], marketOutcomeVaultPda] = await Promise.all([
     findAssociatedTokenPda({
       mint: outcomeMint,
-      owner: accounts.briber.address,
+      owner: recipient,
       tokenProgram: TOKEN_PROGRAM_ADDRESS,
     }),
     getMarketVaultPda(marketPda, outcomeMint),
   ]);
 
-  const createBriberAtaIx = await getCreateAssociatedTokenInstructionAsync({
-    payer: accounts.briber,
-    ata: briberAta,
-    owner: accounts.briber.address,
+  const createRecipientAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+    payer: claimer,
+    ata: recipientAta,
+    owner: recipient,
     mint: outcomeMint,
   });
 
-  // Layout: [u8 discriminator=4, ...utf8 id]
-  const ixData = Buffer.concat([
-    Buffer.from([constants.CLAIM_FEES_DISCRIMINATOR]),
-    Buffer.from(id, "utf8"),
-  ]);
+  const contributionAccounts = contributor
+    ? [
+        {
+          address: await getBriberContributionPda(marketPda, contributor.address),
+          role: AccountRole.WRITABLE,
+        },
+      ]
+    : [];
+
+  // Layout: [u8 discriminator=4]
+  const ixData = Buffer.from([constants.CLAIM_FEES_DISCRIMINATOR]);
 
   const claimIx: Instruction = {
     programAddress: constants.PROGRAM_ID,
     accounts: [
-      { address: accounts.briber.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: claimer.address, role: AccountRole.WRITABLE_SIGNER },
       { address: marketPda, role: AccountRole.WRITABLE },
       { address: outcomeMint, role: AccountRole.READONLY },
-      { address: briberAta, role: AccountRole.WRITABLE },
+      { address: recipientAta, role: AccountRole.WRITABLE },
       { address: marketOutcomeVaultPda, role: AccountRole.WRITABLE },
       { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...contributionAccounts,
     ],
     data: ixData,
   };
 
-  return [createBriberAtaIx, claimIx];
+  return [createRecipientAtaIx, claimIx];
 }
@@ -0,0 +1,72 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
+} from "@solana-program/token";
+import { getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+
+export type ClaimPenaltiesParams = {
+  id: string;
+  /** Outcome mint of the side whose penalties no staker can claim. */
+  outcomeMint: Address;
+  /** Owner of the destination ATA, must match `market.fee_recipient`. Defaults to the briber. */
+  feeRecipient?: Address;
+};
+
+/**
+ * Builds the `claim_penalties` instruction.
+ *
+ * Accounts (in order):
+ *   briber (writable signer), market (writable), outcome_mint (readonly),
+ *   recipient_ata (writable), market_outcome_vault (writable), token_program
+ *
+ * Data layout: [u8 discriminator=40]  — no extra fields.
+ *
+ * Pre-conditions:
+ *   - Market must be resolved and past its dispute window.
+ *   - The side lost, or has no winning stake left to claim.
+ */
+export async function buildClaimPenaltiesIxs(
+  accounts: Accounts,
+  { id, outcomeMint, feeRecipient = accounts.briber.address }: ClaimPenaltiesParams,
+): Promise<Instruction[]> {
+  const marketPda = await getMarketPda(id);
+
+  const [[recipientAta], marketOutcomeVaultPda] = await Promise.all([
+    findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: feeRecipient,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    getMarketVaultPda(marketPda, outcomeMint),
+  ]);
+
+  const createRecipientAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+    payer: accounts.briber,
+    ata: recipientAta,
+    owner: feeRecipient,
+    mint: outcomeMint,
+  });
+
+  // Layout: [u8 discriminator=40]
+  const ixData = Buffer.from([constants.CLAIM_PENALTIES_DISCRIMINATOR]);
+
+  const claimIx: Instruction = {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+      { address: outcomeMint, role: AccountRole.READONLY },
+      { address: recipientAta, role: AccountRole.WRITABLE },
+      { address: marketOutcomeVaultPda, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+
+  return [createRecipientAtaIx, claimIx];
+}
@@ -2,7 +2,7 @@ import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/ki
 import {
   TOKEN_PROGRAM_ADDRESS,
   findAssociatedTokenPda,
-  getCreateAssociatedTokenInstructionAsync,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
 } from "@solana-program/token";
 import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
 
@@ -15,11 +15,16 @@ export interface ClaimRewardsParams {
   /** The winning outcome mint — yes or no based on market resolution. */
   outcomeMint: Address;
   farmer: TransactionSigner;
+  /** Signs instead of the farmer, as set with `set_position_delegate`. */
+  delegate?: TransactionSigner;
+  /** Position's reward_recipient, owner of the payout ATAs (defaults to the farmer). */
+  rewardRecipient?: Address;
 }
 
 /**
  * Builds the instructions required for the `claim_rewards` flow:
- *   1. Create farmer's ATA for rewardMint if it doesn't exist yet.
+ *   1. Create the recipient's ATA for rewardMint (and outcomeMint for a
+ *      reward_recipient) if it doesn't exist yet (idempotent).
  *   2. claim_rewards program instruction.
  *
  * Accounts (in order for the program ix):
@@ -27,9 +32,13 @@ export interface ClaimRewardsParams {
  *   reward_mint (readonly), outcome_mint (readonly),
  *   market_reward_vault (writable), market_outcome_vault (writable),
  *   farmer_reward_ata (writable), farmer_outcome_ata (writable),
- *   token_program (readonly)
+ *   token_program (readonly), [farmer (writable)] when signed by the delegate
  *
- * Data layout: [u8 discriminator=9, ...utf8 market_id]
+ * Data layout: [u8 discriminator=9]  — no extra fields.
+ *
+ * A delegate signs in place of the farmer, which then trails the accounts.
+ * Delegated claims, and claims on positions with a reward_recipient, must pay
+ * into ATAs owned by the recipient (the farmer when none is set).
  *
  * Pre-conditions:
  *   - Market must be settled.
@@ -42,7 +51,9 @@ export interface ClaimRewardsParams {
  *   - farmer_position account is closed (rent returned to farmer).
  */
 export async function buildClaimRewardsIxs(params: ClaimRewardsParams): Promise<Instruction[]> {
-  const { id, rewardMint, outcomeMint, farmer } = params;
+  const { id, rewardMint, outcomeMint, farmer, delegate, rewardRecipient } = params;
+  const authority = delegate ?? farmer;
+  const recipient = rewardRecipient ?? farmer.address;
 
   const marketAddress = await getMarketPda(id);
 
@@ -55,12 +66,12 @@ export async function buildClaimRewardsIxs(params: ClaimRewardsParams): Promise<
   ] = await Promise.all([
     findAssociatedTokenPda({
       mint: rewardMint,
-      owner: farmer.address,
+      owner: recipient,
       tokenProgram: TOKEN_PROGRAM_ADDRESS,
     }),
     findAssociatedTokenPda({
       mint: outcomeMint,
-      owner: farmer.address,
+      owner: recipient,
       tokenProgram: TOKEN_PROGRAM_ADDRESS,
     }),
     getFarmerPositionPda(marketAddress, farmer.address),
@@ -68,23 +79,28 @@ export async function buildClaimRewardsIxs(params: ClaimRewardsParams): Promise<
     getMarketVaultPda(marketAddress, outcomeMint),
   ]);
 
-  const createFarmerRewardAtaIx = await getCreateAssociatedTokenInstructionAsync({
-    payer: farmer,
-    ata: farmerRewardAta,
-    owner: farmer.address,
-    mint: rewardMint,
-  });
+  const payoutAtas: [Address, Address][] = [
+    [farmerRewardAta, rewardMint],
+    ...(rewardRecipient ? ([[farmerOutcomeAta, outcomeMint]] as [Address, Address][]) : []),
+  ];
+  const createAtaIxs = await Promise.all(
+    payoutAtas.map(([ata, mint]) =>
+      getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer: authority,
+        ata,
+        owner: recipient,
+        mint,
+      }),
+    ),
+  );
 
-  // Data layout: [u8 disc=9, ...utf8 market_id]
-  const ixData = Buffer.concat([
-    Buffer.from([constants.CLAIM_REWARDS_DISCRIMINATOR]),
-    Buffer.from(id, "utf-8"),
-  ]);
+  // Data layout: [u8 disc=9]
+  const ixData = Buffer.from([constants.CLAIM_REWARDS_DISCRIMINATOR]);
 
   const claimIx: Instruction = {
     programAddress: constants.PROGRAM_ID,
     accounts: [
-      { address: farmer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: authority.address, role: AccountRole.WRITABLE_SIGNER },
       { address: marketAddress, role: AccountRole.WRITABLE },
       { address: farmerPositionAddress, role: AccountRole.WRITABLE },
       { address: rewardMint, role: AccountRole.READONLY },
@@ -94,9 +110,10 @@ export async function buildClaimRewardsIxs(params: ClaimRewardsParams): Promise<
       { address: farmerRewardAta, role: AccountRole.WRITABLE },
       { address: farmerOutcomeAta, role: AccountRole.WRITABLE },
       { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...(delegate ? [{ address: farmer.address, role: AccountRole.WRITABLE }] : []),
     ],
     data: ixData,
   };
 
-  return [createFarmerRewardAtaIx, claimIx];
+  return [...createAtaIxs, claimIx];
 }
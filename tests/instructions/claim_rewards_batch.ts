@@ -0,0 +1,108 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
+} from "@solana-program/token";
+import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+export interface ClaimRewardsBatchPosition {
+  id: string;
+  /** market.incentive_mint — the reward token. */
+  rewardMint: Address;
+  /** The outcome mint the farmer staked and expects to have won. */
+  outcomeMint: Address;
+}
+
+/**
+ * Builds the instructions required for the `claim_rewards_batch` flow:
+ *   1. Create the farmer's ATA for each reward mint if it doesn't exist yet.
+ *   2. claim_rewards_batch program instruction.
+ *
+ * Accounts (in order for the program ix):
+ *   farmer (writable signer), token_program (readonly), then per position (1..=6):
+ *   market (writable), farmer_position PDA (writable), reward_mint (readonly),
+ *   outcome_mint (readonly), market_reward_vault (writable),
+ *   market_outcome_vault (writable), farmer_reward_ata (writable),
+ *   farmer_outcome_ata (writable)
+ *
+ * Data layout: [u8 discriminator=28]  — no extra fields.
+ *
+ * Each position is claimed like `claim_rewards`. Positions on markets still in
+ * their dispute window, on the losing side, or already claimed are skipped and
+ * logged; invalid accounts fail the whole instruction.
+ */
+export async function buildClaimRewardsBatchIxs(
+  farmer: TransactionSigner,
+  positions: ClaimRewardsBatchPosition[],
+): Promise<Instruction[]> {
+  const rewardMints = [...new Set(positions.map((p) => p.rewardMint))];
+  const createRewardAtaIxs = await Promise.all(
+    rewardMints.map(async (mint) =>
+      getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer: farmer,
+        ata: (
+          await findAssociatedTokenPda({
+            mint,
+            owner: farmer.address,
+            tokenProgram: TOKEN_PROGRAM_ADDRESS,
+          })
+        )[0],
+        owner: farmer.address,
+        mint,
+      }),
+    ),
+  );
+
+  const positionAccounts = await Promise.all(
+    positions.map(async ({ id, rewardMint, outcomeMint }) => {
+      const marketAddress = await getMarketPda(id);
+      const [
+        [farmerRewardAta],
+        [farmerOutcomeAta],
+        farmerPositionAddress,
+        marketRewardVault,
+        marketOutcomeVault,
+      ] = await Promise.all([
+        findAssociatedTokenPda({
+          mint: rewardMint,
+          owner: farmer.address,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+        findAssociatedTokenPda({
+          mint: outcomeMint,
+          owner: farmer.address,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+        getFarmerPositionPda(marketAddress, farmer.address),
+        getMarketVaultPda(marketAddress, rewardMint),
+        getMarketVaultPda(marketAddress, outcomeMint),
+      ]);
+
+      return [
+        { address: marketAddress, role: AccountRole.WRITABLE },
+        { address: farmerPositionAddress, role: AccountRole.WRITABLE },
+        { address: rewardMint, role: AccountRole.READONLY },
+        { address: outcomeMint, role: AccountRole.READONLY },
+        { address: marketRewardVault, role: AccountRole.WRITABLE },
+        { address: marketOutcomeVault, role: AccountRole.WRITABLE },
+        { address: farmerRewardAta, role: AccountRole.WRITABLE },
+        { address: farmerOutcomeAta, role: AccountRole.WRITABLE },
+      ];
+    }),
+  );
+
+  const claimIx: Instruction = {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: farmer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...positionAccounts.flat(),
+    ],
+    data: Buffer.from([constants.CLAIM_REWARDS_BATCH_DISCRIMINATOR]),
+  };
+
+  return [...createRewardAtaIxs, claimIx];
+}
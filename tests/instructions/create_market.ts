@@ -7,23 +7,46 @@ import {
 } from "@solana-program/token";
 import { AccountRole, Address, Instruction, KeyPairSigner } from "@solana/kit";
 import { SYSTEM_PROGRAM_ADDRESS, getTransferSolInstruction } from "@solana-program/system";
-import { getConfigPda, getMarketPda, getMarketVaultPda, getTreasuryPda } from "../utils/pda";
+import {
+  getBriberAllowlistPda,
+  getConfigPda,
+  getMarketPda,
+  getMarketVaultPda,
+  getOutcomeMintsPda,
+  getTreasuryPda,
+} from "../utils/pda";
 
 import { Accounts } from "../utils/accounts";
 import { Client } from "../utils/client";
 import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { MarketCreationMode } from "../utils/fetch/config";
 import { constants } from "../utils/constants";
 
 export type CreateMarketParams = {
+  /** Kalshi ticker, 10 to 64 bytes. The market PDA is derived from its sha256 hash. */
   id: string;
   /** Amount of incentive tokens to lock (in lamports). */
   amount: bigint;
+  /** Staking fee charged to farmers, within the config bounds. */
+  briberFeeBps: number;
+  /** Highest protocol fee the briber accepts. Defaults to 5_000, the config cap. */
+  maxFeeBps?: number;
+  /** Smallest stake accepted, after staking fees. Defaults to 0, any amount. */
+  minStake?: bigint;
+  /** Cap on each farmer's stake per side. Defaults to 0, no cap. */
+  maxStakePerFarmer?: bigint;
+  /** Cap on each side's total stake. Defaults to 0, no cap. */
+  maxTotalStake?: bigint;
+  /** Cap on each farmer's reward, in bps of the incentives. Defaults to 0, no cap. */
+  maxRewardBpsPerFarmer?: number;
   yesMint: KeyPairSigner;
   noMint: KeyPairSigner;
   /** SOL to wrap as WSOL in the briber's ATA. Defaults to 100 SOL. */
   briberWsolAmount?: bigint;
   /** Incentive mint, defaults to WSOL. */
   incentiveMint?: Address;
+  /** Config market creation mode, selects the gatekeeper account. Defaults to Authority. */
+  creationMode?: MarketCreationMode;
 };
 
 /**
@@ -34,10 +57,14 @@ export type CreateMarketParams = {
  *   4. create_market program instruction
  *
  * Accounts (in order for the program ix):
- *   authority (readonly signer), config, briber (writable signer), market,
+ *   gatekeeper, config, briber (writable signer), market,
  *   incentive_mint, briber_ata, market_incentive_vault, treasury,
  *   outcome_yes_mint, outcome_no_mint, market_yes_vault, market_no_vault,
  *   token_program, associated_token_program, system_program
+ *
+ * The gatekeeper is the config authority (readonly signer) in Authority mode,
+ * the briber's allowlist PDA in Allowlist mode, and the ticker's outcome mints
+ * PDA in Permissionless mode, which must hold the yes and no mints.
  */
 export async function buildCreateMarketIxs(
   client: Client,
@@ -45,10 +72,17 @@ export async function buildCreateMarketIxs(
   {
     id,
     amount,
+    briberFeeBps,
+    maxFeeBps = 5_000,
+    minStake = 0n,
+    maxStakePerFarmer = 0n,
+    maxTotalStake = 0n,
+    maxRewardBpsPerFarmer = 0,
     yesMint,
     noMint,
     briberWsolAmount = BigInt(100 * LAMPORTS_PER_SOL),
     incentiveMint = constants.WSOL_MINT,
+    creationMode = MarketCreationMode.Authority,
   }: CreateMarketParams,
 ): Promise<Instruction[]> {
   const marketPda = await getMarketPda(id);
@@ -83,16 +117,35 @@ export async function buildCreateMarketIxs(
 
   const syncNativeIx = getSyncNativeInstruction({ account: briberAta });
 
-  // Layout: [u8 discriminator, u64 amount, ...utf8 id]
-  const firstData = Buffer.alloc(9);
+  const gatekeeper =
+    creationMode === MarketCreationMode.Authority
+      ? { address: client.wallet.address, role: AccountRole.READONLY_SIGNER }
+      : {
+          address:
+            creationMode === MarketCreationMode.Allowlist
+              ? await getBriberAllowlistPda(accounts.briber.address)
+              : await getOutcomeMintsPda(id),
+          role: AccountRole.READONLY,
+        };
+
+  // Layout: [u8 discriminator, u64 amount, u16 briber_fee_bps, u16 max_fee_bps,
+  //          u64 min_stake, u64 max_stake_per_farmer, u64 max_total_stake,
+  //          u16 max_reward_bps_per_farmer, ...utf8 id]
+  const firstData = Buffer.alloc(39);
   firstData.writeUInt8(constants.CREATE_MARKET_DISCRIMINATOR, 0);
   firstData.writeBigUInt64LE(amount, 1);
+  firstData.writeUInt16LE(briberFeeBps, 9);
+  firstData.writeUInt16LE(maxFeeBps, 11);
+  firstData.writeBigUInt64LE(minStake, 13);
+  firstData.writeBigUInt64LE(maxStakePerFarmer, 21);
+  firstData.writeBigUInt64LE(maxTotalStake, 29);
+  firstData.writeUInt16LE(maxRewardBpsPerFarmer, 37);
   const ixData = Buffer.concat([firstData, Buffer.from(id, "utf8")]);
 
   const createMarketIx: Instruction = {
     programAddress: constants.PROGRAM_ID,
     accounts: [
-      { address: client.wallet.address, role: AccountRole.READONLY_SIGNER },
+      gatekeeper,
       { address: configPda, role: AccountRole.READONLY },
       { address: accounts.briber.address, role: AccountRole.WRITABLE_SIGNER },
       { address: marketPda, role: AccountRole.WRITABLE },
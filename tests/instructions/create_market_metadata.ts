@@ -0,0 +1,72 @@
+import { AccountRole, Instruction } from "@solana/kit";
+import { getMarketMetadataPda, getMarketPda } from "../utils/pda";
+
+import { Accounts } from "../utils/accounts";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+export type MarketMetadataParams = {
+  id: string;
+  /** Up to 128 bytes. */
+  title: string;
+  /** Up to 32 bytes. */
+  category: string;
+  /** Unix timestamp (seconds) at which the market is expected to resolve. */
+  expectedResolutionTimestamp: bigint;
+  /** External URI, up to 200 bytes. */
+  uri: string;
+};
+
+/**
+ * Encodes the editable metadata fields shared by `create_market_metadata`
+ * and `update_market_metadata`:
+ *   [u8 discriminator, i64 expected_resolution_timestamp (LE),
+ *    u8 title_len, ...utf8 title, u8 category_len, ...utf8 category,
+ *    u8 uri_len, ...utf8 uri]
+ */
+export function encodeMarketMetadataData(
+  discriminator: number,
+  { title, category, expectedResolutionTimestamp, uri }: MarketMetadataParams,
+): Buffer {
+  const header = Buffer.alloc(9);
+  header.writeUInt8(discriminator, 0);
+  header.writeBigInt64LE(expectedResolutionTimestamp, 1);
+
+  const lengthPrefixed = (value: string) => {
+    const bytes = Buffer.from(value, "utf8");
+    return Buffer.concat([Buffer.from([bytes.length]), bytes]);
+  };
+
+  return Buffer.concat([
+    header,
+    lengthPrefixed(title),
+    lengthPrefixed(category),
+    lengthPrefixed(uri),
+  ]);
+}
+
+/**
+ * Builds the `create_market_metadata` instruction. The ticker is copied from
+ * the market, so only the briber-editable fields are sent.
+ *
+ * Accounts (in order):
+ *   briber (writable signer), market, market_metadata (writable), system_program
+ */
+export async function buildCreateMarketMetadataIx(
+  accounts: Accounts,
+  params: MarketMetadataParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(params.id);
+  const marketMetadataPda = await getMarketMetadataPda(marketPda);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketPda, role: AccountRole.READONLY },
+      { address: marketMetadataPda, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: encodeMarketMetadataData(constants.CREATE_MARKET_METADATA_DISCRIMINATOR, params),
+  };
+}
@@ -0,0 +1,48 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import { getMarketPda, getReceiptMintPda } from "../utils/pda";
+
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { TOKEN_PROGRAM_ADDRESS } from "@solana-program/token";
+import { constants } from "../utils/constants";
+
+interface CreateReceiptMintParams {
+  id: string;
+  outcomeMint: Address;
+  payer: TransactionSigner;
+}
+
+/**
+ * Builds the `create_receipt_mint` instruction.
+ *
+ * Accounts (in order):
+ *   payer (writable signer), market (writable), outcome_mint (readonly),
+ *   receipt_mint PDA (writable), token_program (readonly), system_program (readonly)
+ *
+ * Data layout: [u8 discriminator=30]  — no extra fields.
+ *
+ * The receipt mint copies the outcome mint decimals and has the market PDA as
+ * mint authority.
+ */
+export async function buildCreateReceiptMintIx(
+  params: CreateReceiptMintParams,
+): Promise<Instruction> {
+  const { id, outcomeMint, payer } = params;
+
+  const marketAddress = await getMarketPda(id);
+  const receiptMint = await getReceiptMintPda(marketAddress, outcomeMint);
+
+  const ixData = Buffer.from([constants.CREATE_RECEIPT_MINT_DISCRIMINATOR]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: payer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketAddress, role: AccountRole.WRITABLE },
+      { address: outcomeMint, role: AccountRole.READONLY },
+      { address: receiptMint, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
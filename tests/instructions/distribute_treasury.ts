@@ -0,0 +1,81 @@
+import { AccountRole, Address, Instruction, MessageSigner, TransactionSigner } from "@solana/kit";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
+} from "@solana-program/token";
+import { getConfigPda, getRevenueSplitPda, getTreasuryPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+export type DistributeTreasuryParams = {
+  /** Any signer, the crank is permissionless. Pays for missing recipient ATAs. */
+  payer: TransactionSigner & MessageSigner;
+  /** Treasury mint to distribute. */
+  mint: Address;
+  /** Recipients in the order stored on the revenue split. */
+  recipients: Address[];
+};
+
+/**
+ * Builds the instructions required for the `distribute_treasury` flow:
+ *   1. Create each recipient's ATA for the mint if it doesn't exist yet.
+ *   2. distribute_treasury program instruction.
+ *
+ * Accounts (in order for the program ix):
+ *   config, revenue_split, mint, treasury (writable), token_program,
+ *   then one destination ATA (writable) per recipient, in revenue split order
+ *
+ * Data layout: [u8 discriminator=22]  — no extra fields.
+ */
+export async function buildDistributeTreasuryIxs({
+  payer,
+  mint,
+  recipients,
+}: DistributeTreasuryParams): Promise<Instruction[]> {
+  const [configPda, revenueSplitPda, treasuryPda] = await Promise.all([
+    getConfigPda(),
+    getRevenueSplitPda(),
+    getTreasuryPda(mint),
+  ]);
+
+  const destinations = await Promise.all(
+    recipients.map(async (recipient) => {
+      const [ata] = await findAssociatedTokenPda({
+        mint,
+        owner: recipient,
+        tokenProgram: TOKEN_PROGRAM_ADDRESS,
+      });
+      return ata;
+    }),
+  );
+
+  const createDestinationIxs = await Promise.all(
+    recipients.map((recipient, i) =>
+      getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer,
+        ata: destinations[i],
+        owner: recipient,
+        mint,
+      }),
+    ),
+  );
+
+  // Layout: [u8 discriminator=22]
+  const ixData = Buffer.from([constants.DISTRIBUTE_TREASURY_DISCRIMINATOR]);
+
+  const distributeIx: Instruction = {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: configPda, role: AccountRole.READONLY },
+      { address: revenueSplitPda, role: AccountRole.READONLY },
+      { address: mint, role: AccountRole.READONLY },
+      { address: treasuryPda, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...destinations.map((address) => ({ address, role: AccountRole.WRITABLE })),
+    ],
+    data: ixData,
+  };
+
+  return [...createDestinationIxs, distributeIx];
+}
@@ -2,6 +2,7 @@ import { AccountRole, Instruction } from "@solana/kit";
 import { getConfigPda, getTreasuryPda } from "../utils/pda";
 
 import { Client } from "../utils/client";
+import { MarketCreationMode } from "../utils/fetch/config";
 import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
 import { TOKEN_PROGRAM_ADDRESS } from "@solana-program/token";
 import { constants } from "../utils/constants";
@@ -17,12 +18,19 @@ export async function buildInitializeIx(
   client: Client,
   feeBps: number = 500,
   briberFeeBps: number = 500,
+  minBriberFeeBps: number = 0,
+  maxBriberFeeBps: number = 1_000,
+  marketCreationMode: MarketCreationMode = MarketCreationMode.Authority,
 ): Promise<Instruction> {
-  // Layout: [u8 discriminator, u16 fee_bps, u16 briber_fee_bps]
-  const data = Buffer.alloc(5);
+  // Layout: [u8 discriminator, u16 fee_bps, u16 briber_fee_bps,
+  //          u16 min_briber_fee_bps, u16 max_briber_fee_bps, u8 market_creation_mode]
+  const data = Buffer.alloc(10);
   data.writeUInt8(constants.INITIALIZE_DISCRIMINATOR, 0);
   data.writeUInt16LE(feeBps, 1);
   data.writeUInt16LE(briberFeeBps, 3);
+  data.writeUInt16LE(minBriberFeeBps, 5);
+  data.writeUInt16LE(maxBriberFeeBps, 7);
+  data.writeUInt8(marketCreationMode, 9);
 
   const [configPda, wsolTreasuryPda, usdcTreasuryPda] = await Promise.all([
     getConfigPda(),
@@ -0,0 +1,116 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+export interface MigrateAccountParams {
+  /** Any program-owned state account. */
+  account: Address;
+  /** Pays the rent top-up for the grown account. */
+  payer: TransactionSigner;
+  /** Raw id of a market created before ids were hashed into its seed. */
+  legacyMarketId?: string;
+}
+
+/**
+ * Builds the `migrate_account` instruction.
+ *
+ * Accounts (in order):
+ *   payer (writable signer), account (writable), system_program (readonly)
+ *
+ * Data layout: [u8 discriminator=11, ...utf8 legacy_market_id]
+ *   The id is only required when migrating a Market created before hashed seeds.
+ *
+ * Upgrades the account to the current layout version. Accounts that are
+ * already up to date are left untouched.
+ */
+export async function buildMigrateAccountIx(params: MigrateAccountParams): Promise<Instruction> {
+  const { account, payer, legacyMarketId = "" } = params;
+
+  // Layout: [u8 discriminator=11, ...utf8 legacy_market_id]
+  const ixData = Buffer.concat([
+    Buffer.from([constants.MIGRATE_ACCOUNT_DISCRIMINATOR]),
+    Buffer.from(legacyMarketId, "utf-8"),
+  ]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: payer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: account, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
+
+// size of each account type's current layout, by discriminator; older layouts are smaller
+const CURRENT_ACCOUNT_LENS: Record<number, number> = {
+  [constants.CONFIG_ACCOUNT_DISCRIMINATOR]: 84,
+  [constants.MARKET_ACCOUNT_DISCRIMINATOR]: 537,
+  [constants.FARMER_POSITION_ACCOUNT_DISCRIMINATOR]: 156,
+  [constants.MARKET_METADATA_ACCOUNT_DISCRIMINATOR]: 471,
+  [constants.BRIBER_ALLOWLIST_ACCOUNT_DISCRIMINATOR]: 35,
+  [constants.BRIBER_CONTRIBUTION_ACCOUNT_DISCRIMINATOR]: 84,
+  [constants.REVENUE_SPLIT_ACCOUNT_DISCRIMINATOR]: 174,
+  [constants.SETTLEMENT_COMMITTEE_ACCOUNT_DISCRIMINATOR]: 229,
+  [constants.SETTLEMENT_VOTES_ACCOUNT_DISCRIMINATOR]: 267,
+  [constants.OUTCOME_MINTS_ACCOUNT_DISCRIMINATOR]: 67,
+};
+
+// headerless accounts from before versioning, recognized by their size
+const LEGACY_ACCOUNT_DISCRIMINATORS: Record<number, number> = {
+  37: constants.CONFIG_ACCOUNT_DISCRIMINATOR,
+  181: constants.MARKET_ACCOUNT_DISCRIMINATOR,
+  18: constants.FARMER_POSITION_ACCOUNT_DISCRIMINATOR,
+};
+
+export interface ProgramAccount {
+  address: Address;
+  data: Uint8Array;
+}
+
+/**
+ * Builds the `migrate_account` instructions that bring every outdated account
+ * to the current layouts after a program upgrade, in the order they must run:
+ * the Config first, since most instructions load it, then every Market, since
+ * the accounts tied to a market are only used through it, then everything else.
+ * Up-to-date and closed accounts are skipped.
+ *
+ * Markets created before ids were hashed need their raw id, keyed by address.
+ */
+export async function buildMigrationSequenceIxs(
+  programAccounts: ProgramAccount[],
+  payer: TransactionSigner,
+  legacyMarketIds: Map<Address, string> = new Map(),
+): Promise<Instruction[]> {
+  const rank = (discriminator: number) =>
+    discriminator === constants.CONFIG_ACCOUNT_DISCRIMINATOR
+      ? 0
+      : discriminator === constants.MARKET_ACCOUNT_DISCRIMINATOR
+        ? 1
+        : 2;
+
+  const outdated = programAccounts
+    .map(({ address, data }) => ({
+      address,
+      length: data.length,
+      discriminator: LEGACY_ACCOUNT_DISCRIMINATORS[data.length] ?? data[0],
+    }))
+    .filter(
+      ({ length, discriminator }) =>
+        CURRENT_ACCOUNT_LENS[discriminator] !== undefined &&
+        length < CURRENT_ACCOUNT_LENS[discriminator],
+    )
+    .sort((a, b) => rank(a.discriminator) - rank(b.discriminator));
+
+  return Promise.all(
+    outdated.map(({ address }) =>
+      buildMigrateAccountIx({
+        account: address,
+        payer,
+        legacyMarketId: legacyMarketIds.get(address),
+      }),
+    ),
+  );
+}
@@ -0,0 +1,74 @@
+import { AccountRole, Instruction, MessageSigner, TransactionSigner } from "@solana/kit";
+import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
+import { getBriberContributionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+
+export type ReclaimRewardOverflowParams = {
+  id: string;
+  /**
+   * Co-funder reclaiming its share into its own ATA. Defaults to the briber
+   * reclaiming the briber share.
+   */
+  contributor?: TransactionSigner & MessageSigner;
+};
+
+/**
+ * Builds the `reclaim_reward_overflow` instruction, returning to a funder its
+ * share of the rewards no farmer could take under the market's per-farmer
+ * reward cap.
+ *
+ * Accounts (in order):
+ *   claimer (writable signer), market (writable),
+ *   claimer_ata (writable), market_incentive_vault (writable), token_program,
+ *   briber_contribution (writable, co-funders only)
+ *
+ * Data layout: [u8 discriminator=38]  — no extra fields.
+ *
+ * Pre-conditions:
+ *   - The market is settled and every winning position has claimed, or 30 days
+ *     have passed since claims opened.
+ *   - The overflow is split pro rata to the incentives each funder added.
+ */
+export async function buildReclaimRewardOverflowIx(
+  accounts: Accounts,
+  { id, contributor }: ReclaimRewardOverflowParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+  const claimer = contributor ?? accounts.briber;
+
+  const [[claimerAta], marketIncentiveVaultPda] = await Promise.all([
+    findAssociatedTokenPda({
+      mint: constants.WSOL_MINT,
+      owner: claimer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    getMarketVaultPda(marketPda, constants.WSOL_MINT),
+  ]);
+
+  const contributionAccounts = contributor
+    ? [
+        {
+          address: await getBriberContributionPda(marketPda, contributor.address),
+          role: AccountRole.WRITABLE,
+        },
+      ]
+    : [];
+
+  // Layout: [u8 discriminator=38]
+  const ixData = Buffer.from([constants.RECLAIM_REWARD_OVERFLOW_DISCRIMINATOR]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: claimer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+      { address: claimerAta, role: AccountRole.WRITABLE },
+      { address: marketIncentiveVaultPda, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...contributionAccounts,
+    ],
+    data: ixData,
+  };
+}
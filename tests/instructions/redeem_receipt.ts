@@ -0,0 +1,102 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
+} from "@solana-program/token";
+import { getMarketPda, getMarketVaultPda, getReceiptMintPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+interface RedeemReceiptParams {
+  id: string;
+  amount: bigint;
+  /** market.incentive_mint — only paid out once the market is settled. */
+  rewardMint: Address;
+  outcomeMint: Address;
+  holder: TransactionSigner;
+}
+
+/**
+ * Builds the instructions required for the `redeem_receipt` flow:
+ *   1. Create holder's ATAs for outcomeMint and rewardMint if needed (idempotent).
+ *   2. redeem_receipt program instruction.
+ *
+ * Accounts (in order for the program ix):
+ *   holder (writable signer), market (writable), outcome_mint (readonly),
+ *   receipt_mint (writable), reward_mint (readonly), holder_receipt_ata (writable),
+ *   holder_outcome_ata (writable), holder_reward_ata (writable),
+ *   market_outcome_vault (writable), market_reward_vault (writable),
+ *   token_program (readonly)
+ *
+ * Data layout: [u8 discriminator=31, u64 amount LE]
+ *
+ * While the market is open the burned receipts are unstaked. Once it is
+ * settled, winning receipts also pay their share of the incentives.
+ */
+export async function buildRedeemReceiptIxs(params: RedeemReceiptParams): Promise<Instruction[]> {
+  const { id, amount, rewardMint, outcomeMint, holder } = params;
+
+  const marketAddress = await getMarketPda(id);
+  const receiptMint = await getReceiptMintPda(marketAddress, outcomeMint);
+
+  const [
+    [holderReceiptAta],
+    [holderOutcomeAta],
+    [holderRewardAta],
+    marketOutcomeVault,
+    marketRewardVault,
+  ] = await Promise.all([
+    findAssociatedTokenPda({
+      mint: receiptMint,
+      owner: holder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: holder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    findAssociatedTokenPda({
+      mint: rewardMint,
+      owner: holder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    getMarketVaultPda(marketAddress, outcomeMint),
+    getMarketVaultPda(marketAddress, rewardMint),
+  ]);
+
+  const createAtaIxs = await Promise.all(
+    [outcomeMint, rewardMint].map((mint) =>
+      getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer: holder,
+        owner: holder.address,
+        mint,
+      }),
+    ),
+  );
+
+  const ixData = Buffer.alloc(1 + 8);
+  ixData.writeUInt8(constants.REDEEM_RECEIPT_DISCRIMINATOR, 0);
+  ixData.writeBigUInt64LE(amount, 1);
+
+  const redeemIx: Instruction = {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: holder.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketAddress, role: AccountRole.WRITABLE },
+      { address: outcomeMint, role: AccountRole.READONLY },
+      { address: receiptMint, role: AccountRole.WRITABLE },
+      { address: rewardMint, role: AccountRole.READONLY },
+      { address: holderReceiptAta, role: AccountRole.WRITABLE },
+      { address: holderOutcomeAta, role: AccountRole.WRITABLE },
+      { address: holderRewardAta, role: AccountRole.WRITABLE },
+      { address: marketOutcomeVault, role: AccountRole.WRITABLE },
+      { address: marketRewardVault, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+
+  return [...createAtaIxs, redeemIx];
+}
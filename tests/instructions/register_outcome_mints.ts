@@ -0,0 +1,40 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import { getConfigPda, getOutcomeMintsPda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `register_outcome_mints` instruction.
+ *
+ * Accounts (in order):
+ *   authority (writable signer), config, outcome_mints (writable),
+ *   outcome_yes_mint, outcome_no_mint, system_program
+ *
+ * Data layout: [u8 discriminator=39, ...utf8 id]
+ */
+export async function buildRegisterOutcomeMintsIx(
+  client: Client,
+  id: string,
+  yesMint: Address,
+  noMint: Address,
+): Promise<Instruction> {
+  const [configPda, outcomeMintsPda] = await Promise.all([getConfigPda(), getOutcomeMintsPda(id)]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: outcomeMintsPda, role: AccountRole.WRITABLE },
+      { address: yesMint, role: AccountRole.READONLY },
+      { address: noMint, role: AccountRole.READONLY },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: Buffer.concat([
+      Buffer.from([constants.REGISTER_OUTCOME_MINTS_DISCRIMINATOR]),
+      Buffer.from(id, "utf8"),
+    ]),
+  };
+}
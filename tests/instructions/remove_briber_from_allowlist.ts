@@ -0,0 +1,33 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import { getBriberAllowlistPda, getConfigPda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `remove_briber_from_allowlist` instruction. Rent is returned to the authority.
+ *
+ * Accounts (in order):
+ *   authority (writable signer), config, briber_allowlist (writable)
+ *
+ * Data layout: [u8 discriminator=16]
+ */
+export async function buildRemoveBriberFromAllowlistIx(
+  client: Client,
+  briber: Address,
+): Promise<Instruction> {
+  const [configPda, briberAllowlistPda] = await Promise.all([
+    getConfigPda(),
+    getBriberAllowlistPda(briber),
+  ]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: briberAllowlistPda, role: AccountRole.WRITABLE },
+    ],
+    data: Buffer.from([constants.REMOVE_BRIBER_FROM_ALLOWLIST_DISCRIMINATOR]),
+  };
+}
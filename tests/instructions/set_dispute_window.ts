@@ -0,0 +1,37 @@
+import { AccountRole, Instruction } from "@solana/kit";
+
+import { Client } from "../utils/client";
+import { constants } from "../utils/constants";
+import { getConfigPda } from "../utils/pda";
+
+/**
+ * Builds the `set_dispute_window` instruction.
+ *
+ * Accounts (in order):
+ *   authority (readonly signer), config (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=27, i64 dispute_window (LE, seconds, at most a week)]
+ *
+ * Only the authority stored in Config can call this instruction. Markets keep
+ * the window in force when they were settled.
+ */
+export async function buildSetDisputeWindowIx(
+  client: Client,
+  disputeWindow: bigint,
+): Promise<Instruction> {
+  const configPda = await getConfigPda();
+
+  const data = Buffer.alloc(9);
+  data.writeUInt8(constants.SET_DISPUTE_WINDOW_DISCRIMINATOR, 0);
+  data.writeBigInt64LE(disputeWindow, 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.READONLY_SIGNER },
+      { address: configPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
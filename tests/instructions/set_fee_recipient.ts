@@ -0,0 +1,35 @@
+import { AccountRole, Address, Instruction, getAddressEncoder } from "@solana/kit";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+
+/**
+ * Builds the `set_fee_recipient` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), market (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=19, [u8; 32] fee_recipient]
+ */
+export async function buildSetFeeRecipientIx(
+  accounts: Accounts,
+  id: string,
+  feeRecipient: Address,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  const data = Buffer.alloc(33);
+  data.writeUInt8(constants.SET_FEE_RECIPIENT_DISCRIMINATOR, 0);
+  data.set(getAddressEncoder().encode(feeRecipient), 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -0,0 +1,42 @@
+import { AccountRole, Instruction } from "@solana/kit";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+
+export type SetImbalanceBoostParams = {
+  id: string;
+  /** Extra weight for a stake on an empty side, at most 10_000 (2x). 0 disables it. */
+  imbalanceBoostBps: number;
+};
+
+/**
+ * Builds the `set_imbalance_boost` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), market (writable)
+ *
+ * Data layout: [u8 discriminator=37, u16 imbalance_boost_bps (LE)]
+ *
+ * The boost can only be set while nothing is staked, on a market with a staking fee
+ * or an unstake cooldown.
+ */
+export async function buildSetImbalanceBoostIx(
+  accounts: Accounts,
+  { id, imbalanceBoostBps }: SetImbalanceBoostParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  const data = Buffer.alloc(1 + 2);
+  data.writeUInt8(constants.SET_IMBALANCE_BOOST_DISCRIMINATOR, 0);
+  data.writeUInt16LE(imbalanceBoostBps, 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -0,0 +1,47 @@
+import { AccountRole, Instruction } from "@solana/kit";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+import { LockupTier } from "../utils/fetch/market";
+
+export type SetLockupTiersParams = {
+  id: string;
+  /** At most 4 tiers; an empty list removes them. */
+  tiers: LockupTier[];
+};
+
+/**
+ * Builds the `set_lockup_tiers` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), market (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=36, N × (i64 duration (LE), u16 multiplier_bps (LE))]
+ *
+ * Durations are in seconds, up to a year, and multipliers range from 10_000 (1x)
+ * to 30_000 (3x). Tiers can only be set while nothing is staked.
+ */
+export async function buildSetLockupTiersIx(
+  accounts: Accounts,
+  { id, tiers }: SetLockupTiersParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  const data = Buffer.alloc(1 + tiers.length * 10);
+  data.writeUInt8(constants.SET_LOCKUP_TIERS_DISCRIMINATOR, 0);
+  tiers.forEach(({ duration, multiplierBps }, i) => {
+    data.writeBigInt64LE(duration, 1 + i * 10);
+    data.writeUInt16LE(multiplierBps, 9 + i * 10);
+  });
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
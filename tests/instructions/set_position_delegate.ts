@@ -0,0 +1,52 @@
+import {
+  AccountRole,
+  Address,
+  Instruction,
+  TransactionSigner,
+  getAddressEncoder,
+} from "@solana/kit";
+import { getFarmerPositionPda, getMarketPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+interface SetPositionDelegateParams {
+  id: string;
+  farmer: TransactionSigner;
+  /** May claim on the farmer's behalf; the default (all zeros) address removes it. */
+  delegate: Address;
+  /** Owner of the payout accounts; the default (all zeros) address pays the farmer. */
+  rewardRecipient: Address;
+}
+
+/**
+ * Builds the `set_position_delegate` instruction.
+ *
+ * Accounts (in order):
+ *   farmer (readonly signer), market (readonly), farmer_position PDA (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=33, [u8; 32] delegate, [u8; 32] reward_recipient]
+ */
+export async function buildSetPositionDelegateIx(
+  params: SetPositionDelegateParams,
+): Promise<Instruction> {
+  const { id, farmer, delegate, rewardRecipient } = params;
+
+  const marketAddress = await getMarketPda(id);
+  const farmerPositionAddress = await getFarmerPositionPda(marketAddress, farmer.address);
+
+  const data = Buffer.alloc(1 + 32 + 32);
+  data.writeUInt8(constants.SET_POSITION_DELEGATE_DISCRIMINATOR, 0);
+  data.set(getAddressEncoder().encode(delegate), 1);
+  data.set(getAddressEncoder().encode(rewardRecipient), 33);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: farmer.address, role: AccountRole.READONLY_SIGNER },
+      { address: marketAddress, role: AccountRole.READONLY },
+      { address: farmerPositionAddress, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -0,0 +1,43 @@
+import { AccountRole, Instruction, getAddressEncoder } from "@solana/kit";
+import { getConfigPda, getRevenueSplitPda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { RevenueRecipient } from "../utils/fetch/revenue_split";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `set_revenue_split` instruction. Creates the revenue split on
+ * first use and replaces every recipient afterwards.
+ *
+ * Accounts (in order):
+ *   authority (writable signer), config, revenue_split (writable), system_program
+ *
+ * Data layout:
+ *   [u8 discriminator=21, ([u8; 32] recipient, u16 weight_bps (LE)) * 1..=5]
+ *   The weights must be non-zero and sum to 10_000.
+ */
+export async function buildSetRevenueSplitIx(
+  client: Client,
+  recipients: RevenueRecipient[],
+): Promise<Instruction> {
+  const [configPda, revenueSplitPda] = await Promise.all([getConfigPda(), getRevenueSplitPda()]);
+
+  const ixData = Buffer.alloc(1 + recipients.length * 34);
+  ixData.writeUInt8(constants.SET_REVENUE_SPLIT_DISCRIMINATOR, 0);
+  recipients.forEach(({ recipient, weightBps }, i) => {
+    ixData.set(getAddressEncoder().encode(recipient), 1 + i * 34);
+    ixData.writeUInt16LE(weightBps, 1 + i * 34 + 32);
+  });
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: revenueSplitPda, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
@@ -0,0 +1,44 @@
+import { AccountRole, Address, Instruction, getAddressEncoder } from "@solana/kit";
+import { getConfigPda, getSettlementCommitteePda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `set_settlement_committee` instruction. Creates the committee on
+ * first use and replaces every member afterwards.
+ *
+ * Accounts (in order):
+ *   authority (writable signer), config, settlement_committee (writable), system_program
+ *
+ * Data layout:
+ *   [u8 discriminator=25, u8 threshold, [u8; 32] member * 1..=7]
+ *   Members must be distinct, and 1 <= threshold <= member count.
+ */
+export async function buildSetSettlementCommitteeIx(
+  client: Client,
+  threshold: number,
+  members: Address[],
+): Promise<Instruction> {
+  const [configPda, settlementCommitteePda] = await Promise.all([
+    getConfigPda(),
+    getSettlementCommitteePda(),
+  ]);
+
+  const ixData = Buffer.alloc(2 + members.length * 32);
+  ixData.writeUInt8(constants.SET_SETTLEMENT_COMMITTEE_DISCRIMINATOR, 0);
+  ixData.writeUInt8(threshold, 1);
+  members.forEach((member, i) => ixData.set(getAddressEncoder().encode(member), 2 + i * 32));
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: settlementCommitteePda, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
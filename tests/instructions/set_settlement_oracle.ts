@@ -0,0 +1,37 @@
+import { AccountRole, Address, Instruction, getAddressEncoder } from "@solana/kit";
+
+import { Client } from "../utils/client";
+import { constants } from "../utils/constants";
+import { getConfigPda } from "../utils/pda";
+
+/**
+ * Builds the `set_settlement_oracle` instruction.
+ *
+ * Accounts (in order):
+ *   authority (readonly signer), config (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=23, [u8; 32] settlement_oracle]
+ *
+ * Only the authority stored in Config can call this instruction. The default
+ * (all zeros) address disables attestation settlement.
+ */
+export async function buildSetSettlementOracleIx(
+  client: Client,
+  settlementOracle: Address,
+): Promise<Instruction> {
+  const configPda = await getConfigPda();
+
+  const data = Buffer.alloc(33);
+  data.writeUInt8(constants.SET_SETTLEMENT_ORACLE_DISCRIMINATOR, 0);
+  data.set(getAddressEncoder().encode(settlementOracle), 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.READONLY_SIGNER },
+      { address: configPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -0,0 +1,45 @@
+import { AccountRole, Instruction } from "@solana/kit";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+
+export type SetUnstakeRulesParams = {
+  id: string;
+  /** Seconds, at most a week. 0 removes the rules. */
+  unstakeCooldown: bigint;
+  /** 0 queues unstakes for the cooldown, otherwise the initial early-exit penalty. */
+  earlyExitPenaltyBps: number;
+};
+
+/**
+ * Builds the `set_unstake_rules` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), market (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=34, i64 unstake_cooldown (LE), u16 early_exit_penalty_bps (LE)]
+ *
+ * Rules can only be set while nothing is staked; afterwards they can only be removed.
+ */
+export async function buildSetUnstakeRulesIx(
+  accounts: Accounts,
+  { id, unstakeCooldown, earlyExitPenaltyBps }: SetUnstakeRulesParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  const data = Buffer.alloc(1 + 8 + 2);
+  data.writeUInt8(constants.SET_UNSTAKE_RULES_DISCRIMINATOR, 0);
+  data.writeBigInt64LE(unstakeCooldown, 1);
+  data.writeUInt16LE(earlyExitPenaltyBps, 9);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
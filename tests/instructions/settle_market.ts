@@ -17,9 +17,11 @@ export type SettleMarketParams = {
  * Accounts (in order):
  *   authority (readonly signer), config (readonly), market (writable)
  *
- * Data layout: [u8 discriminator=5, u8 resolution, ...utf8 id]
+ * Data layout: [u8 discriminator=5, u8 resolution]
  *
- * Only the authority stored in Config can call this instruction.
+ * Only the authority stored in Config can call this instruction. It also
+ * resolves disputed markets, and corrects a resolution until its dispute window
+ * is over.
  */
 export async function buildSettleMarketIx(
   client: Client,
@@ -27,11 +29,8 @@ export async function buildSettleMarketIx(
 ): Promise<Instruction> {
   const [configPda, marketPda] = await Promise.all([getConfigPda(), getMarketPda(id)]);
 
-  // Layout: [u8 discriminator=5, u8 resolution, ...utf8 id]
-  const ixData = Buffer.concat([
-    Buffer.from([constants.SETTLE_MARKET_DISCRIMINATOR, resolution]),
-    Buffer.from(id, "utf8"),
-  ]);
+  // Layout: [u8 discriminator=5, u8 resolution]
+  const ixData = Buffer.from([constants.SETTLE_MARKET_DISCRIMINATOR, resolution]);
 
   return {
     programAddress: constants.PROGRAM_ID,
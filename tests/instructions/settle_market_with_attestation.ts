@@ -0,0 +1,95 @@
+import {
+  AccountRole,
+  Address,
+  Instruction,
+  KeyPairSigner,
+  getAddressEncoder,
+  signBytes,
+} from "@solana/kit";
+import { getConfigPda, getMarketPda } from "../utils/pda";
+
+import { MarketResolution } from "../utils/fetch/market";
+import { constants } from "../utils/constants";
+
+export type SettleMarketWithAttestationParams = {
+  id: string;
+  /** 1 = Yes, 2 = No — use the MarketResolution enum. */
+  resolution: MarketResolution.Yes | MarketResolution.No;
+  /** Unix timestamp at which the oracle observed the resolution. */
+  timestamp: bigint;
+  /** Key signing the attestation, must match Config.settlement_oracle to settle. */
+  oracle: KeyPairSigner;
+  /** Program id in the signed message. Defaults to Reflex; override to test replays. */
+  signedProgramId?: Address;
+};
+
+/**
+ * Builds the ed25519 verification instruction followed by the
+ * `settle_market_with_attestation` instruction. Both must be sent together, in
+ * this order.
+ *
+ * Signed message: ["reflex:settle_market:v1", [u8; 32] program id, u8 resolution,
+ *   i64 timestamp (LE), market id bytes]
+ *
+ * Ed25519 instruction data:
+ *   [u8 num_signatures=1, u8 padding, 7 × u16 offsets, [u8; 32] public_key,
+ *    [u8; 64] signature, message]
+ *   every instruction index is u16::MAX (this instruction)
+ *
+ * Accounts (in order):
+ *   config (readonly), market (writable), instructions sysvar (readonly)
+ *
+ * Data layout: [u8 discriminator=24, u8 resolution, i64 timestamp (LE)]
+ *
+ * Anyone can relay a valid attestation.
+ */
+export async function buildSettleMarketWithAttestationIxs(
+  params: SettleMarketWithAttestationParams,
+): Promise<Instruction[]> {
+  const { id, resolution, timestamp, oracle, signedProgramId = constants.PROGRAM_ID } = params;
+  const [configPda, marketPda] = await Promise.all([getConfigPda(), getMarketPda(id)]);
+
+  const prefixLength = constants.ATTESTATION_DOMAIN.length + 32;
+  const message = Buffer.alloc(prefixLength + 9 + Buffer.byteLength(id));
+  constants.ATTESTATION_DOMAIN.copy(message, 0);
+  message.set(getAddressEncoder().encode(signedProgramId), constants.ATTESTATION_DOMAIN.length);
+  message.writeUInt8(resolution, prefixLength);
+  message.writeBigInt64LE(timestamp, prefixLength + 1);
+  message.write(id, prefixLength + 9);
+
+  const signature = await signBytes(oracle.keyPair.privateKey, message);
+
+  const publicKeyOffset = 16;
+  const signatureOffset = publicKeyOffset + 32;
+  const messageOffset = signatureOffset + 64;
+  const ed25519Data = Buffer.alloc(messageOffset + message.length);
+  ed25519Data.writeUInt8(1, 0); // num_signatures
+  ed25519Data.writeUInt16LE(signatureOffset, 2);
+  ed25519Data.writeUInt16LE(0xffff, 4);
+  ed25519Data.writeUInt16LE(publicKeyOffset, 6);
+  ed25519Data.writeUInt16LE(0xffff, 8);
+  ed25519Data.writeUInt16LE(messageOffset, 10);
+  ed25519Data.writeUInt16LE(message.length, 12);
+  ed25519Data.writeUInt16LE(0xffff, 14);
+  ed25519Data.set(getAddressEncoder().encode(oracle.address), publicKeyOffset);
+  ed25519Data.set(signature, signatureOffset);
+  message.copy(ed25519Data, messageOffset);
+
+  const data = Buffer.alloc(10);
+  data.writeUInt8(constants.SETTLE_MARKET_WITH_ATTESTATION_DISCRIMINATOR, 0);
+  data.writeUInt8(resolution, 1);
+  data.writeBigInt64LE(timestamp, 2);
+
+  return [
+    { programAddress: constants.ED25519_PROGRAM_ID, data: ed25519Data },
+    {
+      programAddress: constants.PROGRAM_ID,
+      accounts: [
+        { address: configPda, role: AccountRole.READONLY },
+        { address: marketPda, role: AccountRole.WRITABLE },
+        { address: constants.INSTRUCTIONS_SYSVAR, role: AccountRole.READONLY },
+      ],
+      data,
+    },
+  ];
+}
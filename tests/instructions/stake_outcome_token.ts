@@ -1,6 +1,11 @@
 import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
 import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
-import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+import {
+  getFarmerPositionPda,
+  getMarketPda,
+  getMarketVaultPda,
+  getReceiptMintPda,
+} from "../utils/pda";
 
 import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
 import { constants } from "../utils/constants";
@@ -10,6 +15,10 @@ interface StakeOutcomeTokenParams {
   amount: bigint;
   outcomeMint: Address;
   farmer: TransactionSigner;
+  /** Mint receipt tokens to the farmer's receipt ATA instead of crediting the position. */
+  withReceipt?: boolean;
+  /** Index into the market's lockup tiers, unlocked if omitted. Not allowed with receipts. */
+  lockupTier?: number;
 }
 
 /**
@@ -18,14 +27,18 @@ interface StakeOutcomeTokenParams {
  * Accounts (in order):
  *   farmer (writable signer), market (writable), farmer_position PDA (writable),
  *   outcome_mint (readonly), farmer_ata (writable), market_outcome_vault (writable),
- *   token_program (readonly)
+ *   token_program (readonly), system_program (readonly),
+ *   [receipt_mint (writable), farmer_receipt_ata (writable)] when `withReceipt`
  *
- * Data layout: [u8 discriminator=7, u64 amount LE, ...utf8 market_id]
+ * Data layout: [u8 discriminator=7, u64 amount LE, (u8 lockup_tier)]
+ *
+ * With receipts the receipt mint (see `create_receipt_mint`) and the farmer's
+ * receipt ATA must already exist.
  */
 export async function buildStakeOutcomeTokenIx(
   params: StakeOutcomeTokenParams,
 ): Promise<Instruction> {
-  const { id, amount, outcomeMint, farmer } = params;
+  const { id, amount, outcomeMint, farmer, withReceipt, lockupTier } = params;
 
   const marketAddress = await getMarketPda(id);
 
@@ -39,12 +52,27 @@ export async function buildStakeOutcomeTokenIx(
     getMarketVaultPda(marketAddress, outcomeMint),
   ]);
 
-  // Data layout: [u8 disc=7, u64 amount LE, ...utf8 market_id]
-  const idBytes = Buffer.from(id, "utf-8");
-  const ixData = Buffer.alloc(1 + 8 + idBytes.length);
+  // Data layout: [u8 disc=7, u64 amount LE, (u8 lockup_tier)]
+  const ixData = Buffer.alloc(1 + 8 + (lockupTier === undefined ? 0 : 1));
   ixData.writeUInt8(constants.STAKE_OUTCOME_TOKEN_DISCRIMINATOR, 0);
   ixData.writeBigUInt64LE(amount, 1);
-  idBytes.copy(ixData, 9);
+  if (lockupTier !== undefined) {
+    ixData.writeUInt8(lockupTier, 9);
+  }
+
+  const receiptAccounts = [];
+  if (withReceipt) {
+    const receiptMint = await getReceiptMintPda(marketAddress, outcomeMint);
+    const [farmerReceiptAta] = await findAssociatedTokenPda({
+      mint: receiptMint,
+      owner: farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    receiptAccounts.push(
+      { address: receiptMint, role: AccountRole.WRITABLE },
+      { address: farmerReceiptAta, role: AccountRole.WRITABLE },
+    );
+  }
 
   return {
     programAddress: constants.PROGRAM_ID,
@@ -57,6 +85,7 @@ export async function buildStakeOutcomeTokenIx(
       { address: marketOutcomeVault, role: AccountRole.WRITABLE },
       { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
       { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      ...receiptAccounts,
     ],
     data: ixData,
   };
@@ -0,0 +1,70 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
+import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+interface StakeOutcomeTokenForParams {
+  id: string;
+  amount: bigint;
+  outcomeMint: Address;
+  /** Pays the outcome tokens and the position rent. */
+  payer: TransactionSigner;
+  /** Owner of the credited position, does not sign. */
+  beneficiary: Address;
+  /** Index into the market's lockup tiers. Rejected, only the farmer may lock its stake. */
+  lockupTier?: number;
+}
+
+/**
+ * Builds the `stake_outcome_token_for` instruction.
+ *
+ * Accounts (in order):
+ *   payer (writable signer), beneficiary (readonly), market (writable),
+ *   farmer_position PDA of the beneficiary (writable), outcome_mint (readonly),
+ *   payer_ata (writable), market_outcome_vault (writable),
+ *   token_program (readonly), system_program (readonly)
+ *
+ * Data layout: [u8 discriminator=32, u64 amount LE, (u8 lockup_tier)]
+ */
+export async function buildStakeOutcomeTokenForIx(
+  params: StakeOutcomeTokenForParams,
+): Promise<Instruction> {
+  const { id, amount, outcomeMint, payer, beneficiary, lockupTier } = params;
+
+  const marketAddress = await getMarketPda(id);
+
+  const [[payerAta], farmerPositionAddress, marketOutcomeVault] = await Promise.all([
+    findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: payer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    getFarmerPositionPda(marketAddress, beneficiary),
+    getMarketVaultPda(marketAddress, outcomeMint),
+  ]);
+
+  const ixData = Buffer.alloc(1 + 8 + (lockupTier === undefined ? 0 : 1));
+  ixData.writeUInt8(constants.STAKE_OUTCOME_TOKEN_FOR_DISCRIMINATOR, 0);
+  ixData.writeBigUInt64LE(amount, 1);
+  if (lockupTier !== undefined) {
+    ixData.writeUInt8(lockupTier, 9);
+  }
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: payer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: beneficiary, role: AccountRole.READONLY },
+      { address: marketAddress, role: AccountRole.WRITABLE },
+      { address: farmerPositionAddress, role: AccountRole.WRITABLE },
+      { address: outcomeMint, role: AccountRole.READONLY },
+      { address: payerAta, role: AccountRole.WRITABLE },
+      { address: marketOutcomeVault, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
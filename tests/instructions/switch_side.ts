@@ -0,0 +1,78 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
+import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+interface SwitchSideParams {
+  id: string;
+  /** Staked tokens moved off the current side. */
+  amount: bigint;
+  /** Opposite outcome tokens supplied by the farmer. */
+  toAmount: bigint;
+  /** Outcome currently staked. */
+  fromMint: Address;
+  /** Opposite outcome. */
+  toMint: Address;
+  farmer: TransactionSigner;
+}
+
+/**
+ * Builds the `switch_side` instruction.
+ *
+ * Accounts (in order):
+ *   farmer (writable signer), market (writable), farmer_position PDA (writable),
+ *   from_mint (readonly), to_mint (readonly), farmer_from_ata (writable),
+ *   farmer_to_ata (writable), market_from_vault (writable), market_to_vault (writable),
+ *   token_program (readonly)
+ *
+ * Data layout: [u8 discriminator=29, u64 amount LE, u64 to_amount LE]
+ *
+ * `amount` staked tokens are returned to the farmer and `to_amount` opposite
+ * tokens are staked, paying the staking fee, weighing and checked against the
+ * stake limits like a new stake.
+ */
+export async function buildSwitchSideIx(params: SwitchSideParams): Promise<Instruction> {
+  const { id, amount, toAmount, fromMint, toMint, farmer } = params;
+
+  const marketAddress = await getMarketPda(id);
+
+  const [[farmerFromAta], [farmerToAta], farmerPositionAddress, marketFromVault, marketToVault] =
+    await Promise.all([
+      findAssociatedTokenPda({
+        mint: fromMint,
+        owner: farmer.address,
+        tokenProgram: TOKEN_PROGRAM_ADDRESS,
+      }),
+      findAssociatedTokenPda({
+        mint: toMint,
+        owner: farmer.address,
+        tokenProgram: TOKEN_PROGRAM_ADDRESS,
+      }),
+      getFarmerPositionPda(marketAddress, farmer.address),
+      getMarketVaultPda(marketAddress, fromMint),
+      getMarketVaultPda(marketAddress, toMint),
+    ]);
+
+  const ixData = Buffer.alloc(1 + 8 + 8);
+  ixData.writeUInt8(constants.SWITCH_SIDE_DISCRIMINATOR, 0);
+  ixData.writeBigUInt64LE(amount, 1);
+  ixData.writeBigUInt64LE(toAmount, 9);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: farmer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketAddress, role: AccountRole.WRITABLE },
+      { address: farmerPositionAddress, role: AccountRole.WRITABLE },
+      { address: fromMint, role: AccountRole.READONLY },
+      { address: toMint, role: AccountRole.READONLY },
+      { address: farmerFromAta, role: AccountRole.WRITABLE },
+      { address: farmerToAta, role: AccountRole.WRITABLE },
+      { address: marketFromVault, role: AccountRole.WRITABLE },
+      { address: marketToVault, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
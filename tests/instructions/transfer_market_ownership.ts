@@ -0,0 +1,42 @@
+import { AccountRole, Address, Instruction, getAddressEncoder } from "@solana/kit";
+
+import { constants } from "../utils/constants";
+import { getMarketPda } from "../utils/pda";
+
+export type TransferMarketOwnershipParams = {
+  id: string;
+  /** Current briber, must sign. */
+  briber: Address;
+  /** Briber that must accept the transfer. The zero address cancels a pending transfer. */
+  newBriber: Address;
+};
+
+/**
+ * Builds the `transfer_market_ownership` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), market (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=17, [u8; 32] new_briber]
+ */
+export async function buildTransferMarketOwnershipIx({
+  id,
+  briber,
+  newBriber,
+}: TransferMarketOwnershipParams): Promise<Instruction> {
+  const marketPda = await getMarketPda(id);
+
+  const data = Buffer.alloc(33);
+  data.writeUInt8(constants.TRANSFER_MARKET_OWNERSHIP_DISCRIMINATOR, 0);
+  data.set(getAddressEncoder().encode(newBriber), 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: briber, role: AccountRole.READONLY_SIGNER },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -19,7 +19,7 @@ export interface UnstakeOutcomeTokenParams {
  *   outcome_mint (readonly), farmer_ata (writable), market_outcome_vault (writable),
  *   token_program (readonly)
  *
- * Data layout: [u8 discriminator=8, u64 amount LE, ...utf8 market_id]
+ * Data layout: [u8 discriminator=8, u64 amount LE]
  *
  * Pre-conditions:
  *   - Market must be Open.
@@ -43,12 +43,10 @@ export async function buildUnstakeOutcomeTokenIx(
     getMarketVaultPda(marketAddress, outcomeMint),
   ]);
 
-  // Data layout: [u8 disc=8, u64 amount LE, ...utf8 market_id]
-  const idBytes = Buffer.from(id, "utf-8");
-  const ixData = Buffer.alloc(1 + 8 + idBytes.length);
+  // Data layout: [u8 disc=8, u64 amount LE]
+  const ixData = Buffer.alloc(1 + 8);
   ixData.writeUInt8(constants.UNSTAKE_OUTCOME_TOKEN_DISCRIMINATOR, 0);
   ixData.writeBigUInt64LE(amount, 1);
-  idBytes.copy(ixData, 9);
 
   return {
     programAddress: constants.PROGRAM_ID,
@@ -1,6 +1,7 @@
 import { AccountRole, Address, Instruction, getAddressEncoder } from "@solana/kit";
 
 import { Client } from "../utils/client";
+import { MarketCreationMode } from "../utils/fetch/config";
 import { constants } from "../utils/constants";
 import { getConfigPda } from "../utils/pda";
 
@@ -8,6 +9,9 @@ export interface UpdateConfigParams {
   newAuthority: Address;
   newFeeBps: number;
   newBriberFeeBps: number;
+  newMinBriberFeeBps: number;
+  newMaxBriberFeeBps: number;
+  newMarketCreationMode: MarketCreationMode;
 }
 
 /**
@@ -16,23 +20,36 @@ export interface UpdateConfigParams {
  * Accounts (in order):
  *   authority (signer, writable), config (writable)
  *
- * Data layout (37 bytes):
+ * Data layout (42 bytes):
  *   [u8  discriminator  ]  offset 0
  *   [u8; 32 new_authority] offset 1
  *   [u16 new_fee_bps LE ]  offset 33
  *   [u16 new_briber_fee_bps LE] offset 35
+ *   [u16 new_min_briber_fee_bps LE] offset 37
+ *   [u16 new_max_briber_fee_bps LE] offset 39
+ *   [u8  new_market_creation_mode] offset 41
  */
 export async function buildUpdateConfigIx(
   client: Client,
   params: UpdateConfigParams,
 ): Promise<Instruction> {
-  const { newAuthority, newFeeBps, newBriberFeeBps } = params;
+  const {
+    newAuthority,
+    newFeeBps,
+    newBriberFeeBps,
+    newMinBriberFeeBps,
+    newMaxBriberFeeBps,
+    newMarketCreationMode,
+  } = params;
 
-  const data = Buffer.alloc(37);
+  const data = Buffer.alloc(42);
   data.writeUInt8(constants.UPDATE_CONFIG_DISCRIMINATOR, 0);
   data.set(getAddressEncoder().encode(newAuthority), 1);
   data.writeUInt16LE(newFeeBps, 33);
   data.writeUInt16LE(newBriberFeeBps, 35);
+  data.writeUInt16LE(newMinBriberFeeBps, 37);
+  data.writeUInt16LE(newMaxBriberFeeBps, 39);
+  data.writeUInt8(newMarketCreationMode, 41);
 
   const configPda = await getConfigPda();
 
@@ -0,0 +1,41 @@
+import { AccountRole, Instruction } from "@solana/kit";
+import { getConfigPda, getMarketPda } from "../utils/pda";
+
+import { Accounts } from "../utils/accounts";
+import { constants } from "../utils/constants";
+
+export type UpdateMarketFeeParams = {
+  id: string;
+  /** New staking fee, must not exceed the current one. */
+  newFeeBps: number;
+};
+
+/**
+ * Builds the `update_market_fee` instruction.
+ *
+ * Accounts (in order):
+ *   briber (readonly signer), config, market (writable)
+ *
+ * Data layout:
+ *   [u8 discriminator=14, u16 new_fee_bps (LE)]
+ */
+export async function buildUpdateMarketFeeIx(
+  accounts: Accounts,
+  { id, newFeeBps }: UpdateMarketFeeParams,
+): Promise<Instruction> {
+  const [configPda, marketPda] = await Promise.all([getConfigPda(), getMarketPda(id)]);
+
+  const data = Buffer.alloc(3);
+  data.writeUInt8(constants.UPDATE_MARKET_FEE_DISCRIMINATOR, 0);
+  data.writeUInt16LE(newFeeBps, 1);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: accounts.briber.address, role: AccountRole.READONLY_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: marketPda, role: AccountRole.WRITABLE },
+    ],
+    data,
+  };
+}
@@ -0,0 +1,36 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import { MarketMetadataParams, encodeMarketMetadataData } from "./create_market_metadata";
+import { getConfigPda, getMarketMetadataPda, getMarketPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `update_market_metadata` instruction. The signer must be the
+ * market's briber or the config authority.
+ *
+ * Accounts (in order):
+ *   signer (readonly signer), config, market, market_metadata (writable)
+ *
+ * Data layout: same as `create_market_metadata`.
+ */
+export async function buildUpdateMarketMetadataIx(
+  signer: Address,
+  params: MarketMetadataParams,
+): Promise<Instruction> {
+  const marketPda = await getMarketPda(params.id);
+  const [configPda, marketMetadataPda] = await Promise.all([
+    getConfigPda(),
+    getMarketMetadataPda(marketPda),
+  ]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: signer, role: AccountRole.READONLY_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: marketPda, role: AccountRole.READONLY },
+      { address: marketMetadataPda, role: AccountRole.WRITABLE },
+    ],
+    data: encodeMarketMetadataData(constants.UPDATE_MARKET_METADATA_DISCRIMINATOR, params),
+  };
+}
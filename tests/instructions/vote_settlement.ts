@@ -0,0 +1,53 @@
+import { AccountRole, Instruction, TransactionSigner } from "@solana/kit";
+import {
+  getConfigPda,
+  getMarketPda,
+  getSettlementCommitteePda,
+  getSettlementVotesPda,
+} from "../utils/pda";
+
+import { MarketResolution } from "../utils/fetch/market";
+import { SYSTEM_PROGRAM_ADDRESS } from "@solana-program/system";
+import { constants } from "../utils/constants";
+
+/**
+ * Builds the `vote_settlement` instruction. The first vote on a market creates
+ * its settlement votes account, paid by the voting oracle.
+ *
+ * Accounts (in order):
+ *   oracle (writable signer), config, settlement_committee, market (writable),
+ *   settlement_votes (writable), system_program
+ *
+ * Data layout: [u8 discriminator=26, u8 resolution]
+ *
+ * The market is resolved once `threshold` committee members voted the same way, and
+ * becomes Disputed, left to the authority's `settle_market`, on a conflicting vote.
+ * Either way the deciding vote closes the settlement votes account, and a vote on a
+ * market settled some other way only closes the votes left on it. Votes of oracles
+ * removed from the committee are dropped.
+ */
+export async function buildVoteSettlementIx(
+  oracle: TransactionSigner,
+  id: string,
+  resolution: MarketResolution.Yes | MarketResolution.No,
+): Promise<Instruction> {
+  const [configPda, settlementCommitteePda, marketPda] = await Promise.all([
+    getConfigPda(),
+    getSettlementCommitteePda(),
+    getMarketPda(id),
+  ]);
+  const settlementVotesPda = await getSettlementVotesPda(marketPda);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: oracle.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: settlementCommitteePda, role: AccountRole.READONLY },
+      { address: marketPda, role: AccountRole.WRITABLE },
+      { address: settlementVotesPda, role: AccountRole.WRITABLE },
+      { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: Buffer.from([constants.VOTE_SETTLEMENT_DISCRIMINATOR, resolution]),
+  };
+}
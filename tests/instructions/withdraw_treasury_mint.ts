@@ -0,0 +1,77 @@
+import { AccountRole, Address, Instruction } from "@solana/kit";
+import {
+  TOKEN_PROGRAM_ADDRESS,
+  findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
+} from "@solana-program/token";
+import { getConfigPda, getTreasuryPda } from "../utils/pda";
+
+import { Client } from "../utils/client";
+import { constants } from "../utils/constants";
+
+export type WithdrawTreasuryMintParams = {
+  /** Treasury mint to withdraw from. */
+  mint: Address;
+  /** Owner of the destination ATA. */
+  recipient: Address;
+  /** Amount to withdraw. Defaults to the whole treasury balance. */
+  amount?: bigint;
+};
+
+/**
+ * Builds the instructions required for the `withdraw_treasury_mint` flow:
+ *   1. Create the recipient's ATA for the mint if it doesn't exist yet.
+ *   2. withdraw_treasury_mint program instruction.
+ *
+ * Accounts (in order for the program ix):
+ *   authority (writable signer), config, mint, treasury,
+ *   destination (token account of `mint`), token_program
+ *
+ * Data layout:
+ *   [u8 discriminator=20]                       — withdraw the whole balance
+ *   [u8 discriminator=20, u64 amount (LE)]      — withdraw `amount`
+ */
+export async function buildWithdrawTreasuryMintIxs(
+  client: Client,
+  { mint, recipient, amount }: WithdrawTreasuryMintParams,
+): Promise<Instruction[]> {
+  const [configPda, treasuryPda, [destination]] = await Promise.all([
+    getConfigPda(),
+    getTreasuryPda(mint),
+    findAssociatedTokenPda({
+      mint,
+      owner: recipient,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+  ]);
+
+  const createDestinationIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+    payer: client.wallet,
+    ata: destination,
+    owner: recipient,
+    mint,
+  });
+
+  // Layout: [u8 discriminator=20, optional u64 amount (LE)]
+  let ixData = Buffer.from([constants.WITHDRAW_TREASURY_MINT_DISCRIMINATOR]);
+  if (amount !== undefined) {
+    const amountData = Buffer.alloc(8);
+    amountData.writeBigUInt64LE(amount, 0);
+    ixData = Buffer.concat([ixData, amountData]);
+  }
+
+  const withdrawIx: Instruction = {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: client.wallet.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: configPda, role: AccountRole.READONLY },
+      { address: mint, role: AccountRole.READONLY },
+      { address: treasuryPda, role: AccountRole.WRITABLE },
+      { address: destination, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+
+  return [createDestinationIx, withdrawIx];
+}
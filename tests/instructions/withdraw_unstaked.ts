@@ -0,0 +1,58 @@
+import { AccountRole, Address, Instruction, TransactionSigner } from "@solana/kit";
+import { TOKEN_PROGRAM_ADDRESS, findAssociatedTokenPda } from "@solana-program/token";
+import { getFarmerPositionPda, getMarketPda, getMarketVaultPda } from "../utils/pda";
+
+import { constants } from "../utils/constants";
+
+export interface WithdrawUnstakedParams {
+  id: string;
+  outcomeMint: Address;
+  farmer: TransactionSigner;
+}
+
+/**
+ * Builds the `withdraw_unstaked` instruction.
+ *
+ * Accounts (in order):
+ *   farmer (writable signer), market (writable), farmer_position PDA (writable),
+ *   outcome_mint (readonly), farmer_ata (writable), market_outcome_vault (writable),
+ *   token_program (readonly)
+ *
+ * Data layout: [u8 discriminator=35]  — no extra fields.
+ *
+ * Pays the withdrawal queued on outcomeMint once the position's
+ * withdrawal_unlock has passed, whatever the market status.
+ */
+export async function buildWithdrawUnstakedIx(
+  params: WithdrawUnstakedParams,
+): Promise<Instruction> {
+  const { id, outcomeMint, farmer } = params;
+
+  const marketAddress = await getMarketPda(id);
+
+  const [[farmerAta], farmerPositionAddress, marketOutcomeVault] = await Promise.all([
+    findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    }),
+    getFarmerPositionPda(marketAddress, farmer.address),
+    getMarketVaultPda(marketAddress, outcomeMint),
+  ]);
+
+  const ixData = Buffer.from([constants.WITHDRAW_UNSTAKED_DISCRIMINATOR]);
+
+  return {
+    programAddress: constants.PROGRAM_ID,
+    accounts: [
+      { address: farmer.address, role: AccountRole.WRITABLE_SIGNER },
+      { address: marketAddress, role: AccountRole.WRITABLE },
+      { address: farmerPositionAddress, role: AccountRole.WRITABLE },
+      { address: outcomeMint, role: AccountRole.READONLY },
+      { address: farmerAta, role: AccountRole.WRITABLE },
+      { address: marketOutcomeVault, role: AccountRole.WRITABLE },
+      { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+    ],
+    data: ixData,
+  };
+}
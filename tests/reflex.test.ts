@@ -7,45 +7,179 @@ import {
 import {
   TOKEN_PROGRAM_ADDRESS,
   findAssociatedTokenPda,
+  getCreateAssociatedTokenIdempotentInstructionAsync,
   getCreateAssociatedTokenInstructionAsync,
   getMintToInstruction,
+  getSyncNativeInstruction,
+  getTransferInstruction,
 } from "@solana-program/token";
 import { fetchFarmerPosition, fetchMaybeFarmerPosition } from "./utils/fetch/farmer_position";
-import { getConfigPda, getFarmerPositionPda, getMarketPda } from "./utils/pda";
+import {
+  findReceiptMintPda,
+  getBriberAllowlistPda,
+  getBriberContributionPda,
+  getConfigPda,
+  getFarmerPositionPda,
+  getMarketMetadataPda,
+  getMarketPda,
+  getOutcomeMintsPda,
+  getReceiptMintPda,
+  getRevenueSplitPda,
+  getSettlementCommitteePda,
+  getSettlementVotesPda,
+  getTreasuryPda,
+} from "./utils/pda";
 
-import { KeyPairSigner } from "@solana/kit";
+import {
+  Address,
+  Instruction,
+  KeyPairSigner,
+  airdropFactory,
+  generateKeyPairSigner,
+  getAddressEncoder,
+  getProgramDerivedAddress,
+  lamports,
+} from "@solana/kit";
 import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ADDRESS, getTransferSolInstruction } from "@solana-program/system";
+import { buildAddBriberToAllowlistIx } from "./instructions/add_briber_to_allowlist";
+import { buildAcceptMarketOwnershipIx } from "./instructions/accept_market_ownership";
 import { buildAddIncentivesIx } from "./instructions/add_incentives";
-import { buildAndSendTransaction } from "./utils/tx";
+import { buildAndSendTransaction, expectTransactionFailure } from "./utils/tx";
 import { buildCancelMarketIx } from "./instructions/cancel_market";
 import { buildClaimFeesIxs } from "./instructions/claim_fees";
+import { buildClaimPenaltiesIxs } from "./instructions/claim_penalties";
 import { buildClaimRewardsIxs } from "./instructions/claim_rewards";
+import { buildClaimRewardsBatchIxs } from "./instructions/claim_rewards_batch";
 import { buildCreateMarketIxs } from "./instructions/create_market";
+import { buildCreateMarketMetadataIx } from "./instructions/create_market_metadata";
+import { buildCreateReceiptMintIx } from "./instructions/create_receipt_mint";
+import { buildDistributeTreasuryIxs } from "./instructions/distribute_treasury";
 import { buildInitializeIx } from "./instructions/initialize";
+import { buildMigrateAccountIx, buildMigrationSequenceIxs } from "./instructions/migrate_account";
+import { buildReclaimRewardOverflowIx } from "./instructions/reclaim_reward_overflow";
+import { buildRedeemReceiptIxs } from "./instructions/redeem_receipt";
+import { buildRegisterOutcomeMintsIx } from "./instructions/register_outcome_mints";
+import { buildRemoveBriberFromAllowlistIx } from "./instructions/remove_briber_from_allowlist";
+import { buildSetDisputeWindowIx } from "./instructions/set_dispute_window";
+import { buildSetFeeRecipientIx } from "./instructions/set_fee_recipient";
+import { buildSetImbalanceBoostIx } from "./instructions/set_imbalance_boost";
+import { buildSetLockupTiersIx } from "./instructions/set_lockup_tiers";
+import { buildSetPositionDelegateIx } from "./instructions/set_position_delegate";
+import { buildSetRevenueSplitIx } from "./instructions/set_revenue_split";
+import { buildSetSettlementCommitteeIx } from "./instructions/set_settlement_committee";
+import { buildSetSettlementOracleIx } from "./instructions/set_settlement_oracle";
+import { buildSetUnstakeRulesIx } from "./instructions/set_unstake_rules";
 import { buildSettleMarketIx } from "./instructions/settle_market";
+import { buildSettleMarketWithAttestationIxs } from "./instructions/settle_market_with_attestation";
 import { buildStakeOutcomeTokenIx } from "./instructions/stake_outcome_token";
+import { buildStakeOutcomeTokenForIx } from "./instructions/stake_outcome_token_for";
+import { buildSwitchSideIx } from "./instructions/switch_side";
 import { buildUnstakeOutcomeTokenIx } from "./instructions/unstake_outcome_token";
+import { buildTransferMarketOwnershipIx } from "./instructions/transfer_market_ownership";
 import { buildUpdateConfigIx } from "./instructions/update_config";
+import { buildUpdateMarketFeeIx } from "./instructions/update_market_fee";
+import { buildUpdateMarketMetadataIx } from "./instructions/update_market_metadata";
+import { buildVoteSettlementIx } from "./instructions/vote_settlement";
+import { getComputeUnitLimit, getComputeUnitsConsumed } from "./utils/compute";
 import { buildWithdrawTreasuryIxs } from "./instructions/withdraw_treasury";
+import { buildWithdrawTreasuryMintIxs } from "./instructions/withdraw_treasury_mint";
+import { buildWithdrawUnstakedIx } from "./instructions/withdraw_unstaked";
 import { constants } from "./utils/constants";
 import { createAccounts } from "./utils/accounts";
+import { ReflexError } from "./utils/errors";
 import { createClient } from "./utils/client";
 import { createMint } from "./utils/mint";
 import { expect } from "chai";
-import { fetchConfig } from "./utils/fetch/config";
+import { MarketCreationMode, fetchConfig } from "./utils/fetch/config";
+import { fetchMaybeBriberAllowlist } from "./utils/fetch/briber_allowlist";
+import { fetchMaybeOutcomeMints } from "./utils/fetch/outcome_mints";
+import {
+  fetchBriberContribution,
+  fetchMaybeBriberContribution,
+} from "./utils/fetch/briber_contribution";
+import { fetchMarketMetadata } from "./utils/fetch/market_metadata";
+import { fetchRevenueSplit } from "./utils/fetch/revenue_split";
+import { fetchSettlementCommittee } from "./utils/fetch/settlement_committee";
+import { setAccount } from "./utils/surfnet";
 
 describe("reflex", () => {
   let client: Awaited<ReturnType<typeof createClient>>;
   let accounts: Awaited<ReturnType<typeof createAccounts>>;
   let yesMint: KeyPairSigner;
   let noMint: KeyPairSigner;
+  let newBriber: KeyPairSigner;
+  let coFunder: KeyPairSigner;
 
   before(async () => {
     client = await createClient();
     accounts = await createAccounts(client);
     [yesMint, noMint] = await Promise.all([createMint(client), createMint(client)]);
+
+    // co-funds markets created by the briber
+    coFunder = await generateKeyPairSigner();
+    await airdropFactory({ rpc: client.rpc, rpcSubscriptions: client.rpcSubscriptions })({
+      recipientAddress: coFunder.address,
+      lamports: lamports(100_000_000_000n),
+      commitment: "confirmed",
+    });
   });
 
+  // wraps SOL into the co-funder's WSOL ATA
+  const wrapCoFunderSol = async (amount: bigint) => {
+    const [coFunderAta] = await findAssociatedTokenPda({
+      mint: constants.WSOL_MINT,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const createAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+      payer: coFunder,
+      ata: coFunderAta,
+      owner: coFunder.address,
+      mint: constants.WSOL_MINT,
+    });
+    const transferIx = getTransferSolInstruction({
+      source: coFunder,
+      destination: coFunderAta,
+      amount,
+    });
+    await buildAndSendTransaction(
+      client,
+      [createAtaIx, transferIx, getSyncNativeInstruction({ account: coFunderAta })],
+      { feePayer: coFunder },
+    );
+    return coFunderAta;
+  };
+
+  // co-funds a market in Authority mode, the config authority co-signs
+  const coFundMarket = async (id: string, amount: bigint) => {
+    await wrapCoFunderSol(amount * 2n); // covers the protocol fee
+    const ix = await buildAddIncentivesIx(accounts, {
+      id,
+      amount,
+      contributor: coFunder,
+      authority: client.wallet.address,
+    });
+    return buildAndSendTransaction(client, [ix], {
+      feePayer: coFunder,
+      additionalSigners: [client.wallet],
+    });
+  };
+
+  // keeps every other config field as is
+  const setMarketCreationMode = async (mode: MarketCreationMode) => {
+    const config = await fetchConfig(client.rpc, await getConfigPda());
+    const ix = await buildUpdateConfigIx(client, {
+      newAuthority: config.authority,
+      newFeeBps: config.feeBps,
+      newBriberFeeBps: config.briberFeeBps,
+      newMinBriberFeeBps: config.minBriberFeeBps,
+      newMaxBriberFeeBps: config.maxBriberFeeBps,
+      newMarketCreationMode: mode,
+    });
+    await buildAndSendTransaction(client, [ix]);
+  };
+
   it("--- initialize ix ---", async () => {
     const feeBps = 500;
     const briberFeeBps = 500;
@@ -57,14 +191,19 @@ describe("reflex", () => {
     const configAddress = await getConfigPda();
     const config = await fetchConfig(client.rpc, configAddress);
 
+    expect(config.discriminator).to.equal(constants.CONFIG_ACCOUNT_DISCRIMINATOR);
     expect(config.authority).to.equal(client.wallet.address);
     expect(config.feeBps).to.equal(feeBps);
     expect(config.briberFeeBps).to.equal(briberFeeBps);
+    expect(config.minBriberFeeBps).to.equal(0);
+    expect(config.maxBriberFeeBps).to.equal(1_000);
   });
 
   it("--- update_config ix ---", async () => {
     const newFeeBps = 300;
     const newBriberFeeBps = 200;
+    const newMinBriberFeeBps = 0;
+    const newMaxBriberFeeBps = 500;
 
     const configAddress = await getConfigPda();
     const configBefore = await fetchConfig(client.rpc, configAddress);
@@ -73,6 +212,9 @@ describe("reflex", () => {
       newAuthority: client.wallet.address,
       newFeeBps,
       newBriberFeeBps,
+      newMinBriberFeeBps,
+      newMaxBriberFeeBps,
+      newMarketCreationMode: MarketCreationMode.Authority,
     });
     const txSig = await buildAndSendTransaction(client, [ix]);
     console.log("update_config tx:", txSig);
@@ -81,6 +223,9 @@ describe("reflex", () => {
     expect(configAfter.authority).to.equal(client.wallet.address);
     expect(configAfter.feeBps).to.equal(newFeeBps);
     expect(configAfter.briberFeeBps).to.equal(newBriberFeeBps);
+    expect(configAfter.minBriberFeeBps).to.equal(newMinBriberFeeBps);
+    expect(configAfter.maxBriberFeeBps).to.equal(newMaxBriberFeeBps);
+    expect(configAfter.marketCreationMode).to.equal(MarketCreationMode.Authority);
     // bump must not change
     expect(configAfter.bump).to.equal(configBefore.bump);
   });
@@ -88,10 +233,40 @@ describe("reflex", () => {
   it("--- create_market ix ---", async () => {
     const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
     const amount = BigInt(10 * LAMPORTS_PER_SOL);
+    const briberFeeBps = 300;
+
+    // the requested fee must fall within the config bounds
+    const tooHighIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount,
+      briberFeeBps: 501,
+      yesMint,
+      noMint,
+    });
+    await expectTransactionFailure(client, tooHighIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    // the protocol fee must not exceed the briber's guard
+    const config = await fetchConfig(client.rpc, await getConfigPda());
+    const guardedIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount,
+      briberFeeBps,
+      maxFeeBps: config.feeBps - 1,
+      yesMint,
+      noMint,
+    });
+    await expectTransactionFailure(client, guardedIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
 
     const ixs = await buildCreateMarketIxs(client, accounts, {
       id,
       amount,
+      briberFeeBps,
       yesMint,
       noMint,
     });
@@ -105,9 +280,231 @@ describe("reflex", () => {
     const marketAddress = await getMarketPda(id);
     const market = await fetchMarket(client.rpc, marketAddress);
 
+    expect(market.discriminator).to.equal(constants.MARKET_ACCOUNT_DISCRIMINATOR);
+    expect(market.id).to.equal(id);
     expect(market.briber).to.equal(accounts.briber.address);
     expect(market.totalIncentiveAmount).to.equal(amount);
     expect(market.incentiveMint).to.equal(constants.WSOL_MINT);
+    expect(market.feeBps).to.equal(briberFeeBps);
+    expect(market.feeRecipient).to.equal(accounts.briber.address);
+
+    expect(market.protocolFeeBps).to.equal(config.feeBps);
+  });
+
+  it("--- update_market_fee ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+    const marketAddress = await getMarketPda(id);
+    const marketBefore = await fetchMarket(client.rpc, marketAddress);
+
+    // the fee can never be raised
+    const raiseIx = await buildUpdateMarketFeeIx(accounts, {
+      id,
+      newFeeBps: marketBefore.feeBps + 1,
+    });
+    await expectTransactionFailure(client, [raiseIx], { feePayer: accounts.briber });
+
+    const newFeeBps = marketBefore.feeBps - 100;
+    const ix = await buildUpdateMarketFeeIx(accounts, { id, newFeeBps });
+    const txSig = await buildAndSendTransaction(client, [ix], {
+      feePayer: accounts.briber,
+    });
+    console.log("update_market_fee tx:", txSig);
+
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.feeBps).to.equal(newFeeBps);
+  });
+
+  it("--- create_market_metadata ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+    const params = {
+      id,
+      title: "Miami at Indiana",
+      category: "NCAAF",
+      expectedResolutionTimestamp: 1768870800n,
+      uri: "https://kalshi.com/markets/kxncaafgame",
+    };
+
+    const ix = await buildCreateMarketMetadataIx(accounts, params);
+    const txSig = await buildAndSendTransaction(client, [ix], {
+      feePayer: accounts.briber,
+    });
+    console.log("create_market_metadata tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    const metadata = await fetchMarketMetadata(
+      client.rpc,
+      await getMarketMetadataPda(marketAddress),
+    );
+
+    expect(metadata.discriminator).to.equal(constants.MARKET_METADATA_ACCOUNT_DISCRIMINATOR);
+    expect(metadata.market).to.equal(marketAddress);
+    expect(metadata.ticker).to.equal(id);
+    expect(metadata.title).to.equal(params.title);
+    expect(metadata.category).to.equal(params.category);
+    expect(metadata.expectedResolutionTimestamp).to.equal(params.expectedResolutionTimestamp);
+    expect(metadata.uri).to.equal(params.uri);
+  });
+
+  it("--- update_market_metadata ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+    const params = {
+      id,
+      title: "Miami at Indiana (CFP Final)",
+      category: "NCAAF",
+      expectedResolutionTimestamp: 1768874400n,
+      uri: "",
+    };
+
+    // the admin can edit metadata as well as the briber
+    const ix = await buildUpdateMarketMetadataIx(client.wallet.address, params);
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("update_market_metadata tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    const metadata = await fetchMarketMetadata(
+      client.rpc,
+      await getMarketMetadataPda(marketAddress),
+    );
+
+    expect(metadata.ticker).to.equal(id);
+    expect(metadata.title).to.equal(params.title);
+    expect(metadata.expectedResolutionTimestamp).to.equal(params.expectedResolutionTimestamp);
+    expect(metadata.uri).to.equal("");
+  });
+
+  it("--- add_briber_to_allowlist ix ---", async () => {
+    const ix = await buildAddBriberToAllowlistIx(client, accounts.briber.address);
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("add_briber_to_allowlist tx:", txSig);
+
+    const briberAllowlist = await fetchMaybeBriberAllowlist(
+      client.rpc,
+      await getBriberAllowlistPda(accounts.briber.address),
+    );
+    expect(briberAllowlist?.discriminator).to.equal(
+      constants.BRIBER_ALLOWLIST_ACCOUNT_DISCRIMINATOR,
+    );
+    expect(briberAllowlist?.briber).to.equal(accounts.briber.address);
+
+    // allowlisted bribers create markets without the authority co-signing
+    await setMarketCreationMode(MarketCreationMode.Allowlist);
+
+    const id = "KXALLOWLIST-26JAN19-BRIBER";
+    const ixs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(1 * LAMPORTS_PER_SOL),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+      creationMode: MarketCreationMode.Allowlist,
+    });
+    await buildAndSendTransaction(client, ixs, { feePayer: accounts.briber });
+
+    const market = await fetchMarket(client.rpc, await getMarketPda(id));
+    expect(market.briber).to.equal(accounts.briber.address);
+  });
+
+  it("--- remove_briber_from_allowlist ix ---", async () => {
+    const ix = await buildRemoveBriberFromAllowlistIx(client, accounts.briber.address);
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("remove_briber_from_allowlist tx:", txSig);
+
+    const briberAllowlist = await fetchMaybeBriberAllowlist(
+      client.rpc,
+      await getBriberAllowlistPda(accounts.briber.address),
+    );
+    expect(briberAllowlist).to.be.null;
+
+    // the briber can no longer create markets in allowlist mode...
+    const id = "KXPERMISSIONLESS-26JAN19-BRIBER";
+    const params = {
+      id,
+      amount: BigInt(1 * LAMPORTS_PER_SOL),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    };
+    const allowlistIxs = await buildCreateMarketIxs(client, accounts, {
+      ...params,
+      creationMode: MarketCreationMode.Allowlist,
+    });
+    await expectTransactionFailure(client, allowlistIxs, { feePayer: accounts.briber });
+
+    // ...but anyone can once creation is permissionless, on the ticker's registered mints
+    await setMarketCreationMode(MarketCreationMode.Permissionless);
+
+    const permissionlessIxs = await buildCreateMarketIxs(client, accounts, {
+      ...params,
+      creationMode: MarketCreationMode.Permissionless,
+    });
+    await expectTransactionFailure(client, permissionlessIxs, { feePayer: accounts.briber });
+
+    const registerIx = await buildRegisterOutcomeMintsIx(
+      client,
+      id,
+      yesMint.address,
+      noMint.address,
+    );
+    await buildAndSendTransaction(client, [registerIx]);
+
+    const outcomeMints = await fetchMaybeOutcomeMints(client.rpc, await getOutcomeMintsPda(id));
+    expect(outcomeMints?.discriminator).to.equal(constants.OUTCOME_MINTS_ACCOUNT_DISCRIMINATOR);
+    expect(outcomeMints?.outcomeYesMint).to.equal(yesMint.address);
+    expect(outcomeMints?.outcomeNoMint).to.equal(noMint.address);
+
+    const swappedIxs = await buildCreateMarketIxs(client, accounts, {
+      ...params,
+      yesMint: noMint,
+      noMint: yesMint,
+      creationMode: MarketCreationMode.Permissionless,
+    });
+    await expectTransactionFailure(client, swappedIxs, { feePayer: accounts.briber });
+
+    await buildAndSendTransaction(client, permissionlessIxs, { feePayer: accounts.briber });
+
+    const market = await fetchMarket(client.rpc, await getMarketPda(id));
+    expect(market.briber).to.equal(accounts.briber.address);
+
+    await setMarketCreationMode(MarketCreationMode.Authority);
+  });
+
+  it("--- transfer_market_ownership ix ---", async () => {
+    const id = "KXALLOWLIST-26JAN19-BRIBER";
+    newBriber = await generateKeyPairSigner();
+
+    const ix = await buildTransferMarketOwnershipIx({
+      id,
+      briber: accounts.briber.address,
+      newBriber: newBriber.address,
+    });
+    const txSig = await buildAndSendTransaction(client, [ix], {
+      feePayer: accounts.briber,
+    });
+    console.log("transfer_market_ownership tx:", txSig);
+
+    // ownership only changes once the new briber accepts
+    const market = await fetchMarket(client.rpc, await getMarketPda(id));
+    expect(market.briber).to.equal(accounts.briber.address);
+    expect(market.pendingBriber).to.equal(newBriber.address);
+  });
+
+  it("--- accept_market_ownership ix ---", async () => {
+    const id = "KXALLOWLIST-26JAN19-BRIBER";
+
+    // only the pending briber can accept
+    const wrongIx = await buildAcceptMarketOwnershipIx(id, accounts.farmer.address);
+    await expectTransactionFailure(client, [wrongIx], { additionalSigners: [accounts.farmer] });
+
+    const ix = await buildAcceptMarketOwnershipIx(id, newBriber.address);
+    const txSig = await buildAndSendTransaction(client, [ix], {
+      additionalSigners: [newBriber],
+    });
+    console.log("accept_market_ownership tx:", txSig);
+
+    const market = await fetchMarket(client.rpc, await getMarketPda(id));
+    expect(market.briber).to.equal(newBriber.address);
+    expect(market.feeRecipient).to.equal(newBriber.address);
+    expect(market.pendingBriber).to.equal(SYSTEM_PROGRAM_ADDRESS);
   });
 
   it("--- add_incentives ix ---", async () => {
@@ -117,25 +514,109 @@ describe("reflex", () => {
     const marketAddress = await getMarketPda(id);
     const marketBefore = await fetchMarket(client.rpc, marketAddress);
 
-    const ix = await buildAddIncentivesIx(accounts, { id, amount });
+    // raise the protocol fee, the market keeps charging its snapshot
+    const config = await fetchConfig(client.rpc, await getConfigPda());
+    const setProtocolFee = async (feeBps: number) => {
+      const updateConfigIx = await buildUpdateConfigIx(client, {
+        newAuthority: config.authority,
+        newFeeBps: feeBps,
+        newBriberFeeBps: config.briberFeeBps,
+        newMinBriberFeeBps: config.minBriberFeeBps,
+        newMaxBriberFeeBps: config.maxBriberFeeBps,
+        newMarketCreationMode: config.marketCreationMode,
+      });
+      await buildAndSendTransaction(client, [updateConfigIx]);
+    };
+    await setProtocolFee(config.feeBps + 100);
+
+    // the snapshot must not exceed the briber's guard
+    const guardedIx = await buildAddIncentivesIx(accounts, {
+      id,
+      amount,
+      maxFeeBps: marketBefore.protocolFeeBps - 1,
+    });
+    await expectTransactionFailure(client, [guardedIx], { feePayer: accounts.briber });
+
+    const treasuryAddress = await getTreasuryPda(constants.WSOL_MINT);
+    const { value: treasuryBefore } = await client.rpc
+      .getTokenAccountBalance(treasuryAddress)
+      .send();
+
+    const ix = await buildAddIncentivesIx(accounts, {
+      id,
+      amount,
+      maxFeeBps: marketBefore.protocolFeeBps,
+    });
 
     const txSig = await buildAndSendTransaction(client, [ix], {
       feePayer: accounts.briber,
     });
     console.log("add_incentives tx:", txSig);
+    await setProtocolFee(config.feeBps);
 
     const market = await fetchMarket(client.rpc, marketAddress);
     expect(market.totalIncentiveAmount).to.equal(marketBefore.totalIncentiveAmount + amount);
+    expect(market.briberContribution).to.equal(marketBefore.briberContribution + amount);
+    expect(market.protocolFeeBps).to.equal(marketBefore.protocolFeeBps);
+
+    const { value: treasuryAfter } = await client.rpc.getTokenAccountBalance(treasuryAddress).send();
+    expect(BigInt(treasuryAfter.amount) - BigInt(treasuryBefore.amount)).to.equal(
+      (amount * BigInt(marketBefore.protocolFeeBps)) / 10_000n,
+    );
+
+    // a co-funder without the authority signature is rejected
+    await wrapCoFunderSol(2n * amount);
+    const unsignedIx = await buildAddIncentivesIx(accounts, {
+      id,
+      amount,
+      contributor: coFunder,
+      creationMode: MarketCreationMode.Permissionless,
+    });
+    await expectTransactionFailure(client, [unsignedIx], { feePayer: coFunder });
+
+    // a new co-funder must add at least 5% of the incentives so far
+    const dustIx = await buildAddIncentivesIx(accounts, {
+      id,
+      amount: market.totalIncentiveAmount / 20n - 1n,
+      contributor: coFunder,
+      authority: client.wallet.address,
+    });
+    await expectTransactionFailure(client, [dustIx], {
+      feePayer: coFunder,
+      additionalSigners: [client.wallet],
+    });
+
+    // co-fund the market, twice to check the contribution accumulates
+    const coFundSig = await coFundMarket(id, amount / 2n);
+    console.log("add_incentives (co-funder) tx:", coFundSig);
+    await coFundMarket(id, amount / 2n);
+
+    const coFundedMarket = await fetchMarket(client.rpc, marketAddress);
+    expect(coFundedMarket.totalIncentiveAmount).to.equal(market.totalIncentiveAmount + amount);
+    expect(coFundedMarket.briberContribution).to.equal(market.briberContribution);
+    expect(coFundedMarket.contributorCount).to.equal(1);
+
+    const contribution = await fetchBriberContribution(
+      client.rpc,
+      await getBriberContributionPda(marketAddress, coFunder.address),
+    );
+    expect(contribution.discriminator).to.equal(
+      constants.BRIBER_CONTRIBUTION_ACCOUNT_DISCRIMINATOR,
+    );
+    expect(contribution.contributor).to.equal(coFunder.address);
+    expect(contribution.amount).to.equal(amount);
   });
 
   it("--- cancel_market ix ---", async () => {
-    // first create a new market since the main one will be settled in a later test
-    const id = "KXNCAAFGAME-26JAN19MIAIND-IND-2";
+    // first create a new market since the main one will be settled in a later test.
+    // its ticker is longer than the 32-byte seed limit, so only its hash is used as seed
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND-2-WITH-A-LONG-SUFFIX";
     const amount = BigInt(1 * LAMPORTS_PER_SOL);
 
     const ixs = await buildCreateMarketIxs(client, accounts, {
       id,
       amount,
+      briberFeeBps: 0,
       yesMint,
       noMint,
     });
@@ -145,22 +626,101 @@ describe("reflex", () => {
       additionalSigners: [client.wallet],
     });
 
-    // cancel_market transfers all incentives back to the briber and closes
-    // market + vault accounts. It requires no pending fees on the market.
-    const ix = await buildCancelMarketIx(accounts, {
+    const marketAddress = await getMarketPda(id);
+    const createdMarket = await fetchMarket(client.rpc, marketAddress);
+    expect(createdMarket.id).to.equal(id);
+
+    // a co-funder adds as much as the briber
+    await coFundMarket(id, amount);
+    const [coFunderAta] = await findAssociatedTokenPda({
+      mint: constants.WSOL_MINT,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const { value: coFunderBalanceBefore } = await client.rpc
+      .getTokenAccountBalance(coFunderAta)
+      .send();
+
+    // every co-funder must be refunded
+    const partialIx = await buildCancelMarketIx(accounts, {
       id,
       yesMint: yesMint.address,
       noMint: noMint.address,
     });
+    await expectTransactionFailure(client, [partialIx], { feePayer: accounts.briber });
 
-    const txSig = await buildAndSendTransaction(client, [ix], {
+    // a zero-fee market collects no fees, yet live stakes still block cancelling it
+    const cancelIx = await buildCancelMarketIx(accounts, {
+      id,
+      yesMint: yesMint.address,
+      noMint: noMint.address,
+      contributors: [coFunder.address],
+    });
+    const stakeAmount = BigInt(1_000_000);
+    const [farmerYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        await getCreateAssociatedTokenIdempotentInstructionAsync({
+          payer: client.wallet,
+          owner: accounts.farmer.address,
+          mint: yesMint.address,
+        }),
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: farmerYesAta,
+          mintAuthority: client.wallet,
+          amount: stakeAmount,
+        }),
+        await buildStakeOutcomeTokenIx({
+          id,
+          amount: stakeAmount,
+          outcomeMint: yesMint.address,
+          farmer: accounts.farmer,
+        }),
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+    await expectTransactionFailure(client, [cancelIx], { feePayer: accounts.briber });
+
+    const unstakeIx = await buildUnstakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, [unstakeIx], { additionalSigners: [accounts.farmer] });
+
+    // cancel_market refunds the incentives pro rata to each funder and closes
+    // market + vault accounts. It requires no stakes nor pending fees on the market.
+    const txSig = await buildAndSendTransaction(client, [cancelIx], {
       feePayer: accounts.briber,
     });
     console.log("cancel_market tx:", txSig);
 
-    const marketAddress = await getMarketPda(id);
+    const unitsConsumed = await getComputeUnitsConsumed(client, txSig);
+    console.log("cancel_market compute units:", unitsConsumed);
+    expect(unitsConsumed).to.be.at.most(getComputeUnitLimit("CANCEL_MARKET"));
+
     const market = await fetchMaybeMarket(client.rpc, marketAddress);
     expect(market).to.be.null;
+
+    const contribution = await fetchMaybeBriberContribution(
+      client.rpc,
+      await getBriberContributionPda(marketAddress, coFunder.address),
+    );
+    expect(contribution).to.be.null;
+
+    const { value: coFunderBalanceAfter } = await client.rpc
+      .getTokenAccountBalance(coFunderAta)
+      .send();
+    expect(BigInt(coFunderBalanceAfter.amount) - BigInt(coFunderBalanceBefore.amount)).to.equal(
+      amount,
+    );
   });
 
   it("--- stake_outcome_token ix ---", async () => {
@@ -210,11 +770,21 @@ describe("reflex", () => {
     });
     console.log("stake_outcome_token tx:", txSig);
 
+    const unitsConsumed = await getComputeUnitsConsumed(client, txSig);
+    console.log("stake_outcome_token compute units:", unitsConsumed);
+    expect(unitsConsumed).to.be.at.most(getComputeUnitLimit("STAKE_OUTCOME_TOKEN"));
+
     const marketAfter = await fetchMarket(client.rpc, marketAddress);
     expect(marketAfter.totalYesStaked > 0n).to.be.true;
     expect(marketAfter.availableYesFees > 0n).to.be.true;
     expect(marketAfter.totalNoStaked).to.equal(0n);
     expect(marketAfter.availableNoFees).to.equal(0n);
+
+    const position = await fetchFarmerPosition(
+      client.rpc,
+      await getFarmerPositionPda(marketAddress, accounts.farmer.address),
+    );
+    expect(position.discriminator).to.equal(constants.FARMER_POSITION_ACCOUNT_DISCRIMINATOR);
   });
 
   it("--- unstake_outcome_token ix ---", async () => {
@@ -245,6 +815,10 @@ describe("reflex", () => {
     });
     console.log("unstake_outcome_token tx:", txSig);
 
+    const unitsConsumed = await getComputeUnitsConsumed(client, txSig);
+    console.log("unstake_outcome_token compute units:", unitsConsumed);
+    expect(unitsConsumed).to.be.at.most(getComputeUnitLimit("UNSTAKE_OUTCOME_TOKEN"));
+
     const [marketAfter, positionAfter] = await Promise.all([
       fetchMarket(client.rpc, marketAddress),
       fetchFarmerPosition(client.rpc, farmerPositionAddress),
@@ -259,83 +833,2274 @@ describe("reflex", () => {
     expect(positionAfter.noStaked).to.equal(0n);
   });
 
-  it("--- settle_market ix ---", async () => {
-    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
-
-    const ix = await buildSettleMarketIx(client, {
+  it("--- switch_side ix ---", async () => {
+    // a fresh market with a staking fee, so the main one keeps its YES stake
+    const id = "KXSWITCH-26JAN19-SIDE";
+    const feeBps = 100;
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
       id,
-      resolution: MarketResolution.Yes,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: feeBps,
+      yesMint,
+      noMint,
     });
-
-    const txSig = await buildAndSendTransaction(client, [ix]);
-    console.log("settle_market tx:", txSig);
-
-    const marketAddress = await getMarketPda(id);
-    const market = await fetchMarket(client.rpc, marketAddress);
-
-    expect(market.resolution).to.equal(MarketResolution.Yes);
-    expect(market.status).to.equal(MarketStatus.Settled);
-  });
-
-  // claim_fees requires a settled market. A fresh market is created here since
-  // the main one was already cancelled. It is settled inline before claiming.
-  it("--- claim_fees ix ---", async () => {
-    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
-
-    // read on-chain state to pick the correct outcome mint.
-    const marketAddress = await getMarketPda(id);
-    const market = await fetchMarket(client.rpc, marketAddress);
-    const outcomeMint =
-      market.resolution === MarketResolution.Yes ? market.outcomeYesMint : market.outcomeNoMint;
-
-    // claim fees.
-    const ixs = await buildClaimFeesIxs(accounts, { id, outcomeMint });
-    const txSig = await buildAndSendTransaction(client, ixs, {
+    await buildAndSendTransaction(client, createIxs, {
       feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
     });
-    console.log("claim_fees tx:", txSig);
 
-    // after claiming, available fees must be 0.
-    const marketAfter = await fetchMarket(client.rpc, marketAddress);
-    expect(marketAfter.availableYesFees).to.equal(0n);
-    expect(marketAfter.availableNoFees).to.equal(0n);
-  });
+    const [[farmerYesAta], [farmerNoAta]] = await Promise.all(
+      [yesMint, noMint].map((mint) =>
+        findAssociatedTokenPda({
+          mint: mint.address,
+          owner: accounts.farmer.address,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const createNoAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+      payer: client.wallet,
+      ata: farmerNoAta,
+      owner: accounts.farmer.address,
+      mint: noMint.address,
+    });
+    const mintTo = (mint: KeyPairSigner, token: typeof farmerYesAta, amount: bigint) =>
+      getMintToInstruction({ mint: mint.address, token, mintAuthority: client.wallet, amount });
 
-  it("--- claim_rewards ix ---", async () => {
-    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+    // stake YES first
+    const stakeAmount = BigInt(10_000_000);
+    const stakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(
+      client,
+      [createNoAtaIx, mintTo(yesMint, farmerYesAta, stakeAmount), stakeIx],
+      { additionalSigners: [accounts.farmer] },
+    );
 
     const marketAddress = await getMarketPda(id);
-    const market = await fetchMarket(client.rpc, marketAddress);
-    // market is resolved Yes — reward_mint is the incentive mint (WSOL),
-    // outcome_mint is the winning side.
-    const outcomeMint = market.outcomeYesMint;
-    const rewardMint = market.incentiveMint;
-
     const farmerPositionAddress = await getFarmerPositionPda(
       marketAddress,
       accounts.farmer.address,
     );
-    const positionBefore = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    const market = await fetchMarket(client.rpc, marketAddress);
+    const yesStaked = (await fetchFarmerPosition(client.rpc, farmerPositionAddress)).yesStaked;
 
-    const ixs = await buildClaimRewardsIxs({
+    // moving the whole stake over to NO stakes the NO tokens as a new stake, fee included
+    const switchIx = await buildSwitchSideIx({
       id,
-      rewardMint,
-      outcomeMint,
+      amount: yesStaked,
+      toAmount: yesStaked,
+      fromMint: yesMint.address,
+      toMint: noMint.address,
       farmer: accounts.farmer,
     });
+    const txSig = await buildAndSendTransaction(
+      client,
+      [mintTo(noMint, farmerNoAta, yesStaked), switchIx],
+      { additionalSigners: [accounts.farmer] },
+    );
+    console.log("switch_side tx:", txSig);
 
-    const txSig = await buildAndSendTransaction(client, ixs, {
-      additionalSigners: [accounts.farmer],
+    const noFee = (yesStaked * BigInt(feeBps)) / 10_000n;
+    const noStaked = yesStaked - noFee;
+    const switched = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(switched.yesStaked).to.equal(0n);
+    expect(switched.noStaked).to.equal(noStaked);
+    expect(switched.noWeight).to.equal(noStaked);
+    const marketSwitched = await fetchMarket(client.rpc, marketAddress);
+    expect(marketSwitched.totalYesStaked).to.equal(market.totalYesStaked - yesStaked);
+    expect(marketSwitched.totalNoStaked).to.equal(market.totalNoStaked + noStaked);
+    expect(marketSwitched.availableNoFees).to.equal(market.availableNoFees + noFee);
+
+    // switching back pays the fee again, on every YES token supplied
+    const extra = BigInt(1_000_000);
+    const switchBackIx = await buildSwitchSideIx({
+      id,
+      amount: noStaked,
+      toAmount: yesStaked + extra,
+      fromMint: noMint.address,
+      toMint: yesMint.address,
+      farmer: accounts.farmer,
     });
-    console.log("claim_rewards tx:", txSig);
+    await buildAndSendTransaction(
+      client,
+      [mintTo(yesMint, farmerYesAta, yesStaked + extra), switchBackIx],
+      { additionalSigners: [accounts.farmer] },
+    );
 
-    // farmer_position must be closed after claiming
-    const positionAfter = await fetchMaybeFarmerPosition(client.rpc, farmerPositionAddress);
-    expect(positionAfter).to.be.null;
+    const fee = ((yesStaked + extra) * BigInt(feeBps)) / 10_000n;
+    const switchedBack = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(switchedBack.yesStaked).to.equal(yesStaked + extra - fee);
+    expect(switchedBack.noStaked).to.equal(0n);
+    const marketBack = await fetchMarket(client.rpc, marketAddress);
+    expect(marketBack.totalYesStaked).to.equal(market.totalYesStaked + extra - fee);
+    expect(marketBack.totalNoStaked).to.equal(market.totalNoStaked);
+    expect(marketBack.availableYesFees).to.equal(market.availableYesFees + fee);
+    expect(marketBack.availableNoFees).to.equal(market.availableNoFees + noFee);
 
-    // the staked amount was non-zero before claiming
-    expect(positionBefore.yesStaked > 0n).to.be.true;
-  });
+    // the moved amount cannot exceed the stake
+    const tooMuchIx = await buildSwitchSideIx({
+      id,
+      amount: yesStaked + extra,
+      toAmount: 1n,
+      fromMint: noMint.address,
+      toMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(client, [tooMuchIx], { additionalSigners: [accounts.farmer] });
+  });
+
+  it("--- stake with receipt / redeem_receipt ix ---", async () => {
+    const id = "KXRECEIPT-26JAN19-MINT";
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const balance = async (ata: Address) =>
+      BigInt((await client.rpc.getTokenAccountBalance(ata).send()).value.amount);
+
+    const marketAddress = await getMarketPda(id);
+    const receiptMint = await getReceiptMintPda(marketAddress, yesMint.address);
+    const [[farmerYesAta], [farmerReceiptAta], [holderReceiptAta], [holderYesAta]] =
+      await Promise.all(
+        [
+          [yesMint.address, accounts.farmer.address],
+          [receiptMint, accounts.farmer.address],
+          [receiptMint, coFunder.address],
+          [yesMint.address, coFunder.address],
+        ].map(([mint, owner]) =>
+          findAssociatedTokenPda({ mint, owner, tokenProgram: TOKEN_PROGRAM_ADDRESS }),
+        ),
+      );
+
+    // anyone can create the receipt mint, the market PDA is its authority
+    const createReceiptMintIx = await buildCreateReceiptMintIx({
+      id,
+      outcomeMint: yesMint.address,
+      payer: client.wallet,
+    });
+    const createReceiptAtaIxs = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        getCreateAssociatedTokenIdempotentInstructionAsync({
+          payer: client.wallet,
+          owner,
+          mint: receiptMint,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, [createReceiptMintIx, ...createReceiptAtaIxs]);
+
+    // the bump is stored so staking and redeeming skip the bump search
+    const [, receiptMintBump] = await findReceiptMintPda(marketAddress, yesMint.address);
+    expect((await fetchMarket(client.rpc, marketAddress)).receiptYesMintBump).to.equal(
+      receiptMintBump,
+    );
+
+    // staking with receipts mints them instead of crediting the farmer position
+    const stakeAmount = BigInt(10_000_000);
+    const mintToIx = getMintToInstruction({
+      mint: yesMint.address,
+      token: farmerYesAta,
+      mintAuthority: client.wallet,
+      amount: stakeAmount,
+    });
+    const stakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+      withReceipt: true,
+    });
+    const txSig = await buildAndSendTransaction(client, [mintToIx, stakeIx], {
+      additionalSigners: [accounts.farmer],
+    });
+    console.log("stake_outcome_token (receipt) tx:", txSig);
+
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.totalYesStaked).to.equal(stakeAmount);
+    expect(await balance(farmerReceiptAta)).to.equal(stakeAmount);
+    const position = await fetchMaybeFarmerPosition(
+      client.rpc,
+      await getFarmerPositionPda(marketAddress, accounts.farmer.address),
+    );
+    expect(position).to.be.null;
+
+    // receipts are plain SPL tokens, the farmer hands them to another wallet
+    const transferIx = getTransferInstruction({
+      source: farmerReceiptAta,
+      destination: holderReceiptAta,
+      authority: accounts.farmer,
+      amount: stakeAmount,
+    });
+    await buildAndSendTransaction(client, [transferIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    // the new holder unstakes half by burning receipts while the market is open
+    const half = stakeAmount / 2n;
+    const unstakeIxs = await buildRedeemReceiptIxs({
+      id,
+      amount: half,
+      rewardMint: market.incentiveMint,
+      outcomeMint: yesMint.address,
+      holder: coFunder,
+    });
+    await buildAndSendTransaction(client, unstakeIxs, { feePayer: coFunder });
+
+    const marketUnstaked = await fetchMarket(client.rpc, marketAddress);
+    expect(marketUnstaked.totalYesStaked).to.equal(stakeAmount - half);
+    expect(await balance(holderYesAta)).to.equal(half);
+
+    // burning more receipts than held fails
+    const tooMuchIxs = await buildRedeemReceiptIxs({
+      id,
+      amount: stakeAmount,
+      rewardMint: market.incentiveMint,
+      outcomeMint: yesMint.address,
+      holder: coFunder,
+    });
+    await expectTransactionFailure(client, tooMuchIxs, { feePayer: coFunder });
+
+    // once settled, the remaining receipts claim the whole incentive pool
+    const settleIx = await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes });
+    await buildAndSendTransaction(client, [settleIx]);
+
+    const [holderRewardAta] = await findAssociatedTokenPda({
+      mint: market.incentiveMint,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const rewardBefore = await balance(holderRewardAta);
+
+    const claimIxs = await buildRedeemReceiptIxs({
+      id,
+      amount: stakeAmount - half,
+      rewardMint: market.incentiveMint,
+      outcomeMint: yesMint.address,
+      holder: coFunder,
+    });
+    await buildAndSendTransaction(client, claimIxs, { feePayer: coFunder });
+
+    expect((await balance(holderRewardAta)) - rewardBefore).to.equal(market.totalIncentiveAmount);
+    expect(await balance(holderYesAta)).to.equal(stakeAmount);
+    expect(await balance(holderReceiptAta)).to.equal(0n);
+  });
+
+  it("--- set_unstake_rules / withdraw_unstaked ix ---", async () => {
+    const queueId = "KXCOOLDOWN-26JAN19-QUEUE";
+    const penaltyId = "KXCOOLDOWN-26JAN19-PENALTY";
+    const cancelId = "KXCOOLDOWN-26JAN19-CANCEL";
+    const stakeAmount = BigInt(10_000_000);
+    const [farmerYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const balance = async () =>
+      BigInt((await client.rpc.getTokenAccountBalance(farmerYesAta).send()).value.amount);
+
+    // one market queues unstakes for 3 seconds, the others charge up to 10% over a day
+    for (const [id, unstakeCooldown, earlyExitPenaltyBps] of [
+      [queueId, 3n, 0],
+      [penaltyId, 86_400n, 1_000],
+      [cancelId, 86_400n, 1_000],
+    ] as const) {
+      const createIxs = await buildCreateMarketIxs(client, accounts, {
+        id,
+        amount: BigInt(LAMPORTS_PER_SOL / 10),
+        briberFeeBps: 0,
+        yesMint,
+        noMint,
+      });
+      const rulesIx = await buildSetUnstakeRulesIx(accounts, {
+        id,
+        unstakeCooldown,
+        earlyExitPenaltyBps,
+      });
+      const txSig = await buildAndSendTransaction(client, [...createIxs, rulesIx], {
+        feePayer: accounts.briber,
+        additionalSigners: [client.wallet],
+      });
+      console.log("set_unstake_rules tx:", txSig);
+
+      const mintToIx = getMintToInstruction({
+        mint: yesMint.address,
+        token: farmerYesAta,
+        mintAuthority: client.wallet,
+        amount: stakeAmount,
+      });
+      const stakeIx = await buildStakeOutcomeTokenIx({
+        id,
+        amount: stakeAmount,
+        outcomeMint: yesMint.address,
+        farmer: accounts.farmer,
+      });
+      await buildAndSendTransaction(client, [mintToIx, stakeIx], {
+        additionalSigners: [accounts.farmer],
+      });
+
+      const market = await fetchMarket(client.rpc, await getMarketPda(id));
+      expect(market.unstakeCooldown).to.equal(unstakeCooldown);
+      expect(market.earlyExitPenaltyBps).to.equal(earlyExitPenaltyBps);
+
+      // rules cannot be tightened once farmers staked
+      const tightenIx = await buildSetUnstakeRulesIx(accounts, {
+        id,
+        unstakeCooldown: unstakeCooldown + 1n,
+        earlyExitPenaltyBps,
+      });
+      await expectTransactionFailure(client, [tightenIx], { feePayer: accounts.briber });
+
+      // nor bypassed by switching sides
+      const switchIx = await buildSwitchSideIx({
+        id,
+        amount: stakeAmount,
+        toAmount: stakeAmount,
+        fromMint: yesMint.address,
+        toMint: noMint.address,
+        farmer: accounts.farmer,
+      });
+      await expectTransactionFailure(client, [switchIx], { additionalSigners: [accounts.farmer] });
+    }
+
+    // queue mode: unstaking leaves the tokens in the vault until the cooldown ends
+    const queueMarketAddress = await getMarketPda(queueId);
+    const queuePositionAddress = await getFarmerPositionPda(
+      queueMarketAddress,
+      accounts.farmer.address,
+    );
+    const balanceBefore = await balance();
+    const queueUnstakeIx = await buildUnstakeOutcomeTokenIx({
+      id: queueId,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, [queueUnstakeIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    const queued = await fetchFarmerPosition(client.rpc, queuePositionAddress);
+    expect(queued.yesStaked).to.equal(0n);
+    expect(queued.pendingYesWithdrawal).to.equal(stakeAmount);
+    const queueMarket = await fetchMarket(client.rpc, queueMarketAddress);
+    expect(queueMarket.totalYesStaked).to.equal(0n);
+    expect(queueMarket.pendingWithdrawals).to.equal(stakeAmount);
+    expect(await balance()).to.equal(balanceBefore);
+
+    // the queued tokens keep the market from being cancelled
+    const cancelIx = await buildCancelMarketIx(accounts, {
+      id: queueId,
+      yesMint: yesMint.address,
+      noMint: noMint.address,
+    });
+    await expectTransactionFailure(client, [cancelIx], { feePayer: accounts.briber });
+
+    const withdrawIx = await buildWithdrawUnstakedIx({
+      id: queueId,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(client, [withdrawIx], { additionalSigners: [accounts.farmer] });
+
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+    const withdrawTxSig = await buildAndSendTransaction(client, [withdrawIx], {
+      additionalSigners: [accounts.farmer],
+    });
+    console.log("withdraw_unstaked tx:", withdrawTxSig);
+
+    expect(await balance()).to.equal(balanceBefore + stakeAmount);
+    expect(await fetchMaybeFarmerPosition(client.rpc, queuePositionAddress)).to.be.null;
+    expect((await fetchMarket(client.rpc, queueMarketAddress)).pendingWithdrawals).to.equal(0n);
+
+    // a gift cannot restart the farmer's penalty decay
+    const penaltyMarketAddress = await getMarketPda(penaltyId);
+    const penaltyPositionAddress = await getFarmerPositionPda(
+      penaltyMarketAddress,
+      accounts.farmer.address,
+    );
+    const { lastStakeTimestamp } = await fetchFarmerPosition(client.rpc, penaltyPositionAddress);
+    const [walletYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: client.wallet.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    await new Promise((resolve) => setTimeout(resolve, 1_000));
+    await buildAndSendTransaction(client, [
+      await getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer: client.wallet,
+        owner: client.wallet.address,
+        mint: yesMint.address,
+      }),
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: walletYesAta,
+        mintAuthority: client.wallet,
+        amount: 1n,
+      }),
+      await buildStakeOutcomeTokenForIx({
+        id: penaltyId,
+        amount: 1n,
+        outcomeMint: yesMint.address,
+        payer: client.wallet,
+        beneficiary: accounts.farmer.address,
+      }),
+    ]);
+    const gifted = await fetchFarmerPosition(client.rpc, penaltyPositionAddress);
+    expect(gifted.yesStaked).to.equal(stakeAmount + 1n);
+    expect(gifted.lastStakeTimestamp).to.equal(lastStakeTimestamp);
+
+    // penalty mode: unstaking right after staking pays almost the full penalty
+    const penaltyBalanceBefore = await balance();
+    const penaltyUnstakeIx = await buildUnstakeOutcomeTokenIx({
+      id: penaltyId,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, [penaltyUnstakeIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    const penaltyMarket = await fetchMarket(client.rpc, penaltyMarketAddress);
+    const maxPenalty = stakeAmount / 10n;
+    expect(penaltyMarket.yesPenalties > (maxPenalty * 99n) / 100n).to.be.true;
+    expect(penaltyMarket.yesPenalties <= maxPenalty).to.be.true;
+    expect(await balance()).to.equal(
+      penaltyBalanceBefore + stakeAmount - penaltyMarket.yesPenalties,
+    );
+
+    // penalties of the losing side go to the fee recipient, once
+    const [briberYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.briber.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const briberYesBalance = async () =>
+      BigInt((await client.rpc.getTokenAccountBalance(briberYesAta).send()).value.amount);
+    await buildAndSendTransaction(client, [
+      await getCreateAssociatedTokenIdempotentInstructionAsync({
+        payer: client.wallet,
+        owner: accounts.briber.address,
+        mint: yesMint.address,
+      }),
+      await buildSettleMarketIx(client, { id: penaltyId, resolution: MarketResolution.No }),
+    ]);
+    const noPenaltiesIxs = await buildClaimPenaltiesIxs(accounts, {
+      id: penaltyId,
+      outcomeMint: noMint.address,
+    });
+    await expectTransactionFailure(client, noPenaltiesIxs, { feePayer: accounts.briber });
+
+    const claimPenaltiesIxs = await buildClaimPenaltiesIxs(accounts, {
+      id: penaltyId,
+      outcomeMint: yesMint.address,
+    });
+    const briberYesBefore = await briberYesBalance();
+    const claimPenaltiesTxSig = await buildAndSendTransaction(client, claimPenaltiesIxs, {
+      feePayer: accounts.briber,
+    });
+    console.log("claim_penalties tx:", claimPenaltiesTxSig);
+
+    expect((await briberYesBalance()) - briberYesBefore).to.equal(penaltyMarket.yesPenalties);
+    expect((await fetchMarket(client.rpc, penaltyMarketAddress)).yesPenalties).to.equal(0n);
+    await expectTransactionFailure(client, claimPenaltiesIxs, { feePayer: accounts.briber });
+
+    // penalties left without stakers do not keep the market from being cancelled
+    const cancelMarketAddress = await getMarketPda(cancelId);
+    await buildAndSendTransaction(
+      client,
+      [
+        await buildUnstakeOutcomeTokenIx({
+          id: cancelId,
+          amount: stakeAmount,
+          outcomeMint: yesMint.address,
+          farmer: accounts.farmer,
+        }),
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+    const { yesPenalties } = await fetchMarket(client.rpc, cancelMarketAddress);
+    expect(yesPenalties > 0n).to.be.true;
+
+    const briberYesBeforeCancel = await briberYesBalance();
+    const cancelPenaltyIx = await buildCancelMarketIx(accounts, {
+      id: cancelId,
+      yesMint: yesMint.address,
+      noMint: noMint.address,
+    });
+    await buildAndSendTransaction(client, [cancelPenaltyIx], { feePayer: accounts.briber });
+
+    expect((await briberYesBalance()) - briberYesBeforeCancel).to.equal(yesPenalties);
+    expect(await fetchMaybeMarket(client.rpc, cancelMarketAddress)).to.be.null;
+  });
+
+  it("--- stake_outcome_token_for ix ---", async () => {
+    const id = "KXSTAKEFOR-26JAN19-BENEFICIARY";
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    // the wallet funds a stake credited to a beneficiary without SOL or tokens
+    const beneficiary = await generateKeyPairSigner();
+    const stakeAmount = BigInt(10_000_000);
+    const [[payerYesAta], [beneficiaryYesAta]] = await Promise.all(
+      [client.wallet.address, beneficiary.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: yesMint.address,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const createAtaIxs = await Promise.all(
+      [client.wallet.address, beneficiary.address].map((owner) =>
+        getCreateAssociatedTokenIdempotentInstructionAsync({
+          payer: client.wallet,
+          owner,
+          mint: yesMint.address,
+        }),
+      ),
+    );
+    const mintToIx = getMintToInstruction({
+      mint: yesMint.address,
+      token: payerYesAta,
+      mintAuthority: client.wallet,
+      amount: stakeAmount,
+    });
+    const stakeIx = await buildStakeOutcomeTokenForIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      payer: client.wallet,
+      beneficiary: beneficiary.address,
+    });
+    const txSig = await buildAndSendTransaction(client, [...createAtaIxs, mintToIx, stakeIx]);
+    console.log("stake_outcome_token_for tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    const beneficiaryPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      beneficiary.address,
+    );
+    const position = await fetchFarmerPosition(client.rpc, beneficiaryPositionAddress);
+    expect(position.yesStaked).to.equal(stakeAmount);
+    // the gift neither locks the position nor starts its penalty decay
+    expect(position.lockedUntil).to.equal(0n);
+    expect(position.lastStakeTimestamp).to.equal(0n);
+    const payerPosition = await fetchMaybeFarmerPosition(
+      client.rpc,
+      await getFarmerPositionPda(marketAddress, client.wallet.address),
+    );
+    expect(payerPosition).to.be.null;
+
+    // the payer cannot unstake the beneficiary's stake
+    const payerUnstakeIx = await buildUnstakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: client.wallet,
+    });
+    await expectTransactionFailure(client, [payerUnstakeIx]);
+
+    // the beneficiary can
+    const unstakeIx = await buildUnstakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: beneficiary,
+    });
+    await buildAndSendTransaction(client, [unstakeIx], { additionalSigners: [beneficiary] });
+
+    const { value: beneficiaryBalance } = await client.rpc
+      .getTokenAccountBalance(beneficiaryYesAta)
+      .send();
+    expect(BigInt(beneficiaryBalance.amount)).to.equal(stakeAmount);
+    expect(await fetchMaybeFarmerPosition(client.rpc, beneficiaryPositionAddress)).to.be.null;
+  });
+
+  it("--- settle_market ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+
+    const ix = await buildSettleMarketIx(client, {
+      id,
+      resolution: MarketResolution.Yes,
+    });
+
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("settle_market tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    const market = await fetchMarket(client.rpc, marketAddress);
+
+    expect(market.resolution).to.equal(MarketResolution.Yes);
+    expect(market.status).to.equal(MarketStatus.Settled);
+  });
+
+  it("--- settle_market_with_attestation ix ---", async () => {
+    const id = "KXORACLE-26JAN19-SETTLE";
+    const ixs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, ixs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const oracle = await generateKeyPairSigner();
+    await buildAndSendTransaction(client, [
+      await buildSetSettlementOracleIx(client, oracle.address),
+    ]);
+    const config = await fetchConfig(client.rpc, await getConfigPda());
+    expect(config.settlementOracle).to.equal(oracle.address);
+
+    const marketAddress = await getMarketPda(id);
+    const { creationTimestamp } = await fetchMarket(client.rpc, marketAddress);
+
+    // an attestation signed by any other key is rejected
+    const forgedIxs = await buildSettleMarketWithAttestationIxs({
+      id,
+      resolution: MarketResolution.No,
+      timestamp: creationTimestamp,
+      oracle: await generateKeyPairSigner(),
+    });
+    await expectTransactionFailure(client, forgedIxs, { feePayer: accounts.farmer });
+
+    // so is one the oracle signed for another program
+    const foreignIxs = await buildSettleMarketWithAttestationIxs({
+      id,
+      resolution: MarketResolution.No,
+      timestamp: creationTimestamp,
+      oracle,
+      signedProgramId: (await generateKeyPairSigner()).address,
+    });
+    await expectTransactionFailure(client, foreignIxs, { feePayer: accounts.farmer });
+
+    // anyone can relay the oracle attestation
+    const attestedIxs = await buildSettleMarketWithAttestationIxs({
+      id,
+      resolution: MarketResolution.No,
+      timestamp: creationTimestamp,
+      oracle,
+    });
+    const txSig = await buildAndSendTransaction(client, attestedIxs, {
+      feePayer: accounts.farmer,
+    });
+    console.log("settle_market_with_attestation tx:", txSig);
+
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.resolution).to.equal(MarketResolution.No);
+    expect(market.status).to.equal(MarketStatus.Settled);
+
+    // an attestation cannot settle a market twice
+    const replayedIxs = await buildSettleMarketWithAttestationIxs({
+      id,
+      resolution: MarketResolution.Yes,
+      timestamp: creationTimestamp,
+      oracle,
+    });
+    await expectTransactionFailure(client, replayedIxs, { feePayer: accounts.farmer });
+  });
+
+  it("--- vote_settlement ix ---", async () => {
+    const settledId = "KXCOMMITTEE-26JAN19-SETTLE";
+    const disputedId = "KXCOMMITTEE-26JAN19-DISPUTE";
+    const leftoverId = "KXCOMMITTEE-26JAN19-LEFTOVER";
+    const prunedId = "KXCOMMITTEE-26JAN19-PRUNED";
+    for (const id of [settledId, disputedId, leftoverId, prunedId]) {
+      const ixs = await buildCreateMarketIxs(client, accounts, {
+        id,
+        amount: BigInt(LAMPORTS_PER_SOL / 10),
+        briberFeeBps: 0,
+        yesMint,
+        noMint,
+      });
+      await buildAndSendTransaction(client, ixs, {
+        feePayer: accounts.briber,
+        additionalSigners: [client.wallet],
+      });
+    }
+
+    // 2-of-3 committee, each oracle pays for its own votes
+    const oracles = await Promise.all([0, 1, 2].map(() => generateKeyPairSigner()));
+    const airdrop = airdropFactory({ rpc: client.rpc, rpcSubscriptions: client.rpcSubscriptions });
+    for (const oracle of oracles) {
+      await airdrop({
+        recipientAddress: oracle.address,
+        lamports: lamports(1_000_000_000n),
+        commitment: "confirmed",
+      });
+    }
+    const [first, second, third] = oracles;
+
+    // the threshold must be reachable
+    await expectTransactionFailure(client, [
+      await buildSetSettlementCommitteeIx(client, 4, oracles.map((o) => o.address)),
+    ]);
+    await buildAndSendTransaction(client, [
+      await buildSetSettlementCommitteeIx(client, 2, oracles.map((o) => o.address)),
+    ]);
+    const committee = await fetchSettlementCommittee(client.rpc, await getSettlementCommitteePda());
+    expect(committee.threshold).to.equal(2);
+    expect(committee.members).to.deep.equal(oracles.map((o) => o.address));
+
+    const vote = async (
+      oracle: KeyPairSigner,
+      id: string,
+      resolution: MarketResolution.Yes | MarketResolution.No,
+    ) =>
+      buildAndSendTransaction(client, [await buildVoteSettlementIx(oracle, id, resolution)], {
+        feePayer: oracle,
+      });
+
+    // non-members cannot vote
+    await expectTransactionFailure(
+      client,
+      [await buildVoteSettlementIx(accounts.farmer, settledId, MarketResolution.Yes)],
+      { feePayer: accounts.farmer },
+    );
+
+    // one vote is not a quorum, and an oracle votes once
+    const settledAddress = await getMarketPda(settledId);
+    await vote(first, settledId, MarketResolution.Yes);
+    expect((await fetchMarket(client.rpc, settledAddress)).status).to.equal(MarketStatus.Open);
+    await expectTransactionFailure(
+      client,
+      [await buildVoteSettlementIx(first, settledId, MarketResolution.Yes)],
+      { feePayer: first },
+    );
+
+    const txSig = await vote(second, settledId, MarketResolution.Yes);
+    console.log("vote_settlement tx:", txSig);
+
+    const settled = await fetchMarket(client.rpc, settledAddress);
+    expect(settled.resolution).to.equal(MarketResolution.Yes);
+    expect(settled.status).to.equal(MarketStatus.Settled);
+
+    // the deciding vote closes the votes account
+    const votesExist = async (market: Address) => {
+      const votes = await getSettlementVotesPda(market);
+      return (await client.rpc.getAccountInfo(votes).send()).value !== null;
+    };
+    expect(await votesExist(settledAddress)).to.equal(false);
+
+    // conflicting votes dispute the market, which closes voting
+    const disputedAddress = await getMarketPda(disputedId);
+    await vote(first, disputedId, MarketResolution.Yes);
+    await vote(second, disputedId, MarketResolution.No);
+    const disputed = await fetchMarket(client.rpc, disputedAddress);
+    expect(disputed.status).to.equal(MarketStatus.Disputed);
+    expect(disputed.resolution).to.equal(MarketResolution.None);
+    expect(await votesExist(disputedAddress)).to.equal(false);
+    await expectTransactionFailure(
+      client,
+      [await buildVoteSettlementIx(third, disputedId, MarketResolution.Yes)],
+      { feePayer: third },
+    );
+
+    // only the authority resolves a dispute
+    await buildAndSendTransaction(client, [
+      await buildSettleMarketIx(client, { id: disputedId, resolution: MarketResolution.No }),
+    ]);
+    const resolved = await fetchMarket(client.rpc, disputedAddress);
+    expect(resolved.resolution).to.equal(MarketResolution.No);
+    expect(resolved.status).to.equal(MarketStatus.Settled);
+
+    // votes left on a market the authority settled are closed by the next vote
+    const leftoverAddress = await getMarketPda(leftoverId);
+    await vote(first, leftoverId, MarketResolution.Yes);
+    await buildAndSendTransaction(client, [
+      await buildSettleMarketIx(client, { id: leftoverId, resolution: MarketResolution.Yes }),
+    ]);
+    expect(await votesExist(leftoverAddress)).to.equal(true);
+    await vote(second, leftoverId, MarketResolution.No);
+    expect(await votesExist(leftoverAddress)).to.equal(false);
+    expect((await fetchMarket(client.rpc, leftoverAddress)).resolution).to.equal(
+      MarketResolution.Yes,
+    );
+
+    // a removed oracle's vote is dropped, so it neither disputes nor counts
+    const prunedAddress = await getMarketPda(prunedId);
+    await vote(first, prunedId, MarketResolution.Yes);
+    await buildAndSendTransaction(client, [
+      await buildSetSettlementCommitteeIx(client, 2, [second.address, third.address]),
+    ]);
+    await vote(second, prunedId, MarketResolution.No);
+    expect((await fetchMarket(client.rpc, prunedAddress)).status).to.equal(MarketStatus.Open);
+    await vote(third, prunedId, MarketResolution.No);
+    const pruned = await fetchMarket(client.rpc, prunedAddress);
+    expect(pruned.status).to.equal(MarketStatus.Settled);
+    expect(pruned.resolution).to.equal(MarketResolution.No);
+  });
+
+  it("--- set_dispute_window ix ---", async () => {
+    const id = "KXDISPUTE-26JAN19-WINDOW";
+    const ixs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, ixs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    // the window is capped at a week
+    await expectTransactionFailure(client, [
+      await buildSetDisputeWindowIx(client, BigInt(7 * 24 * 60 * 60 + 1)),
+    ]);
+    const disputeWindow = 5n;
+    const txSig = await buildAndSendTransaction(client, [
+      await buildSetDisputeWindowIx(client, disputeWindow),
+    ]);
+    console.log("set_dispute_window tx:", txSig);
+    const config = await fetchConfig(client.rpc, await getConfigPda());
+    expect(config.disputeWindow).to.equal(disputeWindow);
+
+    // the farmer stakes on the side that will win
+    const stakeAmount = BigInt(10_000_000);
+    const [farmerAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const mintToIx = getMintToInstruction({
+      mint: yesMint.address,
+      token: farmerAta,
+      mintAuthority: client.wallet,
+      amount: stakeAmount,
+    });
+    const stakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, [mintToIx, stakeIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    // a wrong resolution can be corrected while the window is open
+    const marketAddress = await getMarketPda(id);
+    await buildAndSendTransaction(client, [
+      await buildSettleMarketIx(client, { id, resolution: MarketResolution.No }),
+    ]);
+    const pending = await fetchMarket(client.rpc, marketAddress);
+    expect(pending.status).to.equal(MarketStatus.PendingSettlement);
+    expect(pending.disputeWindow).to.equal(disputeWindow);
+    expect(pending.settledAt > 0n).to.be.true;
+
+    await buildAndSendTransaction(client, [
+      await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes }),
+    ]);
+    const corrected = await fetchMarket(client.rpc, marketAddress);
+    expect(corrected.resolution).to.equal(MarketResolution.Yes);
+    expect(corrected.status).to.equal(MarketStatus.PendingSettlement);
+
+    // claims stay closed until the window is over
+    const claimIxs = await buildClaimRewardsIxs({
+      id,
+      rewardMint: corrected.incentiveMint,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(client, claimIxs, { additionalSigners: [accounts.farmer] });
+
+    await new Promise((resolve) => setTimeout(resolve, Number(disputeWindow + 2n) * 1000));
+    await buildAndSendTransaction(client, claimIxs, { additionalSigners: [accounts.farmer] });
+    const settled = await fetchMarket(client.rpc, marketAddress);
+    expect(settled.status).to.equal(MarketStatus.Settled);
+
+    // the resolution is final once claims open
+    await expectTransactionFailure(client, [
+      await buildSettleMarketIx(client, { id, resolution: MarketResolution.No }),
+    ]);
+
+    // later tests settle markets without a window
+    await buildAndSendTransaction(client, [await buildSetDisputeWindowIx(client, 0n)]);
+  });
+
+  // claim_fees requires a settled market. A fresh market is created here since
+  // the main one was already cancelled. It is settled inline before claiming.
+  it("--- claim_fees ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+
+    // read on-chain state to pick the correct outcome mint.
+    const marketAddress = await getMarketPda(id);
+    const market = await fetchMarket(client.rpc, marketAddress);
+    const outcomeMint =
+      market.resolution === MarketResolution.Yes ? market.outcomeYesMint : market.outcomeNoMint;
+
+    // route the fees to a separate recipient
+    const feeRecipient = (await generateKeyPairSigner()).address;
+    const setFeeRecipientIx = await buildSetFeeRecipientIx(accounts, id, feeRecipient);
+    await buildAndSendTransaction(client, [setFeeRecipientIx], {
+      feePayer: accounts.briber,
+    });
+    const marketWithRecipient = await fetchMarket(client.rpc, marketAddress);
+    expect(marketWithRecipient.feeRecipient).to.equal(feeRecipient);
+
+    // claiming into the briber's own account is rejected
+    const briberIxs = await buildClaimFeesIxs(accounts, { id, outcomeMint });
+    await expectTransactionFailure(client, briberIxs, { feePayer: accounts.briber });
+
+    // claim fees.
+    const ixs = await buildClaimFeesIxs(accounts, { id, outcomeMint, feeRecipient });
+    const txSig = await buildAndSendTransaction(client, ixs, {
+      feePayer: accounts.briber,
+    });
+    console.log("claim_fees tx:", txSig);
+
+    const unitsConsumed = await getComputeUnitsConsumed(client, txSig);
+    console.log("claim_fees compute units:", unitsConsumed);
+    expect(unitsConsumed).to.be.at.most(getComputeUnitLimit("CLAIM_FEES"));
+
+    // the briber share leaves the co-funder share in the vault
+    const totalFees =
+      market.resolution === MarketResolution.Yes ? market.availableYesFees : market.availableNoFees;
+    const coFunded = market.totalIncentiveAmount - market.briberContribution;
+    const coFunderFees = (totalFees * coFunded) / market.totalIncentiveAmount;
+    const briberFees = totalFees - coFunderFees;
+
+    const marketAfter = await fetchMarket(client.rpc, marketAddress);
+    expect(marketAfter.briberFeesClaimed).to.be.true;
+    expect(marketAfter.claimedFees).to.equal(briberFees);
+
+    const [feeRecipientAta] = await findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: feeRecipient,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const { value: balance } = await client.rpc.getTokenAccountBalance(feeRecipientAta).send();
+    expect(BigInt(balance.amount)).to.equal(briberFees);
+
+    // the briber share can only be claimed once
+    await expectTransactionFailure(client, ixs, { feePayer: accounts.briber });
+
+    // the co-funder claims its share, closing its contribution
+    const coFunderIxs = await buildClaimFeesIxs(accounts, {
+      id,
+      outcomeMint,
+      contributor: coFunder,
+    });
+    await buildAndSendTransaction(client, coFunderIxs, { feePayer: coFunder });
+
+    // after every funder claimed, available fees must be 0.
+    const marketClaimed = await fetchMarket(client.rpc, marketAddress);
+    expect(marketClaimed.availableYesFees).to.equal(0n);
+    expect(marketClaimed.availableNoFees).to.equal(0n);
+
+    const [coFunderAta] = await findAssociatedTokenPda({
+      mint: outcomeMint,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const { value: coFunderBalance } = await client.rpc.getTokenAccountBalance(coFunderAta).send();
+    expect(BigInt(coFunderBalance.amount)).to.equal(coFunderFees);
+
+    const contribution = await fetchMaybeBriberContribution(
+      client.rpc,
+      await getBriberContributionPda(marketAddress, coFunder.address),
+    );
+    expect(contribution).to.be.null;
+  });
+
+  it("--- claim_rewards ix ---", async () => {
+    const id = "KXNCAAFGAME-26JAN19MIAIND-IND";
+
+    const marketAddress = await getMarketPda(id);
+    const market = await fetchMarket(client.rpc, marketAddress);
+    // market is resolved Yes — reward_mint is the incentive mint (WSOL),
+    // outcome_mint is the winning side.
+    const outcomeMint = market.outcomeYesMint;
+    const rewardMint = market.incentiveMint;
+
+    const farmerPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      accounts.farmer.address,
+    );
+    const positionBefore = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+
+    const ixs = await buildClaimRewardsIxs({
+      id,
+      rewardMint,
+      outcomeMint,
+      farmer: accounts.farmer,
+    });
+
+    const txSig = await buildAndSendTransaction(client, ixs, {
+      additionalSigners: [accounts.farmer],
+    });
+    console.log("claim_rewards tx:", txSig);
+
+    const unitsConsumed = await getComputeUnitsConsumed(client, txSig);
+    console.log("claim_rewards compute units:", unitsConsumed);
+    expect(unitsConsumed).to.be.at.most(getComputeUnitLimit("CLAIM_REWARDS"));
+
+    // farmer_position must be closed after claiming
+    const positionAfter = await fetchMaybeFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(positionAfter).to.be.null;
+
+    // the staked amount was non-zero before claiming
+    expect(positionBefore.yesStaked > 0n).to.be.true;
+  });
+
+  it("--- claim_rewards_batch ix ---", async () => {
+    const wonId = "KXBATCH-26JAN19-WON";
+    const lostId = "KXBATCH-26JAN19-LOST";
+    const stakeAmount = BigInt(10_000_000);
+    const [farmerYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+
+    // the farmer stakes YES on two markets, which resolve differently
+    for (const [id, resolution] of [
+      [wonId, MarketResolution.Yes],
+      [lostId, MarketResolution.No],
+    ] as const) {
+      const createIxs = await buildCreateMarketIxs(client, accounts, {
+        id,
+        amount: BigInt(LAMPORTS_PER_SOL / 10),
+        briberFeeBps: 0,
+        yesMint,
+        noMint,
+      });
+      await buildAndSendTransaction(client, createIxs, {
+        feePayer: accounts.briber,
+        additionalSigners: [client.wallet],
+      });
+
+      const mintToIx = getMintToInstruction({
+        mint: yesMint.address,
+        token: farmerYesAta,
+        mintAuthority: client.wallet,
+        amount: stakeAmount,
+      });
+      const stakeIx = await buildStakeOutcomeTokenIx({
+        id,
+        amount: stakeAmount,
+        outcomeMint: yesMint.address,
+        farmer: accounts.farmer,
+      });
+      await buildAndSendTransaction(client, [mintToIx, stakeIx], {
+        additionalSigners: [accounts.farmer],
+      });
+
+      await buildAndSendTransaction(client, [await buildSettleMarketIx(client, { id, resolution })]);
+    }
+
+    const { value: balanceBefore } = await client.rpc.getTokenAccountBalance(farmerYesAta).send();
+
+    // the losing position and the repeated one are skipped
+    const position = { rewardMint: constants.WSOL_MINT, outcomeMint: yesMint.address };
+    const ixs = await buildClaimRewardsBatchIxs(accounts.farmer, [
+      { id: wonId, ...position },
+      { id: lostId, ...position },
+      { id: wonId, ...position },
+    ]);
+    const txSig = await buildAndSendTransaction(client, ixs, {
+      additionalSigners: [accounts.farmer],
+    });
+    console.log("claim_rewards_batch tx:", txSig);
+
+    const [wonPosition, lostPosition] = await Promise.all(
+      [wonId, lostId].map(async (id) =>
+        fetchMaybeFarmerPosition(
+          client.rpc,
+          await getFarmerPositionPda(await getMarketPda(id), accounts.farmer.address),
+        ),
+      ),
+    );
+    expect(wonPosition).to.be.null;
+    expect(lostPosition).to.not.be.null;
+
+    // only the winning stake came back
+    const { value: balanceAfter } = await client.rpc.getTokenAccountBalance(farmerYesAta).send();
+    expect(BigInt(balanceAfter.amount) - BigInt(balanceBefore.amount)).to.equal(stakeAmount);
+  });
+
+  it("--- set_position_delegate ix ---", async () => {
+    const id = "KXDELEGATE-26JAN19-CLAIM";
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const stakeAmount = BigInt(10_000_000);
+    const [farmerYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const mintToIx = getMintToInstruction({
+      mint: yesMint.address,
+      token: farmerYesAta,
+      mintAuthority: client.wallet,
+      amount: stakeAmount,
+    });
+    const stakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, [mintToIx, stakeIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    // a bot may claim the position, paying out to a cold wallet
+    const [bot, coldWallet] = await Promise.all([
+      generateKeyPairSigner(),
+      generateKeyPairSigner(),
+    ]);
+    await airdropFactory({ rpc: client.rpc, rpcSubscriptions: client.rpcSubscriptions })({
+      recipientAddress: bot.address,
+      lamports: lamports(1_000_000_000n),
+      commitment: "confirmed",
+    });
+    const delegateIx = await buildSetPositionDelegateIx({
+      id,
+      farmer: accounts.farmer,
+      delegate: bot.address,
+      rewardRecipient: coldWallet.address,
+    });
+    const txSig = await buildAndSendTransaction(client, [delegateIx], {
+      additionalSigners: [accounts.farmer],
+    });
+    console.log("set_position_delegate tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    const farmerPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      accounts.farmer.address,
+    );
+    const position = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(position.delegate).to.equal(bot.address);
+    expect(position.rewardRecipient).to.equal(coldWallet.address);
+
+    // delegates are claim-only
+    const unstakeIx = await buildUnstakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: bot,
+    });
+    await expectTransactionFailure(client, [unstakeIx], { feePayer: bot });
+
+    const settleIx = await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes });
+    await buildAndSendTransaction(client, [settleIx]);
+    const market = await fetchMarket(client.rpc, marketAddress);
+
+    // payouts must go to the recipient, even when the bot signs
+    const toBotIxs = await buildClaimRewardsIxs({
+      id,
+      rewardMint: market.incentiveMint,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+      delegate: bot,
+      rewardRecipient: bot.address,
+    });
+    await expectTransactionFailure(client, toBotIxs, { feePayer: bot });
+
+    const claimIxs = await buildClaimRewardsIxs({
+      id,
+      rewardMint: market.incentiveMint,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+      delegate: bot,
+      rewardRecipient: coldWallet.address,
+    });
+    await buildAndSendTransaction(client, claimIxs, { feePayer: bot });
+
+    const [[coldRewardAta], [coldYesAta]] = await Promise.all(
+      [market.incentiveMint, yesMint.address].map((mint) =>
+        findAssociatedTokenPda({
+          mint,
+          owner: coldWallet.address,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const [{ value: coldReward }, { value: coldYes }] = await Promise.all([
+      client.rpc.getTokenAccountBalance(coldRewardAta).send(),
+      client.rpc.getTokenAccountBalance(coldYesAta).send(),
+    ]);
+    expect(BigInt(coldReward.amount)).to.equal(market.totalIncentiveAmount);
+    expect(BigInt(coldYes.amount)).to.equal(stakeAmount);
+    expect(await fetchMaybeFarmerPosition(client.rpc, farmerPositionAddress)).to.be.null;
+  });
+
+  it("--- set_lockup_tiers ix ---", async () => {
+    const id = "KXLOCKUP-26JAN19-TIERS";
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    // a lock never weighs less than 1x
+    const tooLowIx = await buildSetLockupTiersIx(accounts, {
+      id,
+      tiers: [{ duration: 3n, multiplierBps: 9_000 }],
+    });
+    await expectTransactionFailure(client, [tooLowIx], { feePayer: accounts.briber });
+
+    const tiers = [
+      { duration: 3n, multiplierBps: 20_000 },
+      { duration: 86_400n, multiplierBps: 15_000 },
+    ];
+    const tiersIx = await buildSetLockupTiersIx(accounts, { id, tiers });
+    const txSig = await buildAndSendTransaction(client, [tiersIx], {
+      feePayer: accounts.briber,
+    });
+    console.log("set_lockup_tiers tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    expect((await fetchMarket(client.rpc, marketAddress)).lockupTiers).to.deep.equal(tiers);
+
+    // the farmer locks for 3 seconds at 2x, the co-funder stakes unlocked
+    const stakeAmount = BigInt(10_000_000);
+    const [[farmerYesAta], [coFunderYesAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: yesMint.address,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const lockedStakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+      lockupTier: 0,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: farmerYesAta,
+          mintAuthority: client.wallet,
+          amount: stakeAmount,
+        }),
+        lockedStakeIx,
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+
+    const createCoFunderAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+      payer: client.wallet,
+      owner: coFunder.address,
+      mint: yesMint.address,
+    });
+    const unlockedStakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: stakeAmount,
+      outcomeMint: yesMint.address,
+      farmer: coFunder,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        createCoFunderAtaIx,
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: coFunderYesAta,
+          mintAuthority: client.wallet,
+          amount: stakeAmount,
+        }),
+        unlockedStakeIx,
+      ],
+      { additionalSigners: [coFunder] },
+    );
+
+    const farmerPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      accounts.farmer.address,
+    );
+    const position = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(position.yesStaked).to.equal(stakeAmount);
+    expect(position.yesWeight).to.equal(stakeAmount * 2n);
+    expect(position.lockedUntil > 0n).to.be.true;
+
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.totalYesStaked).to.equal(stakeAmount * 2n);
+    expect(market.totalYesWeight).to.equal(stakeAmount * 3n);
+
+    // tiers are fixed once farmers staked, and unknown tiers are rejected
+    const clearIx = await buildSetLockupTiersIx(accounts, { id, tiers: [] });
+    await expectTransactionFailure(client, [clearIx], { feePayer: accounts.briber });
+    const unknownTierIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: 1n,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+      lockupTier: tiers.length,
+    });
+    await expectTransactionFailure(client, [unknownTierIx], {
+      additionalSigners: [accounts.farmer],
+    });
+
+    // locked stakes can neither be unstaked nor switched
+    const quarter = stakeAmount / 4n;
+    const unstakeIx = await buildUnstakeOutcomeTokenIx({
+      id,
+      amount: quarter,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(client, [unstakeIx], { additionalSigners: [accounts.farmer] });
+    const switchIx = await buildSwitchSideIx({
+      id,
+      amount: quarter,
+      toAmount: quarter,
+      fromMint: yesMint.address,
+      toMint: noMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(client, [switchIx], { additionalSigners: [accounts.farmer] });
+
+    // once the lock expires, unstaking releases a pro rata share of the weight
+    await new Promise((resolve) => setTimeout(resolve, 5_000));
+    await buildAndSendTransaction(client, [unstakeIx], { additionalSigners: [accounts.farmer] });
+
+    const unlocked = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(unlocked.yesWeight).to.equal((stakeAmount - quarter) * 2n);
+
+    // incentives are split by weight: 15M for the farmer against 10M for the co-funder
+    const settleIx = await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes });
+    await buildAndSendTransaction(client, [settleIx]);
+
+    const [[farmerRewardAta], [coFunderRewardAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: market.incentiveMint,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const balance = async (ata: Address) =>
+      BigInt((await client.rpc.getTokenAccountBalance(ata).send()).value.amount);
+    const [farmerRewardBefore, coFunderRewardBefore] = await Promise.all([
+      balance(farmerRewardAta),
+      balance(coFunderRewardAta),
+    ]);
+
+    const [farmerClaimIxs, coFunderClaimIxs] = await Promise.all(
+      [accounts.farmer, coFunder].map((farmer) =>
+        buildClaimRewardsIxs({
+          id,
+          rewardMint: market.incentiveMint,
+          outcomeMint: yesMint.address,
+          farmer,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, farmerClaimIxs, {
+      additionalSigners: [accounts.farmer],
+    });
+    await buildAndSendTransaction(client, coFunderClaimIxs, { feePayer: coFunder });
+
+    const totalWeight = (stakeAmount - quarter) * 2n + stakeAmount;
+    expect((await balance(farmerRewardAta)) - farmerRewardBefore).to.equal(
+      (market.totalIncentiveAmount * (stakeAmount - quarter) * 2n) / totalWeight,
+    );
+    expect((await balance(coFunderRewardAta)) - coFunderRewardBefore).to.equal(
+      (market.totalIncentiveAmount * stakeAmount) / totalWeight,
+    );
+  });
+
+  it("--- lockups hold the locked stake only ---", async () => {
+    const id = "KXLOCKSPLIT-26JAN19-HOLD";
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+    const tiersIx = await buildSetLockupTiersIx(accounts, {
+      id,
+      tiers: [{ duration: 86_400n, multiplierBps: 15_000 }],
+    });
+    await buildAndSendTransaction(client, [tiersIx], { feePayer: accounts.briber });
+
+    // the farmer locks one stake for a day, then adds an unlocked one
+    const [lockedAmount, unlockedAmount] = [BigInt(10_000_000), BigInt(4_000_000)];
+    const [farmerYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const stake = (amount: bigint, lockupTier?: number) =>
+      buildStakeOutcomeTokenIx({
+        id,
+        amount,
+        outcomeMint: yesMint.address,
+        farmer: accounts.farmer,
+        lockupTier,
+      });
+    await buildAndSendTransaction(
+      client,
+      [
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: farmerYesAta,
+          mintAuthority: client.wallet,
+          amount: lockedAmount + unlockedAmount,
+        }),
+        await stake(lockedAmount, 0),
+        await stake(unlockedAmount),
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+
+    const marketAddress = await getMarketPda(id);
+    const farmerPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      accounts.farmer.address,
+    );
+    const position = await fetchFarmerPosition(client.rpc, farmerPositionAddress);
+    expect(position.lockedYesStaked).to.equal(lockedAmount);
+
+    // only the unlocked stake can leave
+    const unstake = (amount: bigint) =>
+      buildUnstakeOutcomeTokenIx({
+        id,
+        amount,
+        outcomeMint: yesMint.address,
+        farmer: accounts.farmer,
+      });
+    await expectTransactionFailure(client, [await unstake(unlockedAmount + 1n)], {
+      additionalSigners: [accounts.farmer],
+    });
+    await buildAndSendTransaction(client, [await unstake(unlockedAmount)], {
+      additionalSigners: [accounts.farmer],
+    });
+    expect((await fetchFarmerPosition(client.rpc, farmerPositionAddress)).yesStaked).to.equal(
+      lockedAmount,
+    );
+
+    // nobody else can lock the farmer's position
+    const [coFunderYesAta] = await findAssociatedTokenPda({
+      mint: yesMint.address,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const giftIx = await buildStakeOutcomeTokenForIx({
+      id,
+      amount: 1n,
+      outcomeMint: yesMint.address,
+      payer: coFunder,
+      beneficiary: accounts.farmer.address,
+      lockupTier: 0,
+    });
+    await expectTransactionFailure(
+      client,
+      [
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: coFunderYesAta,
+          mintAuthority: client.wallet,
+          amount: 1n,
+        }),
+        giftIx,
+      ],
+      { feePayer: coFunder, additionalSigners: [client.wallet] },
+    );
+  });
+
+  it("--- set_imbalance_boost ix ---", async () => {
+    const id = "KXIMBALANCE-26JAN19-BOOK";
+    const freeId = "KXIMBALANCE-26JAN19-FREE";
+    const briberFeeBps = 100;
+    for (const [marketId, feeBps] of [
+      [id, briberFeeBps],
+      [freeId, 0],
+    ] as const) {
+      const createIxs = await buildCreateMarketIxs(client, accounts, {
+        id: marketId,
+        amount: BigInt(LAMPORTS_PER_SOL / 10),
+        briberFeeBps: feeBps,
+        yesMint,
+        noMint,
+      });
+      await buildAndSendTransaction(client, createIxs, {
+        feePayer: accounts.briber,
+        additionalSigners: [client.wallet],
+      });
+    }
+    const sendAsBriber = (ix: Instruction) =>
+      buildAndSendTransaction(client, [ix], { feePayer: accounts.briber });
+    const expectBriberFailure = (ix: Instruction) =>
+      expectTransactionFailure(client, [ix], { feePayer: accounts.briber });
+
+    // without a fee or a cooldown, boosted stakes could be recycled for free
+    const freeBoostIx = await buildSetImbalanceBoostIx(accounts, {
+      id: freeId,
+      imbalanceBoostBps: 10_000,
+    });
+    await expectBriberFailure(freeBoostIx);
+    await sendAsBriber(
+      await buildSetUnstakeRulesIx(accounts, {
+        id: freeId,
+        unstakeCooldown: 60n,
+        earlyExitPenaltyBps: 0,
+      }),
+    );
+    await sendAsBriber(freeBoostIx);
+    await expectBriberFailure(
+      await buildSetUnstakeRulesIx(accounts, {
+        id: freeId,
+        unstakeCooldown: 0n,
+        earlyExitPenaltyBps: 0,
+      }),
+    );
+
+    // the boost is capped at 2x
+    const tooHighIx = await buildSetImbalanceBoostIx(accounts, { id, imbalanceBoostBps: 10_001 });
+    await expectTransactionFailure(client, [tooHighIx], { feePayer: accounts.briber });
+
+    const imbalanceBoostBps = 10_000;
+    const boostIx = await buildSetImbalanceBoostIx(accounts, { id, imbalanceBoostBps });
+    const txSig = await buildAndSendTransaction(client, [boostIx], {
+      feePayer: accounts.briber,
+    });
+    console.log("set_imbalance_boost tx:", txSig);
+
+    const marketAddress = await getMarketPda(id);
+    expect((await fetchMarket(client.rpc, marketAddress)).imbalanceBoostBps).to.equal(
+      imbalanceBoostBps,
+    );
+    await expectBriberFailure(await buildUpdateMarketFeeIx(accounts, { id, newFeeBps: 0 }));
+
+    // the farmer opens the book on YES, the co-funder then takes the thinner NO side
+    const yesAmount = BigInt(30_000_000);
+    const noAmount = BigInt(10_000_000);
+    const [[farmerYesAta], [coFunderNoAta]] = await Promise.all(
+      [
+        [yesMint.address, accounts.farmer.address],
+        [noMint.address, coFunder.address],
+      ].map(([mint, owner]) =>
+        findAssociatedTokenPda({ mint, owner, tokenProgram: TOKEN_PROGRAM_ADDRESS }),
+      ),
+    );
+    const yesStakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: yesAmount,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        getMintToInstruction({
+          mint: yesMint.address,
+          token: farmerYesAta,
+          mintAuthority: client.wallet,
+          amount: yesAmount,
+        }),
+        yesStakeIx,
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+
+    const createCoFunderAtaIx = await getCreateAssociatedTokenIdempotentInstructionAsync({
+      payer: client.wallet,
+      owner: coFunder.address,
+      mint: noMint.address,
+    });
+    const noStakeIx = await buildStakeOutcomeTokenIx({
+      id,
+      amount: noAmount,
+      outcomeMint: noMint.address,
+      farmer: coFunder,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        createCoFunderAtaIx,
+        getMintToInstruction({
+          mint: noMint.address,
+          token: coFunderNoAta,
+          mintAuthority: client.wallet,
+          amount: noAmount,
+        }),
+        noStakeIx,
+      ],
+      { additionalSigners: [coFunder] },
+    );
+
+    // the first stake had nothing to balance and weighs 1x, on its amount after fees
+    const net = (amount: bigint) => amount - (amount * BigInt(briberFeeBps)) / 10_000n;
+    const [yesStaked, noStaked] = [net(yesAmount), net(noAmount)];
+    const [yesPosition, noPosition] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map(async (farmer) =>
+        fetchFarmerPosition(client.rpc, await getFarmerPositionPda(marketAddress, farmer)),
+      ),
+    );
+    expect(yesPosition.yesWeight).to.equal(yesStaked);
+
+    // the NO stake is boosted by the gap measured halfway through it: 5/6 of the YES stake
+    const multiplierBps =
+      10_000n + (BigInt(imbalanceBoostBps) * (yesStaked - noStaked / 2n)) / yesStaked;
+    const noWeight = (noStaked * multiplierBps) / 10_000n;
+    expect(noPosition.noWeight).to.equal(noWeight);
+
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.totalNoStaked).to.equal(noStaked);
+    expect(market.totalNoWeight).to.equal(noWeight);
+
+    // a stake switched to the thinner side is weighed on the curve like a new stake
+    const switchAmount = BigInt(6_000_000);
+    const [farmerNoAta] = await findAssociatedTokenPda({
+      mint: noMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const switchIx = await buildSwitchSideIx({
+      id,
+      amount: switchAmount,
+      toAmount: switchAmount,
+      fromMint: yesMint.address,
+      toMint: noMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(
+      client,
+      [
+        getMintToInstruction({
+          mint: noMint.address,
+          token: farmerNoAta,
+          mintAuthority: client.wallet,
+          amount: switchAmount,
+        }),
+        switchIx,
+      ],
+      { additionalSigners: [accounts.farmer] },
+    );
+    const yesLeft = yesStaked - switchAmount;
+    const switchStaked = net(switchAmount);
+    const switchMultiplierBps =
+      10_000n +
+      (BigInt(imbalanceBoostBps) * (yesLeft - noStaked - switchStaked / 2n)) / yesLeft;
+    const switchedPosition = await fetchFarmerPosition(
+      client.rpc,
+      await getFarmerPositionPda(marketAddress, accounts.farmer.address),
+    );
+    expect(switchedPosition.noWeight).to.equal((switchStaked * switchMultiplierBps) / 10_000n);
+
+    // the curve is fixed once farmers staked
+    const disableIx = await buildSetImbalanceBoostIx(accounts, { id, imbalanceBoostBps: 0 });
+    await expectTransactionFailure(client, [disableIx], { feePayer: accounts.briber });
+  });
+
+  it("--- stake limits ---", async () => {
+    const id = "KXLIMITS-26JAN19-CAPS";
+    const [minStake, maxStakePerFarmer, maxTotalStake] = [
+      BigInt(1_000_000),
+      BigInt(5_000_000),
+      BigInt(8_000_000),
+    ];
+
+    // a cap below the minimum stake is rejected
+    const invalidIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      minStake,
+      maxStakePerFarmer: minStake - 1n,
+      yesMint,
+      noMint,
+    });
+    await expectTransactionFailure(client, invalidIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount: BigInt(LAMPORTS_PER_SOL / 10),
+      briberFeeBps: 0,
+      minStake,
+      maxStakePerFarmer,
+      maxTotalStake,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const marketAddress = await getMarketPda(id);
+    const market = await fetchMarket(client.rpc, marketAddress);
+    expect(market.minStake).to.equal(minStake);
+    expect(market.maxStakePerFarmer).to.equal(maxStakePerFarmer);
+    expect(market.maxTotalStake).to.equal(maxTotalStake);
+
+    const [[farmerYesAta], [coFunderYesAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: yesMint.address,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, [
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: farmerYesAta,
+        mintAuthority: client.wallet,
+        amount: maxStakePerFarmer * 2n,
+      }),
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: coFunderYesAta,
+        mintAuthority: client.wallet,
+        amount: maxStakePerFarmer * 2n,
+      }),
+    ]);
+    const stake = (farmer: KeyPairSigner, amount: bigint) =>
+      buildStakeOutcomeTokenIx({ id, amount, outcomeMint: yesMint.address, farmer });
+
+    // dust stakes are rejected
+    await expectTransactionFailure(
+      client,
+      [await stake(accounts.farmer, minStake - 1n)],
+      { additionalSigners: [accounts.farmer] },
+      ReflexError.StakeBelowMinimum,
+    );
+
+    // each farmer is capped per side
+    await buildAndSendTransaction(client, [await stake(accounts.farmer, 4_000_000n)], {
+      additionalSigners: [accounts.farmer],
+    });
+    await expectTransactionFailure(
+      client,
+      [await stake(accounts.farmer, 2_000_000n)],
+      { additionalSigners: [accounts.farmer] },
+      ReflexError.FarmerStakeCapExceeded,
+    );
+
+    // and so is the side as a whole
+    await expectTransactionFailure(
+      client,
+      [await stake(coFunder, maxStakePerFarmer)],
+      { feePayer: coFunder },
+      ReflexError.MarketStakeCapExceeded,
+    );
+    await buildAndSendTransaction(client, [await stake(coFunder, 4_000_000n)], {
+      feePayer: coFunder,
+    });
+
+    expect((await fetchMarket(client.rpc, marketAddress)).totalYesStaked).to.equal(maxTotalStake);
+
+    // gifts count against the beneficiary's cap, not the payer's
+    const [coFunderNoAta] = await findAssociatedTokenPda({
+      mint: noMint.address,
+      owner: coFunder.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const gift = (amount: bigint) =>
+      buildStakeOutcomeTokenForIx({
+        id,
+        amount,
+        outcomeMint: noMint.address,
+        payer: coFunder,
+        beneficiary: accounts.farmer.address,
+      });
+    await buildAndSendTransaction(
+      client,
+      [
+        getMintToInstruction({
+          mint: noMint.address,
+          token: coFunderNoAta,
+          mintAuthority: client.wallet,
+          amount: maxStakePerFarmer,
+        }),
+        await gift(4_000_000n),
+      ],
+      { feePayer: coFunder, additionalSigners: [client.wallet] },
+    );
+    const farmerPositionAddress = await getFarmerPositionPda(
+      marketAddress,
+      accounts.farmer.address,
+    );
+    expect((await fetchFarmerPosition(client.rpc, farmerPositionAddress)).noStaked).to.equal(
+      4_000_000n,
+    );
+    await expectTransactionFailure(
+      client,
+      [await gift(1_000_000n + 1n)],
+      { feePayer: coFunder },
+      ReflexError.FarmerStakeCapExceeded,
+    );
+
+    // switching sides lands a new stake, held to the same minimum
+    const [farmerNoAta] = await findAssociatedTokenPda({
+      mint: noMint.address,
+      owner: accounts.farmer.address,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const dustSwitchIx = await buildSwitchSideIx({
+      id,
+      amount: minStake,
+      toAmount: minStake - 1n,
+      fromMint: yesMint.address,
+      toMint: noMint.address,
+      farmer: accounts.farmer,
+    });
+    await expectTransactionFailure(
+      client,
+      [
+        getMintToInstruction({
+          mint: noMint.address,
+          token: farmerNoAta,
+          mintAuthority: client.wallet,
+          amount: minStake - 1n,
+        }),
+        dustSwitchIx,
+      ],
+      { additionalSigners: [accounts.farmer] },
+      ReflexError.StakeBelowMinimum,
+    );
+  });
+
+  it("--- reward cap / reclaim_reward_overflow ix ---", async () => {
+    const id = "KXREWARDCAP-26JAN19-POOL";
+    const amount = BigInt(LAMPORTS_PER_SOL / 10);
+    const maxRewardBpsPerFarmer = 4_000;
+
+    // a cap above the whole pool is rejected
+    const invalidIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount,
+      briberFeeBps: 0,
+      maxRewardBpsPerFarmer: 10_001,
+      yesMint,
+      noMint,
+    });
+    await expectTransactionFailure(client, invalidIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount,
+      briberFeeBps: 0,
+      maxRewardBpsPerFarmer,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+
+    const marketAddress = await getMarketPda(id);
+    expect((await fetchMarket(client.rpc, marketAddress)).maxRewardBpsPerFarmer).to.equal(
+      maxRewardBpsPerFarmer,
+    );
+
+    // the co-funder adds a fifth of the incentives and gets a fifth of the overflow back
+    await wrapCoFunderSol(amount);
+    const coFundIx = await buildAddIncentivesIx(accounts, {
+      id,
+      amount: amount / 4n,
+      contributor: coFunder,
+      authority: client.wallet.address,
+    });
+    await buildAndSendTransaction(client, [coFundIx], {
+      feePayer: coFunder,
+      additionalSigners: [client.wallet],
+    });
+    const totalIncentives = amount + amount / 4n;
+
+    // the farmer holds 75% of the winning weight, the co-funder 25%
+    const [farmerAmount, coFunderAmount] = [BigInt(30_000_000), BigInt(10_000_000)];
+    const [[farmerYesAta], [coFunderYesAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: yesMint.address,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, [
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: farmerYesAta,
+        mintAuthority: client.wallet,
+        amount: farmerAmount,
+      }),
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: coFunderYesAta,
+        mintAuthority: client.wallet,
+        amount: coFunderAmount,
+      }),
+    ]);
+    const stake = (farmer: KeyPairSigner, stakeAmount: bigint) =>
+      buildStakeOutcomeTokenIx({ id, amount: stakeAmount, outcomeMint: yesMint.address, farmer });
+    await buildAndSendTransaction(client, [await stake(accounts.farmer, farmerAmount)], {
+      additionalSigners: [accounts.farmer],
+    });
+    await buildAndSendTransaction(client, [await stake(coFunder, coFunderAmount)], {
+      feePayer: coFunder,
+    });
+
+    const settleIx = await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes });
+    await buildAndSendTransaction(client, [settleIx]);
+
+    const [[farmerRewardAta], [coFunderRewardAta], [briberAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address, accounts.briber.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: constants.WSOL_MINT,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const balance = async (ata: Address) =>
+      BigInt((await client.rpc.getTokenAccountBalance(ata).send()).value.amount);
+    const [farmerRewardBefore, coFunderRewardBefore, briberBefore] = await Promise.all([
+      balance(farmerRewardAta),
+      balance(coFunderRewardAta),
+      balance(briberAta),
+    ]);
+
+    // the farmer is capped at 40%, leaving 35% of the pool in the overflow
+    const cap = (totalIncentives * BigInt(maxRewardBpsPerFarmer)) / 10_000n;
+    const [farmerClaimIxs, coFunderClaimIxs] = await Promise.all(
+      [accounts.farmer, coFunder].map((farmer) =>
+        buildClaimRewardsIxs({
+          id,
+          rewardMint: constants.WSOL_MINT,
+          outcomeMint: yesMint.address,
+          farmer,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, farmerClaimIxs, {
+      additionalSigners: [accounts.farmer],
+    });
+    expect((await balance(farmerRewardAta)) - farmerRewardBefore).to.equal(cap);
+    const capped = await fetchMarket(client.rpc, marketAddress);
+    expect(capped.rewardOverflow).to.equal((totalIncentives * 3n) / 4n - cap);
+    expect(capped.claimedWeight).to.equal(farmerAmount);
+
+    // the overflow cannot be reclaimed while winners are still to claim
+    const reclaimIx = await buildReclaimRewardOverflowIx(accounts, { id });
+    const coFunderReclaimIx = await buildReclaimRewardOverflowIx(accounts, {
+      id,
+      contributor: coFunder,
+    });
+    await expectTransactionFailure(client, [reclaimIx], { feePayer: accounts.briber });
+    await expectTransactionFailure(client, [coFunderReclaimIx], { feePayer: coFunder });
+
+    // the co-funder takes the whole overflow on top of its 25%, up to the cap
+    await buildAndSendTransaction(client, coFunderClaimIxs, { feePayer: coFunder });
+    expect((await balance(coFunderRewardAta)) - coFunderRewardBefore).to.equal(cap);
+
+    // the rest goes back to the funders pro rata, once each
+    const returned = totalIncentives - 2n * cap;
+    const coFunderShare = (returned * (amount / 4n)) / totalIncentives;
+    const txSig = await buildAndSendTransaction(client, [reclaimIx], {
+      feePayer: accounts.briber,
+    });
+    console.log("reclaim_reward_overflow tx:", txSig);
+    expect((await balance(briberAta)) - briberBefore).to.equal(returned - coFunderShare);
+    const reclaimed = await fetchMarket(client.rpc, marketAddress);
+    expect(reclaimed.rewardOverflow).to.equal(0n);
+    expect(reclaimed.returnedOverflow).to.equal(returned);
+    await expectTransactionFailure(client, [reclaimIx], { feePayer: accounts.briber });
+
+    const coFunderBefore = await balance(coFunderRewardAta);
+    await buildAndSendTransaction(client, [coFunderReclaimIx], { feePayer: coFunder });
+    expect((await balance(coFunderRewardAta)) - coFunderBefore).to.equal(coFunderShare);
+    await expectTransactionFailure(client, [coFunderReclaimIx], { feePayer: coFunder });
+
+    // the contribution stays open until the co-funder has also claimed its fees
+    const contributionAddress = await getBriberContributionPda(marketAddress, coFunder.address);
+    const contribution = await fetchBriberContribution(client.rpc, contributionAddress);
+    expect(contribution.returnedOverflow).to.equal(coFunderShare);
+    expect(contribution.feesClaimed).to.equal(false);
+    const coFunderFeesIxs = await buildClaimFeesIxs(accounts, {
+      id,
+      outcomeMint: yesMint.address,
+      contributor: coFunder,
+    });
+    await buildAndSendTransaction(client, coFunderFeesIxs, { feePayer: coFunder });
+    expect(await fetchMaybeBriberContribution(client.rpc, contributionAddress)).to.equal(null);
+  });
+
+  it("--- reclaim_reward_overflow after the claim period ---", async () => {
+    const id = "KXREWARDCAP-26JAN19-LATE";
+    const amount = BigInt(LAMPORTS_PER_SOL / 10);
+    const maxRewardBpsPerFarmer = 4_000;
+
+    const createIxs = await buildCreateMarketIxs(client, accounts, {
+      id,
+      amount,
+      briberFeeBps: 0,
+      maxRewardBpsPerFarmer,
+      yesMint,
+      noMint,
+    });
+    await buildAndSendTransaction(client, createIxs, {
+      feePayer: accounts.briber,
+      additionalSigners: [client.wallet],
+    });
+    const marketAddress = await getMarketPda(id);
+
+    // the farmer holds 75% of the winning weight, the co-funder 25%
+    const [farmerAmount, coFunderAmount] = [BigInt(30_000_000), BigInt(10_000_000)];
+    const [[farmerYesAta], [coFunderYesAta]] = await Promise.all(
+      [accounts.farmer.address, coFunder.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: yesMint.address,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    await buildAndSendTransaction(client, [
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: farmerYesAta,
+        mintAuthority: client.wallet,
+        amount: farmerAmount,
+      }),
+      getMintToInstruction({
+        mint: yesMint.address,
+        token: coFunderYesAta,
+        mintAuthority: client.wallet,
+        amount: coFunderAmount,
+      }),
+    ]);
+    const stake = (farmer: KeyPairSigner, stakeAmount: bigint) =>
+      buildStakeOutcomeTokenIx({ id, amount: stakeAmount, outcomeMint: yesMint.address, farmer });
+    await buildAndSendTransaction(client, [await stake(accounts.farmer, farmerAmount)], {
+      additionalSigners: [accounts.farmer],
+    });
+    await buildAndSendTransaction(client, [await stake(coFunder, coFunderAmount)], {
+      feePayer: coFunder,
+    });
+
+    const settleIx = await buildSettleMarketIx(client, { id, resolution: MarketResolution.Yes });
+    await buildAndSendTransaction(client, [settleIx]);
+
+    const [[coFunderRewardAta], [briberAta]] = await Promise.all(
+      [coFunder.address, accounts.briber.address].map((owner) =>
+        findAssociatedTokenPda({
+          mint: constants.WSOL_MINT,
+          owner,
+          tokenProgram: TOKEN_PROGRAM_ADDRESS,
+        }),
+      ),
+    );
+    const balance = async (ata: Address) =>
+      BigInt((await client.rpc.getTokenAccountBalance(ata).send()).value.amount);
+
+    const farmerClaimIxs = await buildClaimRewardsIxs({
+      id,
+      rewardMint: constants.WSOL_MINT,
+      outcomeMint: yesMint.address,
+      farmer: accounts.farmer,
+    });
+    await buildAndSendTransaction(client, farmerClaimIxs, {
+      additionalSigners: [accounts.farmer],
+    });
+    const cap = (amount * BigInt(maxRewardBpsPerFarmer)) / 10_000n;
+    const overflow = (amount * 3n) / 4n - cap;
+    expect((await fetchMarket(client.rpc, marketAddress)).rewardOverflow).to.equal(overflow);
+
+    // the co-funder has not claimed and the claim period is still running
+    const reclaimIx = await buildReclaimRewardOverflowIx(accounts, { id });
+    await expectTransactionFailure(client, [reclaimIx], { feePayer: accounts.briber });
+
+    // move the settlement 31 days back so the claim period is over
+    const { value: marketAccount } = await client.rpc
+      .getAccountInfo(marketAddress, { encoding: "base64" })
+      .send();
+    const marketData = Buffer.from(marketAccount!.data[0], "base64");
+    const settledAt = marketData.readBigInt64LE(368);
+    marketData.writeBigInt64LE(settledAt - 31n * 24n * 60n * 60n, 368);
+    await setAccount(marketAddress, {
+      lamports: marketAccount!.lamports,
+      data: marketData,
+      owner: constants.PROGRAM_ID,
+    });
+
+    // the briber takes the overflow back without waiting for the co-funder
+    const briberBefore = await balance(briberAta);
+    await buildAndSendTransaction(client, [reclaimIx], { feePayer: accounts.briber });
+    expect((await balance(briberAta)) - briberBefore).to.equal(overflow);
+    expect((await fetchMarket(client.rpc, marketAddress)).rewardOverflow).to.equal(0n);
+
+    // a late claimer still gets its own reward, without a share of the overflow
+    const coFunderRewardBefore = await balance(coFunderRewardAta);
+    const coFunderClaimIxs = await buildClaimRewardsIxs({
+      id,
+      rewardMint: constants.WSOL_MINT,
+      outcomeMint: yesMint.address,
+      farmer: coFunder,
+    });
+    await buildAndSendTransaction(client, coFunderClaimIxs, { feePayer: coFunder });
+    expect((await balance(coFunderRewardAta)) - coFunderRewardBefore).to.equal(amount / 4n);
+    await expectTransactionFailure(client, [reclaimIx], { feePayer: accounts.briber });
+  });
+
+  it("--- withdraw_treasury_mint ix ---", async () => {
+    const mint = constants.WSOL_MINT;
+    const recipient = (await generateKeyPairSigner()).address;
+
+    const treasuryAddress = await getTreasuryPda(mint);
+    const { value: treasuryBefore } = await client.rpc
+      .getTokenAccountBalance(treasuryAddress)
+      .send();
+    const balance = BigInt(treasuryBefore.amount);
+    expect(balance > 0n).to.be.true;
+
+    // amounts above the treasury balance are rejected
+    const tooMuchIxs = await buildWithdrawTreasuryMintIxs(client, {
+      mint,
+      recipient,
+      amount: balance + 1n,
+    });
+    await expectTransactionFailure(client, tooMuchIxs);
+
+    // partial withdrawal
+    const amount = balance / 2n;
+    const ixs = await buildWithdrawTreasuryMintIxs(client, { mint, recipient, amount });
+    const txSig = await buildAndSendTransaction(client, ixs);
+    console.log("withdraw_treasury_mint tx:", txSig);
+
+    const [destination] = await findAssociatedTokenPda({
+      mint,
+      owner: recipient,
+      tokenProgram: TOKEN_PROGRAM_ADDRESS,
+    });
+    const { value: destinationBalance } = await client.rpc
+      .getTokenAccountBalance(destination)
+      .send();
+    expect(BigInt(destinationBalance.amount)).to.equal(amount);
+
+    const { value: treasuryAfter } = await client.rpc.getTokenAccountBalance(treasuryAddress).send();
+    expect(BigInt(treasuryAfter.amount)).to.equal(balance - amount);
+  });
+
+  it("--- set_revenue_split ix ---", async () => {
+    const [partner, integrator] = await Promise.all([
+      generateKeyPairSigner(),
+      generateKeyPairSigner(),
+    ]);
+    const recipients = [
+      { recipient: partner.address, weightBps: 7_000 },
+      { recipient: integrator.address, weightBps: 3_000 },
+    ];
+
+    // weights must sum to 10_000
+    const invalidIx = await buildSetRevenueSplitIx(client, [
+      { recipient: partner.address, weightBps: 7_000 },
+    ]);
+    await expectTransactionFailure(client, [invalidIx]);
+
+    const ix = await buildSetRevenueSplitIx(client, recipients);
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("set_revenue_split tx:", txSig);
+
+    const revenueSplit = await fetchRevenueSplit(client.rpc, await getRevenueSplitPda());
+    expect(revenueSplit.discriminator).to.equal(constants.REVENUE_SPLIT_ACCOUNT_DISCRIMINATOR);
+    expect(revenueSplit.recipients).to.deep.equal(recipients);
+  });
+
+  it("--- distribute_treasury ix ---", async () => {
+    const mint = constants.WSOL_MINT;
+    const revenueSplit = await fetchRevenueSplit(client.rpc, await getRevenueSplitPda());
+    const recipients = revenueSplit.recipients.map(({ recipient }) => recipient);
+
+    const treasuryAddress = await getTreasuryPda(mint);
+    const { value: treasuryBefore } = await client.rpc
+      .getTokenAccountBalance(treasuryAddress)
+      .send();
+    const balance = BigInt(treasuryBefore.amount);
+
+    // the crank is permissionless
+    const ixs = await buildDistributeTreasuryIxs({ payer: accounts.farmer, mint, recipients });
+    const txSig = await buildAndSendTransaction(client, ixs, { feePayer: accounts.farmer });
+    console.log("distribute_treasury tx:", txSig);
+
+    const { value: treasuryAfter } = await client.rpc.getTokenAccountBalance(treasuryAddress).send();
+    expect(BigInt(treasuryAfter.amount)).to.equal(0n);
+
+    // the first recipient gets the rounding dust
+    const integratorShare = (balance * 3_000n) / 10_000n;
+    const expectedShares = [balance - integratorShare, integratorShare];
+    for (const [i, recipient] of recipients.entries()) {
+      const [destination] = await findAssociatedTokenPda({
+        mint,
+        owner: recipient,
+        tokenProgram: TOKEN_PROGRAM_ADDRESS,
+      });
+      const { value: destinationBalance } = await client.rpc
+        .getTokenAccountBalance(destination)
+        .send();
+      expect(BigInt(destinationBalance.amount)).to.equal(expectedShares[i]);
+    }
+  });
 
   it("--- withdraw_treasury ix ---", async () => {
     // treasury PDAs into them.
@@ -343,4 +3108,179 @@ describe("reflex", () => {
     const txSig = await buildAndSendTransaction(client, ixs);
     console.log("withdraw_treasury tx:", txSig);
   });
+
+  it("--- migrate_account ix ---", async () => {
+    // seed a pre-versioning FarmerPosition (18 bytes, no header)
+    const legacyPosition = (await generateKeyPairSigner()).address;
+    const legacyData = Buffer.alloc(18);
+    legacyData.writeBigUInt64LE(42n, 0); // yes_staked
+    legacyData.writeBigUInt64LE(7n, 8); // no_staked
+    legacyData.writeUInt8(1, 16); // is_initialized
+    legacyData.writeUInt8(254, 17); // bump
+
+    const lamports = await client.rpc.getMinimumBalanceForRentExemption(18n).send();
+    await setAccount(legacyPosition, {
+      lamports,
+      data: legacyData,
+      owner: constants.PROGRAM_ID,
+    });
+
+    const ix = await buildMigrateAccountIx({ account: legacyPosition, payer: client.wallet });
+    const txSig = await buildAndSendTransaction(client, [ix]);
+    console.log("migrate_account tx:", txSig);
+
+    const position = await fetchFarmerPosition(client.rpc, legacyPosition);
+    expect(position.discriminator).to.equal(constants.FARMER_POSITION_ACCOUNT_DISCRIMINATOR);
+    expect(position.version).to.equal(5);
+    expect(position.yesStaked).to.equal(42n);
+    expect(position.noStaked).to.equal(7n);
+    expect(position.isInitialized).to.equal(1);
+    expect(position.bump).to.equal(254);
+    // no delegate nor recipient, i.e. the zero address
+    expect(position.delegate).to.equal(SYSTEM_PROGRAM_ADDRESS);
+    expect(position.rewardRecipient).to.equal(SYSTEM_PROGRAM_ADDRESS);
+    // nothing queued for withdrawal
+    expect(position.pendingYesWithdrawal).to.equal(0n);
+    expect(position.pendingNoWithdrawal).to.equal(0n);
+    // never locked, and the stakes weigh 1x
+    expect(position.lockedUntil).to.equal(0n);
+    expect(position.yesWeight).to.equal(42n);
+    expect(position.noWeight).to.equal(7n);
+    expect(position.lockedYesStaked).to.equal(0n);
+    expect(position.lockedNoStaked).to.equal(0n);
+
+    // migrating an up-to-date account is a no-op
+    const configAddress = await getConfigPda();
+    const configBefore = await fetchConfig(client.rpc, configAddress);
+    const configIx = await buildMigrateAccountIx({ account: configAddress, payer: client.wallet });
+    await buildAndSendTransaction(client, [configIx]);
+
+    const configAfter = await fetchConfig(client.rpc, configAddress);
+    expect(configAfter).to.deep.equal(configBefore);
+
+    // seed a pre-versioning Config (37 bytes, no header) and a pre-versioning Market
+    // (181 bytes, no header) whose address is derived from its raw id
+    const legacyConfig = (await generateKeyPairSigner()).address;
+    const legacyConfigData = Buffer.alloc(37);
+    legacyConfigData.set(getAddressEncoder().encode(client.wallet.address), 0);
+    legacyConfigData.writeUInt16LE(100, 32); // fee_bps
+    legacyConfigData.writeUInt16LE(250, 34); // briber_fee_bps
+    legacyConfigData.writeUInt8(253, 36); // bump
+
+    const legacyId = "KXLEGACY-26JAN19";
+    const [legacyMarket, legacyMarketBump] = await getProgramDerivedAddress({
+      programAddress: constants.PROGRAM_ID,
+      seeds: [constants.MARKET_SEED, Buffer.from(legacyId, "utf-8")],
+    });
+    const [legacyYesMint, legacyNoMint] = await Promise.all(
+      [0, 1].map(async () => (await generateKeyPairSigner()).address),
+    );
+    const legacyMarketData = Buffer.alloc(181);
+    legacyMarketData.set(getAddressEncoder().encode(accounts.briber.address), 0);
+    legacyMarketData.set(getAddressEncoder().encode(constants.WSOL_MINT), 32);
+    legacyMarketData.set(getAddressEncoder().encode(legacyYesMint), 64);
+    legacyMarketData.set(getAddressEncoder().encode(legacyNoMint), 96);
+    legacyMarketData.writeBigUInt64LE(1_000_000n, 128); // total_incentive_amount
+    legacyMarketData.writeBigUInt64LE(42n, 136); // total_yes_staked
+    legacyMarketData.writeBigUInt64LE(7n, 144); // total_no_staked
+    legacyMarketData.writeBigInt64LE(1_700_000_000n, 168); // creation_timestamp
+    legacyMarketData.writeUInt16LE(100, 176); // fee_bps
+    legacyMarketData.writeUInt8(legacyMarketBump, 180); // bump, status and resolution are 0
+
+    for (const [address, data] of [
+      [legacyConfig, legacyConfigData],
+      [legacyMarket, legacyMarketData],
+    ] as const) {
+      await setAccount(address, {
+        lamports: await client.rpc.getMinimumBalanceForRentExemption(BigInt(data.length)).send(),
+        data,
+        owner: constants.PROGRAM_ID,
+      });
+    }
+
+    // a legacy market cannot be migrated without its raw id
+    const noIdIx = await buildMigrateAccountIx({ account: legacyMarket, payer: client.wallet });
+    await expectTransactionFailure(client, [noIdIx]);
+
+    // the sequence migrates the Config before the Market and skips up-to-date accounts
+    const { value: configAccount } = await client.rpc
+      .getAccountInfo(configAddress, { encoding: "base64" })
+      .send();
+    const sequenceIxs = await buildMigrationSequenceIxs(
+      [
+        { address: legacyMarket, data: legacyMarketData },
+        { address: configAddress, data: Buffer.from(configAccount!.data[0], "base64") },
+        { address: legacyConfig, data: legacyConfigData },
+      ],
+      client.wallet,
+      new Map([[legacyMarket, legacyId]]),
+    );
+    expect(sequenceIxs.map((ix) => ix.accounts![1].address)).to.deep.equal([
+      legacyConfig,
+      legacyMarket,
+    ]);
+    await buildAndSendTransaction(client, sequenceIxs);
+
+    const migratedConfig = await fetchConfig(client.rpc, legacyConfig);
+    expect(migratedConfig.discriminator).to.equal(constants.CONFIG_ACCOUNT_DISCRIMINATOR);
+    expect(migratedConfig.version).to.equal(5);
+    expect(migratedConfig.authority).to.equal(client.wallet.address);
+    expect(migratedConfig.feeBps).to.equal(100);
+    expect(migratedConfig.briberFeeBps).to.equal(250);
+    expect(migratedConfig.bump).to.equal(253);
+    // the briber fee range is pinned to the old fixed fee
+    expect(migratedConfig.minBriberFeeBps).to.equal(250);
+    expect(migratedConfig.maxBriberFeeBps).to.equal(250);
+    // authority-only creation, no oracle, and no dispute window
+    expect(migratedConfig.marketCreationMode).to.equal(MarketCreationMode.Authority);
+    expect(migratedConfig.settlementOracle).to.equal(SYSTEM_PROGRAM_ADDRESS);
+    expect(migratedConfig.disputeWindow).to.equal(0n);
+
+    const migratedMarket = await fetchMarket(client.rpc, legacyMarket);
+    expect(migratedMarket.discriminator).to.equal(constants.MARKET_ACCOUNT_DISCRIMINATOR);
+    expect(migratedMarket.version).to.equal(15);
+    expect(migratedMarket.briber).to.equal(accounts.briber.address);
+    expect(migratedMarket.totalIncentiveAmount).to.equal(1_000_000n);
+    expect(migratedMarket.totalYesStaked).to.equal(42n);
+    expect(migratedMarket.totalNoStaked).to.equal(7n);
+    expect(migratedMarket.creationTimestamp).to.equal(1_700_000_000n);
+    expect(migratedMarket.feeBps).to.equal(100);
+    expect(migratedMarket.bump).to.equal(legacyMarketBump);
+    // the raw id is kept as the seed
+    expect(migratedMarket.id).to.equal(legacyId);
+    expect(Buffer.from(migratedMarket.seed)).to.deep.equal(Buffer.from(legacyId, "utf-8"));
+    // vault and receipt mint bumps are found from the market address
+    const [vaultBumps, receiptBumps] = await Promise.all(
+      [constants.MARKET_SEED, constants.RECEIPT_MINT_SEED].map((prefix) =>
+        Promise.all(
+          [constants.WSOL_MINT, legacyYesMint, legacyNoMint].map(async (mint) => {
+            const [, bump] = await getProgramDerivedAddress({
+              programAddress: constants.PROGRAM_ID,
+              seeds: [
+                prefix,
+                getAddressEncoder().encode(legacyMarket),
+                getAddressEncoder().encode(mint),
+              ],
+            });
+            return bump;
+          }),
+        ),
+      ),
+    );
+    expect([
+      migratedMarket.incentiveVaultBump,
+      migratedMarket.yesVaultBump,
+      migratedMarket.noVaultBump,
+    ]).to.deep.equal(vaultBumps);
+    expect([migratedMarket.receiptYesMintBump, migratedMarket.receiptNoMintBump]).to.deep.equal(
+      receiptBumps.slice(1),
+    );
+    // the briber funded everything and collects the fees
+    expect(migratedMarket.feeRecipient).to.equal(accounts.briber.address);
+    expect(migratedMarket.briberContribution).to.equal(1_000_000n);
+    // the stakes weigh 1x and nothing is queued for withdrawal
+    expect(migratedMarket.totalYesWeight).to.equal(42n);
+    expect(migratedMarket.totalNoWeight).to.equal(7n);
+    expect(migratedMarket.pendingWithdrawals).to.equal(0n);
+  });
 });
@@ -0,0 +1,64 @@
+import { signature } from "@solana/kit";
+
+import { Client } from "./client";
+import { constants } from "./constants";
+
+/**
+ * Compute-unit ceilings for the hot-path instructions.
+ *
+ * They re-derive their vault and receipt mint addresses with `derive_address`
+ * from the bumps stored on `Market` instead of searching for the bump with
+ * `find_program_address`.
+ */
+export const COMPUTE_UNIT_BUDGETS = {
+  STAKE_OUTCOME_TOKEN: 20_000,
+  UNSTAKE_OUTCOME_TOKEN: 12_000,
+  CLAIM_REWARDS: 18_000,
+  CLAIM_FEES: 10_000,
+  CANCEL_MARKET: 30_000,
+};
+
+export type ComputeUnitInstruction = keyof typeof COMPUTE_UNIT_BUDGETS;
+
+/**
+ * Compute units the same test transactions consumed before the bumps were
+ * stored on `Market`, when every address was re-derived with
+ * `find_program_address`. Record them from the `<instruction> compute units:`
+ * logs of a run against a build of 68e8694^, the commit preceding the vault
+ * bumps; every instruction must have one.
+ */
+export const COMPUTE_UNIT_BASELINES: Partial<Record<ComputeUnitInstruction, number>> = {};
+
+/**
+ * Returns the most compute units `instruction` may consume: its ceiling, and
+ * strictly less than its recorded baseline so the saving cannot regress.
+ * Throws if the baseline has not been recorded.
+ */
+export function getComputeUnitLimit(instruction: ComputeUnitInstruction): number {
+  const baseline = COMPUTE_UNIT_BASELINES[instruction];
+  if (baseline === undefined) {
+    throw new Error(`No compute unit baseline recorded for ${instruction}`);
+  }
+  return Math.min(COMPUTE_UNIT_BUDGETS[instruction], baseline - 1);
+}
+
+/**
+ * Returns the compute units consumed by the top-level Reflex instruction of a
+ * confirmed transaction (CPIs included), read from the runtime logs so that
+ * helper instructions in the same transaction (e.g. ATA creation) are ignored.
+ */
+export async function getComputeUnitsConsumed(client: Client, txSig: string): Promise<number> {
+  const tx = await client.rpc
+    .getTransaction(signature(txSig), {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    })
+    .send();
+
+  const consumedLog = new RegExp(`^Program ${constants.PROGRAM_ID} consumed (\\d+) of \\d+`);
+  for (const log of tx?.meta?.logMessages ?? []) {
+    const match = consumedLog.exec(log);
+    if (match) return Number(match[1]);
+  }
+  throw new Error(`No compute units logged for ${constants.PROGRAM_ID} in ${txSig}`);
+}
@@ -4,12 +4,25 @@ import { address } from "@solana/kit";
 const PROGRAM_ID = address("4ZegtDo8WG6e2PAswLhnGXYDS5TGkniVCKXDrDX12KYX");
 const WSOL_MINT = address("So11111111111111111111111111111111111111112");
 const USDC_MINT = address("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const ED25519_PROGRAM_ID = address("Ed25519SigVerify111111111111111111111111111");
+const INSTRUCTIONS_SYSVAR = address("Sysvar1nstructions1111111111111111111111111");
 
 // seeds
 const CONFIG_SEED = Buffer.from("config");
 const TREASURY_SEED = Buffer.from("treasury");
 const MARKET_SEED = Buffer.from("market");
 const FARMER_POSITION_SEED = Buffer.from("farmer_position");
+const MARKET_METADATA_SEED = Buffer.from("market_metadata");
+const BRIBER_ALLOWLIST_SEED = Buffer.from("briber_allowlist");
+const BRIBER_CONTRIBUTION_SEED = Buffer.from("briber_contribution");
+const REVENUE_SPLIT_SEED = Buffer.from("revenue_split");
+const SETTLEMENT_COMMITTEE_SEED = Buffer.from("settlement_committee");
+const SETTLEMENT_VOTES_SEED = Buffer.from("settlement_votes");
+const RECEIPT_MINT_SEED = Buffer.from("receipt_mint");
+const OUTCOME_MINTS_SEED = Buffer.from("outcome_mints");
+
+// signed message domains
+const ATTESTATION_DOMAIN = Buffer.from("reflex:settle_market:v1");
 
 // instruction discriminators
 const INITIALIZE_DISCRIMINATOR = 0;
@@ -23,15 +36,68 @@ const STAKE_OUTCOME_TOKEN_DISCRIMINATOR = 7;
 const UNSTAKE_OUTCOME_TOKEN_DISCRIMINATOR = 8;
 const CLAIM_REWARDS_DISCRIMINATOR = 9;
 const UPDATE_CONFIG_DISCRIMINATOR = 10;
+const MIGRATE_ACCOUNT_DISCRIMINATOR = 11;
+const CREATE_MARKET_METADATA_DISCRIMINATOR = 12;
+const UPDATE_MARKET_METADATA_DISCRIMINATOR = 13;
+const UPDATE_MARKET_FEE_DISCRIMINATOR = 14;
+const ADD_BRIBER_TO_ALLOWLIST_DISCRIMINATOR = 15;
+const REMOVE_BRIBER_FROM_ALLOWLIST_DISCRIMINATOR = 16;
+const TRANSFER_MARKET_OWNERSHIP_DISCRIMINATOR = 17;
+const ACCEPT_MARKET_OWNERSHIP_DISCRIMINATOR = 18;
+const SET_FEE_RECIPIENT_DISCRIMINATOR = 19;
+const WITHDRAW_TREASURY_MINT_DISCRIMINATOR = 20;
+const SET_REVENUE_SPLIT_DISCRIMINATOR = 21;
+const DISTRIBUTE_TREASURY_DISCRIMINATOR = 22;
+const SET_SETTLEMENT_ORACLE_DISCRIMINATOR = 23;
+const SETTLE_MARKET_WITH_ATTESTATION_DISCRIMINATOR = 24;
+const SET_SETTLEMENT_COMMITTEE_DISCRIMINATOR = 25;
+const VOTE_SETTLEMENT_DISCRIMINATOR = 26;
+const SET_DISPUTE_WINDOW_DISCRIMINATOR = 27;
+const CLAIM_REWARDS_BATCH_DISCRIMINATOR = 28;
+const SWITCH_SIDE_DISCRIMINATOR = 29;
+const CREATE_RECEIPT_MINT_DISCRIMINATOR = 30;
+const REDEEM_RECEIPT_DISCRIMINATOR = 31;
+const STAKE_OUTCOME_TOKEN_FOR_DISCRIMINATOR = 32;
+const SET_POSITION_DELEGATE_DISCRIMINATOR = 33;
+const SET_UNSTAKE_RULES_DISCRIMINATOR = 34;
+const WITHDRAW_UNSTAKED_DISCRIMINATOR = 35;
+const SET_LOCKUP_TIERS_DISCRIMINATOR = 36;
+const SET_IMBALANCE_BOOST_DISCRIMINATOR = 37;
+const RECLAIM_REWARD_OVERFLOW_DISCRIMINATOR = 38;
+const REGISTER_OUTCOME_MINTS_DISCRIMINATOR = 39;
+const CLAIM_PENALTIES_DISCRIMINATOR = 40;
+
+// account discriminators
+const CONFIG_ACCOUNT_DISCRIMINATOR = 1;
+const MARKET_ACCOUNT_DISCRIMINATOR = 2;
+const FARMER_POSITION_ACCOUNT_DISCRIMINATOR = 3;
+const MARKET_METADATA_ACCOUNT_DISCRIMINATOR = 4;
+const BRIBER_ALLOWLIST_ACCOUNT_DISCRIMINATOR = 5;
+const BRIBER_CONTRIBUTION_ACCOUNT_DISCRIMINATOR = 6;
+const REVENUE_SPLIT_ACCOUNT_DISCRIMINATOR = 7;
+const SETTLEMENT_COMMITTEE_ACCOUNT_DISCRIMINATOR = 8;
+const SETTLEMENT_VOTES_ACCOUNT_DISCRIMINATOR = 9;
+const OUTCOME_MINTS_ACCOUNT_DISCRIMINATOR = 10;
 
 export const constants = {
   PROGRAM_ID,
   WSOL_MINT,
   USDC_MINT,
+  ED25519_PROGRAM_ID,
+  INSTRUCTIONS_SYSVAR,
   CONFIG_SEED,
   TREASURY_SEED,
   MARKET_SEED,
   FARMER_POSITION_SEED,
+  MARKET_METADATA_SEED,
+  BRIBER_ALLOWLIST_SEED,
+  BRIBER_CONTRIBUTION_SEED,
+  REVENUE_SPLIT_SEED,
+  SETTLEMENT_COMMITTEE_SEED,
+  SETTLEMENT_VOTES_SEED,
+  RECEIPT_MINT_SEED,
+  OUTCOME_MINTS_SEED,
+  ATTESTATION_DOMAIN,
   INITIALIZE_DISCRIMINATOR,
   CREATE_MARKET_DISCRIMINATOR,
   ADD_INCENTIVES_DISCRIMINATOR,
@@ -43,4 +109,44 @@ export const constants = {
   UNSTAKE_OUTCOME_TOKEN_DISCRIMINATOR,
   CLAIM_REWARDS_DISCRIMINATOR,
   UPDATE_CONFIG_DISCRIMINATOR,
+  MIGRATE_ACCOUNT_DISCRIMINATOR,
+  CREATE_MARKET_METADATA_DISCRIMINATOR,
+  UPDATE_MARKET_METADATA_DISCRIMINATOR,
+  UPDATE_MARKET_FEE_DISCRIMINATOR,
+  ADD_BRIBER_TO_ALLOWLIST_DISCRIMINATOR,
+  REMOVE_BRIBER_FROM_ALLOWLIST_DISCRIMINATOR,
+  TRANSFER_MARKET_OWNERSHIP_DISCRIMINATOR,
+  ACCEPT_MARKET_OWNERSHIP_DISCRIMINATOR,
+  SET_FEE_RECIPIENT_DISCRIMINATOR,
+  WITHDRAW_TREASURY_MINT_DISCRIMINATOR,
+  SET_REVENUE_SPLIT_DISCRIMINATOR,
+  DISTRIBUTE_TREASURY_DISCRIMINATOR,
+  SET_SETTLEMENT_ORACLE_DISCRIMINATOR,
+  SETTLE_MARKET_WITH_ATTESTATION_DISCRIMINATOR,
+  SET_SETTLEMENT_COMMITTEE_DISCRIMINATOR,
+  VOTE_SETTLEMENT_DISCRIMINATOR,
+  SET_DISPUTE_WINDOW_DISCRIMINATOR,
+  CLAIM_REWARDS_BATCH_DISCRIMINATOR,
+  SWITCH_SIDE_DISCRIMINATOR,
+  CREATE_RECEIPT_MINT_DISCRIMINATOR,
+  REDEEM_RECEIPT_DISCRIMINATOR,
+  STAKE_OUTCOME_TOKEN_FOR_DISCRIMINATOR,
+  SET_POSITION_DELEGATE_DISCRIMINATOR,
+  SET_UNSTAKE_RULES_DISCRIMINATOR,
+  WITHDRAW_UNSTAKED_DISCRIMINATOR,
+  SET_LOCKUP_TIERS_DISCRIMINATOR,
+  SET_IMBALANCE_BOOST_DISCRIMINATOR,
+  RECLAIM_REWARD_OVERFLOW_DISCRIMINATOR,
+  REGISTER_OUTCOME_MINTS_DISCRIMINATOR,
+  CLAIM_PENALTIES_DISCRIMINATOR,
+  CONFIG_ACCOUNT_DISCRIMINATOR,
+  MARKET_ACCOUNT_DISCRIMINATOR,
+  FARMER_POSITION_ACCOUNT_DISCRIMINATOR,
+  MARKET_METADATA_ACCOUNT_DISCRIMINATOR,
+  BRIBER_ALLOWLIST_ACCOUNT_DISCRIMINATOR,
+  BRIBER_CONTRIBUTION_ACCOUNT_DISCRIMINATOR,
+  REVENUE_SPLIT_ACCOUNT_DISCRIMINATOR,
+  SETTLEMENT_COMMITTEE_ACCOUNT_DISCRIMINATOR,
+  SETTLEMENT_VOTES_ACCOUNT_DISCRIMINATOR,
+  OUTCOME_MINTS_ACCOUNT_DISCRIMINATOR,
 };
@@ -0,0 +1,6 @@
+// Mirrors src/errors.rs — ReflexError, returned as custom program errors
+export enum ReflexError {
+  StakeBelowMinimum = 0,
+  FarmerStakeCapExceeded = 1,
+  MarketStakeCapExceeded = 2,
+}
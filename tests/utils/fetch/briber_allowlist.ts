@@ -0,0 +1,37 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  getAddressCodec,
+  getStructCodec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/states/briber_allowlist.rs — BriberAllowlist #[repr(C)]
+// Layout (35 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
+//   [u8; 32]  briber
+//   [u8;  1]  bump
+const briberAllowlistCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["briber", getAddressCodec()],
+  ["bump", getU8Codec()],
+]);
+
+export type BriberAllowlistAccount = ReturnType<typeof briberAllowlistCodec.decode>;
+
+/**
+ * Returns the decoded BriberAllowlist account, or `null` if the account does not exist.
+ * Use this when validating that a briber was removed.
+ */
+export async function fetchMaybeBriberAllowlist(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<BriberAllowlistAccount | null> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) return null;
+  return briberAllowlistCodec.decode(account.data);
+}
@@ -0,0 +1,57 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  getAddressCodec,
+  getBooleanCodec,
+  getStructCodec,
+  getU64Codec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/states/briber_contribution.rs — BriberContribution #[repr(C)]
+// Layout (84 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
+//   [u8; 32]  market
+//   [u8; 32]  contributor
+//   [u8;  8]  amount (u64 LE)
+//   [u8;  1]  bump
+//   [u8;  1]  fees_claimed
+//   [u8;  8]  returned_overflow (u64 LE, reward overflow already paid back)
+const briberContributionCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["market", getAddressCodec()],
+  ["contributor", getAddressCodec()],
+  ["amount", getU64Codec()],
+  ["bump", getU8Codec()],
+  ["feesClaimed", getBooleanCodec()],
+  ["returnedOverflow", getU64Codec()],
+]);
+
+export type BriberContributionAccount = ReturnType<typeof briberContributionCodec.decode>;
+
+/**
+ * Returns the decoded BriberContribution account, or `null` if the account does not exist.
+ * Use this when validating that a contribution was refunded or claimed.
+ */
+export async function fetchMaybeBriberContribution(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<BriberContributionAccount | null> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) return null;
+  return briberContributionCodec.decode(account.data);
+}
+
+/** Returns the decoded BriberContribution account. Throws if the account does not exist. */
+export async function fetchBriberContribution(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<BriberContributionAccount> {
+  const contribution = await fetchMaybeBriberContribution(rpc, address);
+  if (!contribution) throw new Error(`BriberContribution account not found: ${address}`);
+  return contribution;
+}
@@ -5,29 +5,58 @@ import {
   assertAccountExists,
   fetchEncodedAccount,
   getAddressCodec,
+  getI64Codec,
   getStructCodec,
   getU16Codec,
   getU8Codec,
 } from "@solana/kit";
 
+// Mirrors src/states/config.rs — MarketCreationMode #[repr(u8)]
+export enum MarketCreationMode {
+  Authority = 0,
+  Allowlist = 1,
+  Permissionless = 2,
+}
+
 // Mirrors src/states/config.rs — Config #[repr(C)]
-// Layout (37 bytes):
+// Layout (84 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
 //   [u8; 32]  authority
 //   [u8;  2]  fee_bps       (u16 LE)
 //   [u8;  2]  briber_fee_bps (u16 LE)
 //   [u8;  1]  bump
+//   [u8;  2]  min_briber_fee_bps (u16 LE)
+//   [u8;  2]  max_briber_fee_bps (u16 LE)
+//   [u8;  1]  market_creation_mode
+//   [u8; 32]  settlement_oracle (zeroed when disabled)
+//   [u8;  8]  dispute_window (i64 LE, seconds before claims open)
 const configCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
   ["authority", getAddressCodec()],
   ["feeBps", getU16Codec()],
   ["briberFeeBps", getU16Codec()],
   ["bump", getU8Codec()],
+  ["minBriberFeeBps", getU16Codec()],
+  ["maxBriberFeeBps", getU16Codec()],
+  ["marketCreationMode", getU8Codec()],
+  ["settlementOracle", getAddressCodec()],
+  ["disputeWindow", getI64Codec()],
 ]);
 
 export type ConfigAccount = {
+  discriminator: number;
+  version: number;
   authority: Address;
   feeBps: number;
   briberFeeBps: number;
   bump: number;
+  minBriberFeeBps: number;
+  maxBriberFeeBps: number;
+  marketCreationMode: MarketCreationMode;
+  settlementOracle: Address;
+  disputeWindow: bigint;
 };
 
 export async function fetchConfig(
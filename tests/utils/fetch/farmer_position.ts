@@ -3,22 +3,50 @@ import {
   Rpc,
   SolanaRpcApi,
   fetchEncodedAccount,
+  getAddressCodec,
+  getI64Codec,
   getStructCodec,
   getU64Codec,
   getU8Codec,
 } from "@solana/kit";
 
 // Mirrors src/states/farmer_position.rs — FarmerPosition #[repr(C)]
-// Layout (18 bytes):
+// Layout (156 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
 //   [u8;  8]  yes_staked  (u64 LE)
 //   [u8;  8]  no_staked   (u64 LE)
 //   [u8;  1]  is_initialized
 //   [u8;  1]  bump
+//   [u8; 32]  delegate          (zero if none)
+//   [u8; 32]  reward_recipient  (zero for the farmer)
+//   [u8;  8]  last_stake_timestamp    (i64 LE)
+//   [u8;  8]  pending_yes_withdrawal  (u64 LE, queued by the unstake cooldown)
+//   [u8;  8]  pending_no_withdrawal   (u64 LE, queued by the unstake cooldown)
+//   [u8;  8]  withdrawal_unlock       (i64 LE)
+//   [u8;  8]  locked_until  (i64 LE, the locked stake stays until then, 0 if never locked)
+//   [u8;  8]  yes_weight    (u64 LE, YES stake boosted by lockup multipliers)
+//   [u8;  8]  no_weight     (u64 LE, NO stake boosted by lockup multipliers)
+//   [u8;  8]  locked_yes_staked  (u64 LE, part of the YES stake held until locked_until)
+//   [u8;  8]  locked_no_staked   (u64 LE, part of the NO stake held until locked_until)
 const farmerPositionCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
   ["yesStaked", getU64Codec()],
   ["noStaked", getU64Codec()],
   ["isInitialized", getU8Codec()],
   ["bump", getU8Codec()],
+  ["delegate", getAddressCodec()],
+  ["rewardRecipient", getAddressCodec()],
+  ["lastStakeTimestamp", getI64Codec()],
+  ["pendingYesWithdrawal", getU64Codec()],
+  ["pendingNoWithdrawal", getU64Codec()],
+  ["withdrawalUnlock", getI64Codec()],
+  ["lockedUntil", getI64Codec()],
+  ["yesWeight", getU64Codec()],
+  ["noWeight", getU64Codec()],
+  ["lockedYesStaked", getU64Codec()],
+  ["lockedNoStaked", getU64Codec()],
 ]);
 
 export type FarmerPositionAccount = ReturnType<typeof farmerPositionCodec.decode>;
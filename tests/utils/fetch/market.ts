@@ -3,7 +3,11 @@ import {
   Rpc,
   SolanaRpcApi,
   fetchEncodedAccount,
+  fixCodecSize,
   getAddressCodec,
+  getArrayCodec,
+  getBooleanCodec,
+  getBytesCodec,
   getI64Codec,
   getStructCodec,
   getU16Codec,
@@ -11,10 +15,15 @@ import {
   getU8Codec,
 } from "@solana/kit";
 
+// Mirrors src/utils/constants.rs — MAX_LOCKUP_TIERS
+const MAX_LOCKUP_TIERS = 4;
+
 // Mirrors src/states/market.rs — MarketStatus #[repr(u8)]
 export enum MarketStatus {
   Open = 0,
   Settled = 1,
+  Disputed = 2,
+  PendingSettlement = 3,
 }
 
 // Mirrors src/states/market.rs — MarketResolution #[repr(u8)]
@@ -25,7 +34,9 @@ export enum MarketResolution {
 }
 
 // Mirrors src/states/market.rs — Market #[repr(C)]
-// Layout (181 bytes):
+// Layout (537 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
 //   [u8; 32]  briber
 //   [u8; 32]  incentive_mint
 //   [u8; 32]  outcome_yes_mint
@@ -40,7 +51,46 @@ export enum MarketResolution {
 //   [u8;  1]  status                 (u8)
 //   [u8;  1]  resolution             (u8)
 //   [u8;  1]  bump
+//   [u8;  1]  incentive_vault_bump
+//   [u8;  1]  yes_vault_bump
+//   [u8;  1]  no_vault_bump
+//   [u8; 32]  seed        (sha256(id), or the raw id for legacy markets)
+//   [u8;  1]  seed_len
+//   [u8; 64]  id          (utf8 ticker, zero padded)
+//   [u8;  1]  id_len
+//   [u8; 32]  pending_briber (zeroed when no transfer is pending)
+//   [u8; 32]  fee_recipient
+//   [u8;  8]  briber_contribution    (u64 LE, incentives funded by the briber)
+//   [u8;  8]  claimed_fees           (u64 LE)
+//   [u8;  1]  contributor_count      (co-funders with a BriberContribution)
+//   [u8;  1]  briber_fees_claimed    (bool)
+//   [u8;  2]  protocol_fee_bps       (u16 LE, 0xffff until snapshotted on migrated markets)
+//   [u8;  8]  settled_at             (i64 LE, when the current resolution was set)
+//   [u8;  8]  dispute_window         (i64 LE, seconds before claims open)
+//   [u8;  8]  unstake_cooldown       (i64 LE, seconds, 0 unstakes immediately)
+//   [u8;  2]  early_exit_penalty_bps (u16 LE, 0 queues unstakes for the cooldown)
+//   [u8;  8]  yes_penalties          (u64 LE, early-exit penalties kept for YES stakers)
+//   [u8;  8]  no_penalties           (u64 LE, early-exit penalties kept for NO stakers)
+//   [u8;  1]  lockup_tier_count
+//   [u8; 8 * 4]  lockup_durations       (i64 LE, seconds, zeroed past lockup_tier_count)
+//   [u8; 2 * 4]  lockup_multipliers_bps (u16 LE, 10_000 is 1x)
+//   [u8;  8]  total_yes_weight       (u64 LE, YES stakes boosted by lockup multipliers)
+//   [u8;  8]  total_no_weight        (u64 LE, NO stakes boosted by lockup multipliers)
+//   [u8;  2]  imbalance_boost_bps    (u16 LE, extra weight for the thinner side, 0 disables it)
+//   [u8;  8]  min_stake              (u64 LE, 0 for any)
+//   [u8;  8]  max_stake_per_farmer   (u64 LE, per side, 0 for no cap)
+//   [u8;  8]  max_total_stake        (u64 LE, per side, 0 for no cap)
+//   [u8;  2]  max_reward_bps_per_farmer (u16 LE, of the incentives, 0 for no cap)
+//   [u8;  8]  reward_overflow        (u64 LE, rewards over the cap, owed to later claimers)
+//   [u8;  8]  claimed_weight         (u64 LE, winning weight that claimed on a capped market)
+//   [u8;  8]  pending_withdrawals    (u64 LE, queued unstakes, u64::MAX if unknown after migration)
+//   [u8;  8]  returned_overflow      (u64 LE, overflow handed back to the funders so far)
+//   [u8;  8]  briber_returned_overflow (u64 LE, part of it the briber reclaimed)
+//   [u8;  1]  receipt_yes_mint_bump
+//   [u8;  1]  receipt_no_mint_bump
 const marketCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
   ["briber", getAddressCodec()],
   ["incentiveMint", getAddressCodec()],
   ["outcomeYesMint", getAddressCodec()],
@@ -55,9 +105,53 @@ const marketCodec = getStructCodec([
   ["status", getU8Codec()],
   ["resolution", getU8Codec()],
   ["bump", getU8Codec()],
+  ["incentiveVaultBump", getU8Codec()],
+  ["yesVaultBump", getU8Codec()],
+  ["noVaultBump", getU8Codec()],
+  ["seed", fixCodecSize(getBytesCodec(), 32)],
+  ["seedLen", getU8Codec()],
+  ["id", fixCodecSize(getBytesCodec(), 64)],
+  ["idLen", getU8Codec()],
+  ["pendingBriber", getAddressCodec()],
+  ["feeRecipient", getAddressCodec()],
+  ["briberContribution", getU64Codec()],
+  ["claimedFees", getU64Codec()],
+  ["contributorCount", getU8Codec()],
+  ["briberFeesClaimed", getBooleanCodec()],
+  ["protocolFeeBps", getU16Codec()],
+  ["settledAt", getI64Codec()],
+  ["disputeWindow", getI64Codec()],
+  ["unstakeCooldown", getI64Codec()],
+  ["earlyExitPenaltyBps", getU16Codec()],
+  ["yesPenalties", getU64Codec()],
+  ["noPenalties", getU64Codec()],
+  ["lockupTierCount", getU8Codec()],
+  ["lockupDurations", getArrayCodec(getI64Codec(), { size: MAX_LOCKUP_TIERS })],
+  ["lockupMultipliersBps", getArrayCodec(getU16Codec(), { size: MAX_LOCKUP_TIERS })],
+  ["totalYesWeight", getU64Codec()],
+  ["totalNoWeight", getU64Codec()],
+  ["imbalanceBoostBps", getU16Codec()],
+  ["minStake", getU64Codec()],
+  ["maxStakePerFarmer", getU64Codec()],
+  ["maxTotalStake", getU64Codec()],
+  ["maxRewardBpsPerFarmer", getU16Codec()],
+  ["rewardOverflow", getU64Codec()],
+  ["claimedWeight", getU64Codec()],
+  ["pendingWithdrawals", getU64Codec()],
+  ["returnedOverflow", getU64Codec()],
+  ["briberReturnedOverflow", getU64Codec()],
+  ["receiptYesMintBump", getU8Codec()],
+  ["receiptNoMintBump", getU8Codec()],
 ]);
 
+export type LockupTier = {
+  duration: bigint;
+  multiplierBps: number;
+};
+
 export type MarketAccount = {
+  discriminator: number;
+  version: number;
   briber: Address;
   incentiveMint: Address;
   outcomeYesMint: Address;
@@ -72,6 +166,39 @@ export type MarketAccount = {
   status: MarketStatus;
   resolution: MarketResolution;
   bump: number;
+  incentiveVaultBump: number;
+  yesVaultBump: number;
+  noVaultBump: number;
+  seed: Uint8Array;
+  id: string;
+  pendingBriber: Address;
+  feeRecipient: Address;
+  briberContribution: bigint;
+  claimedFees: bigint;
+  contributorCount: number;
+  briberFeesClaimed: boolean;
+  protocolFeeBps: number;
+  settledAt: bigint;
+  disputeWindow: bigint;
+  unstakeCooldown: bigint;
+  earlyExitPenaltyBps: number;
+  yesPenalties: bigint;
+  noPenalties: bigint;
+  lockupTiers: LockupTier[];
+  totalYesWeight: bigint;
+  totalNoWeight: bigint;
+  imbalanceBoostBps: number;
+  minStake: bigint;
+  maxStakePerFarmer: bigint;
+  maxTotalStake: bigint;
+  maxRewardBpsPerFarmer: number;
+  rewardOverflow: bigint;
+  claimedWeight: bigint;
+  pendingWithdrawals: bigint;
+  returnedOverflow: bigint;
+  briberReturnedOverflow: bigint;
+  receiptYesMintBump: number;
+  receiptNoMintBump: number;
 };
 
 /**
@@ -84,9 +211,23 @@ export async function fetchMaybeMarket(
 ): Promise<MarketAccount | null> {
   const account = await fetchEncodedAccount(rpc, address);
   if (!account.exists) return null;
-  const decoded = marketCodec.decode(account.data);
+  const {
+    seed,
+    seedLen,
+    id,
+    idLen,
+    lockupTierCount,
+    lockupDurations,
+    lockupMultipliersBps,
+    ...decoded
+  } = marketCodec.decode(account.data);
   return {
     ...decoded,
+    seed: seed.slice(0, seedLen),
+    lockupTiers: lockupDurations
+      .slice(0, lockupTierCount)
+      .map((duration, i) => ({ duration, multiplierBps: lockupMultipliersBps[i] })),
+    id: Buffer.from(id.slice(0, idLen)).toString("utf-8"),
     status: decoded.status as MarketStatus,
     resolution: decoded.resolution as MarketResolution,
   };
@@ -0,0 +1,89 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  fixCodecSize,
+  getAddressCodec,
+  getBytesCodec,
+  getI64Codec,
+  getStructCodec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/states/market_metadata.rs — MarketMetadata #[repr(C)]
+// Layout (471 bytes):
+//   [u8;   1]  discriminator
+//   [u8;   1]  version
+//   [u8;  32]  market
+//   [u8;  64]  ticker    (utf8, zero padded)
+//   [u8;   1]  ticker_len
+//   [u8; 128]  title     (utf8, zero padded)
+//   [u8;   1]  title_len
+//   [u8;  32]  category  (utf8, zero padded)
+//   [u8;   1]  category_len
+//   [u8;   8]  expected_resolution_timestamp (i64 LE)
+//   [u8; 200]  uri       (utf8, zero padded)
+//   [u8;   1]  uri_len
+//   [u8;   1]  bump
+const marketMetadataCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["market", getAddressCodec()],
+  ["ticker", fixCodecSize(getBytesCodec(), 64)],
+  ["tickerLen", getU8Codec()],
+  ["title", fixCodecSize(getBytesCodec(), 128)],
+  ["titleLen", getU8Codec()],
+  ["category", fixCodecSize(getBytesCodec(), 32)],
+  ["categoryLen", getU8Codec()],
+  ["expectedResolutionTimestamp", getI64Codec()],
+  ["uri", fixCodecSize(getBytesCodec(), 200)],
+  ["uriLen", getU8Codec()],
+  ["bump", getU8Codec()],
+]);
+
+export type MarketMetadataAccount = {
+  discriminator: number;
+  version: number;
+  market: Address;
+  ticker: string;
+  title: string;
+  category: string;
+  expectedResolutionTimestamp: bigint;
+  uri: string;
+  bump: number;
+};
+
+const decodeString = (bytes: Uint8Array, len: number) =>
+  Buffer.from(bytes.slice(0, len)).toString("utf-8");
+
+/** Returns the decoded MarketMetadata account, or `null` if the account does not exist. */
+export async function fetchMaybeMarketMetadata(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<MarketMetadataAccount | null> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) return null;
+  const decoded = marketMetadataCodec.decode(account.data);
+  return {
+    discriminator: decoded.discriminator,
+    version: decoded.version,
+    market: decoded.market,
+    ticker: decodeString(decoded.ticker, decoded.tickerLen),
+    title: decodeString(decoded.title, decoded.titleLen),
+    category: decodeString(decoded.category, decoded.categoryLen),
+    expectedResolutionTimestamp: decoded.expectedResolutionTimestamp,
+    uri: decodeString(decoded.uri, decoded.uriLen),
+    bump: decoded.bump,
+  };
+}
+
+/** Returns the decoded MarketMetadata account. Throws if the account does not exist. */
+export async function fetchMarketMetadata(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<MarketMetadataAccount> {
+  const metadata = await fetchMaybeMarketMetadata(rpc, address);
+  if (!metadata) throw new Error(`MarketMetadata account not found: ${address}`);
+  return metadata;
+}
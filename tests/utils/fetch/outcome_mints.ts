@@ -0,0 +1,38 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  getAddressCodec,
+  getStructCodec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/states/outcome_mints.rs — OutcomeMints #[repr(C)]
+// Layout (67 bytes):
+//   [u8;  1]  discriminator
+//   [u8;  1]  version
+//   [u8; 32]  outcome_yes_mint
+//   [u8; 32]  outcome_no_mint
+//   [u8;  1]  bump
+const outcomeMintsCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["outcomeYesMint", getAddressCodec()],
+  ["outcomeNoMint", getAddressCodec()],
+  ["bump", getU8Codec()],
+]);
+
+export type OutcomeMintsAccount = ReturnType<typeof outcomeMintsCodec.decode>;
+
+/**
+ * Returns the decoded OutcomeMints account, or `null` if the ticker has none registered.
+ */
+export async function fetchMaybeOutcomeMints(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<OutcomeMintsAccount | null> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) return null;
+  return outcomeMintsCodec.decode(account.data);
+}
@@ -0,0 +1,61 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  getAddressCodec,
+  getArrayCodec,
+  getStructCodec,
+  getU16Codec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/utils/constants.rs — MAX_REVENUE_RECIPIENTS
+const MAX_REVENUE_RECIPIENTS = 5;
+
+// Mirrors src/states/revenue_split.rs — RevenueSplit #[repr(C)]
+// Layout (174 bytes):
+//   [u8;       1]  discriminator
+//   [u8;       1]  version
+//   [u8;       1]  recipient_count
+//   [u8; 32 * 5]  recipients  (zeroed past recipient_count)
+//   [u8;  2 * 5]  weights_bps (u16 LE, sum to 10_000)
+//   [u8;       1]  bump
+const revenueSplitCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["recipientCount", getU8Codec()],
+  ["recipients", getArrayCodec(getAddressCodec(), { size: MAX_REVENUE_RECIPIENTS })],
+  ["weightsBps", getArrayCodec(getU16Codec(), { size: MAX_REVENUE_RECIPIENTS })],
+  ["bump", getU8Codec()],
+]);
+
+export type RevenueRecipient = {
+  recipient: Address;
+  weightBps: number;
+};
+
+export type RevenueSplitAccount = {
+  discriminator: number;
+  version: number;
+  recipients: RevenueRecipient[];
+  bump: number;
+};
+
+/** Returns the decoded RevenueSplit account. Throws if the account does not exist. */
+export async function fetchRevenueSplit(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<RevenueSplitAccount> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) throw new Error(`RevenueSplit account not found: ${address}`);
+  const { recipientCount, recipients, weightsBps, ...decoded } = revenueSplitCodec.decode(
+    account.data,
+  );
+  return {
+    ...decoded,
+    recipients: recipients
+      .slice(0, recipientCount)
+      .map((recipient, i) => ({ recipient, weightBps: weightsBps[i] })),
+  };
+}
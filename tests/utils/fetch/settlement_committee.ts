@@ -0,0 +1,49 @@
+import {
+  Address,
+  Rpc,
+  SolanaRpcApi,
+  fetchEncodedAccount,
+  getAddressCodec,
+  getArrayCodec,
+  getStructCodec,
+  getU8Codec,
+} from "@solana/kit";
+
+// Mirrors src/utils/constants.rs — MAX_COMMITTEE_MEMBERS
+const MAX_COMMITTEE_MEMBERS = 7;
+
+// Mirrors src/states/settlement_committee.rs — SettlementCommittee #[repr(C)]
+// Layout (229 bytes):
+//   [u8;       1]  discriminator
+//   [u8;       1]  version
+//   [u8;       1]  threshold
+//   [u8;       1]  member_count
+//   [u8; 32 * 7]  members (zeroed past member_count)
+//   [u8;       1]  bump
+const settlementCommitteeCodec = getStructCodec([
+  ["discriminator", getU8Codec()],
+  ["version", getU8Codec()],
+  ["threshold", getU8Codec()],
+  ["memberCount", getU8Codec()],
+  ["members", getArrayCodec(getAddressCodec(), { size: MAX_COMMITTEE_MEMBERS })],
+  ["bump", getU8Codec()],
+]);
+
+export type SettlementCommitteeAccount = {
+  discriminator: number;
+  version: number;
+  threshold: number;
+  members: Address[];
+  bump: number;
+};
+
+/** Returns the decoded SettlementCommittee account. Throws if the account does not exist. */
+export async function fetchSettlementCommittee(
+  rpc: Rpc<SolanaRpcApi>,
+  address: Address,
+): Promise<SettlementCommitteeAccount> {
+  const account = await fetchEncodedAccount(rpc, address);
+  if (!account.exists) throw new Error(`SettlementCommittee account not found: ${address}`);
+  const { memberCount, members, ...decoded } = settlementCommitteeCodec.decode(account.data);
+  return { ...decoded, members: members.slice(0, memberCount) };
+}
@@ -1,5 +1,6 @@
 import { Address, getAddressEncoder, getProgramDerivedAddress } from "@solana/kit";
 
+import { createHash } from "crypto";
 import { constants } from "./constants";
 
 const getConfigPda = async () => {
@@ -20,10 +21,13 @@ const getTreasuryPda = async (mint: Address) => {
   return treasury;
 };
 
+// market ids may exceed the 32-byte seed limit, so the PDA is derived from sha256(id)
+const getMarketSeed = (id: string) => createHash("sha256").update(id, "utf-8").digest();
+
 const getMarketPda = async (id: string) => {
   const [market, _] = await getProgramDerivedAddress({
     programAddress: constants.PROGRAM_ID,
-    seeds: [constants.MARKET_SEED, Buffer.from(id, "utf-8")],
+    seeds: [constants.MARKET_SEED, getMarketSeed(id)],
   });
 
   return market;
@@ -55,4 +59,104 @@ const getFarmerPositionPda = async (market: Address, farmer: Address) => {
   return farmerPosition;
 };
 
-export { getConfigPda, getTreasuryPda, getMarketPda, getMarketVaultPda, getFarmerPositionPda };
+const getMarketMetadataPda = async (market: Address) => {
+  const [marketMetadata, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.MARKET_METADATA_SEED, getAddressEncoder().encode(market)],
+  });
+
+  return marketMetadata;
+};
+
+const getBriberAllowlistPda = async (briber: Address) => {
+  const [briberAllowlist, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.BRIBER_ALLOWLIST_SEED, getAddressEncoder().encode(briber)],
+  });
+
+  return briberAllowlist;
+};
+
+const getOutcomeMintsPda = async (id: string) => {
+  const [outcomeMints, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.OUTCOME_MINTS_SEED, getMarketSeed(id)],
+  });
+
+  return outcomeMints;
+};
+
+const getBriberContributionPda = async (market: Address, contributor: Address) => {
+  const [briberContribution, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [
+      constants.BRIBER_CONTRIBUTION_SEED,
+      getAddressEncoder().encode(market),
+      getAddressEncoder().encode(contributor),
+    ],
+  });
+
+  return briberContribution;
+};
+
+const getRevenueSplitPda = async () => {
+  const [revenueSplit, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.REVENUE_SPLIT_SEED],
+  });
+
+  return revenueSplit;
+};
+
+const getSettlementCommitteePda = async () => {
+  const [settlementCommittee, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.SETTLEMENT_COMMITTEE_SEED],
+  });
+
+  return settlementCommittee;
+};
+
+const getSettlementVotesPda = async (market: Address) => {
+  const [settlementVotes, _] = await getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [constants.SETTLEMENT_VOTES_SEED, getAddressEncoder().encode(market)],
+  });
+
+  return settlementVotes;
+};
+
+// the program stores the bump on the market, so tests can check it
+const findReceiptMintPda = (market: Address, outcomeMint: Address) =>
+  getProgramDerivedAddress({
+    programAddress: constants.PROGRAM_ID,
+    seeds: [
+      constants.RECEIPT_MINT_SEED,
+      getAddressEncoder().encode(market),
+      getAddressEncoder().encode(outcomeMint),
+    ],
+  });
+
+const getReceiptMintPda = async (market: Address, outcomeMint: Address) => {
+  const [receiptMint, _] = await findReceiptMintPda(market, outcomeMint);
+
+  return receiptMint;
+};
+
+export {
+  getConfigPda,
+  getTreasuryPda,
+  getMarketSeed,
+  getMarketPda,
+  getMarketVaultPda,
+  getFarmerPositionPda,
+  getMarketMetadataPda,
+  getBriberAllowlistPda,
+  getOutcomeMintsPda,
+  getBriberContributionPda,
+  getRevenueSplitPda,
+  getSettlementCommitteePda,
+  getSettlementVotesPda,
+  findReceiptMintPda,
+  getReceiptMintPda,
+};
@@ -0,0 +1,25 @@
+import { Address } from "@solana/kit";
+
+/**
+ * Overwrites an account on the local Surfpool network through the
+ * `surfnet_setAccount` cheatcode. Used to seed accounts with layouts the
+ * current program can no longer create (e.g. pre-versioning state).
+ */
+export async function setAccount(
+  address: Address,
+  { lamports, data, owner }: { lamports: bigint; data: Buffer; owner: Address },
+): Promise<void> {
+  const response = await fetch("http://127.0.0.1:8899", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({
+      jsonrpc: "2.0",
+      id: 1,
+      method: "surfnet_setAccount",
+      params: [address, { lamports: Number(lamports), data: data.toString("hex"), owner }],
+    }),
+  });
+
+  const { error } = await response.json();
+  if (error) throw new Error(`surfnet_setAccount failed: ${error.message}`);
+}
@@ -1,6 +1,7 @@
 import {
   Instruction,
   MessageSigner,
+  SOLANA_ERROR__INSTRUCTION_ERROR__CUSTOM,
   TransactionSigner,
   addSignersToTransactionMessage,
   appendTransactionMessageInstructions,
@@ -8,6 +9,7 @@ import {
   assertIsTransactionWithBlockhashLifetime,
   createTransactionMessage,
   getSignatureFromTransaction,
+  isSolanaError,
   pipe,
   setTransactionMessageFeePayerSigner,
   setTransactionMessageLifetimeUsingBlockhash,
@@ -54,3 +56,36 @@ export async function buildAndSendTransaction(
   }
   return txSig;
 }
+
+/**
+ * Sends a transaction that the program is expected to reject.
+ * Throws if it lands successfully, or if it fails with another custom program
+ * error than `customErrorCode` when given (see `ReflexError`).
+ */
+export async function expectTransactionFailure(
+  client: Client,
+  instructions: Instruction[],
+  options: SendOptions = {},
+  customErrorCode?: number,
+): Promise<void> {
+  let error: unknown;
+  try {
+    await buildAndSendTransaction(client, instructions, options);
+  } catch (err) {
+    error = err ?? new Error("Transaction failed");
+  }
+  if (!error) throw new Error("Transaction was expected to fail but succeeded");
+  if (customErrorCode !== undefined && getCustomErrorCode(error) !== customErrorCode) {
+    throw new Error(`Expected custom program error ${customErrorCode}`, { cause: error });
+  }
+}
+
+/** Walks the error causes for a custom program error code. */
+function getCustomErrorCode(error: unknown): number | undefined {
+  for (let cause = error; cause; cause = (cause as Error).cause) {
+    if (isSolanaError(cause, SOLANA_ERROR__INSTRUCTION_ERROR__CUSTOM)) {
+      return cause.context.code;
+    }
+  }
+  return undefined;
+}